@@ -0,0 +1,343 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+pub const PASSPHRASE_ENV: &str = "MARKIT_PASSPHRASE";
+pub const SECRETS_PASSPHRASE_ENV: &str = "MARKIT_SECRETS_PASSPHRASE";
+const SOCKET_DIR: &str = "agent";
+const SOCKET_FILE: &str = "agent.sock";
+
+pub trait PassphraseCache {
+    fn passphrase(&self, store: &str) -> Option<String>;
+    fn remember(&self, store: &str, passphrase: &str, ttl: Duration) -> Result<(), String>;
+    fn forget(&self) -> Result<bool, String>;
+}
+
+pub struct AgentClient {
+    socket: PathBuf,
+}
+
+impl AgentClient {
+    pub fn new(state_dir: &Path) -> Self {
+        Self {
+            socket: state_dir.join(SOCKET_DIR).join(SOCKET_FILE),
+        }
+    }
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::{
+        collections::HashMap,
+        env, fs,
+        io::{self, BufRead, BufReader, Write},
+        os::unix::{
+            fs::{DirBuilderExt, PermissionsExt},
+            net::{UnixListener, UnixStream},
+            process::CommandExt,
+        },
+        path::Path,
+        process::{Command, Stdio},
+        sync::{
+            Arc, Mutex,
+            atomic::{AtomicBool, Ordering},
+        },
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use super::{AgentClient, PassphraseCache};
+
+    const STARTUP_GRACE: Duration = Duration::from_secs(5);
+    const CLIENT_TIMEOUT: Duration = Duration::from_secs(1);
+
+    type Cache = Arc<Mutex<HashMap<String, (String, Instant)>>>;
+
+    impl AgentClient {
+        fn request(&self, line: &str) -> io::Result<String> {
+            let mut stream = UnixStream::connect(&self.socket)?;
+            stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+            writeln!(stream, "{}", line)?;
+
+            let mut reply = String::new();
+            BufReader::new(stream).read_line(&mut reply)?;
+            Ok(reply.trim_end_matches('\n').to_string())
+        }
+
+        fn spawn(&self) -> io::Result<()> {
+            Command::new(env::current_exe()?)
+                .arg("agent")
+                .arg("--socket")
+                .arg(&self.socket)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .process_group(0)
+                .spawn()?;
+
+            for _ in 0..50 {
+                if UnixStream::connect(&self.socket).is_ok() {
+                    return Ok(());
+                }
+                thread::sleep(Duration::from_millis(50));
+            }
+            Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "the passphrase agent did not start",
+            ))
+        }
+    }
+
+    impl PassphraseCache for AgentClient {
+        fn passphrase(&self, store: &str) -> Option<String> {
+            let reply = self.request(&format!("get {}", store)).ok()?;
+            reply.strip_prefix('+').map(str::to_string)
+        }
+
+        fn remember(&self, store: &str, passphrase: &str, ttl: Duration) -> Result<(), String> {
+            if passphrase.contains('\n') {
+                return Err("Passphrases with line breaks cannot be cached.".to_string());
+            }
+
+            let line = format!("set {} {} {}", ttl.as_secs(), store, passphrase);
+            let reply = match self.request(&line) {
+                Ok(reply) => reply,
+                Err(_) => {
+                    self.spawn().map_err(|e| e.to_string())?;
+                    self.request(&line).map_err(|e| e.to_string())?
+                }
+            };
+
+            match reply.as_str() {
+                "+" => Ok(()),
+                other => Err(format!("Unexpected reply from the agent: {}", other)),
+            }
+        }
+
+        fn forget(&self) -> Result<bool, String> {
+            match self.request("stop") {
+                Ok(_) => Ok(true),
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        io::ErrorKind::NotFound | io::ErrorKind::ConnectionRefused
+                    ) =>
+                {
+                    Ok(false)
+                }
+                Err(e) => Err(e.to_string()),
+            }
+        }
+    }
+
+    pub fn serve(socket: &Path) -> io::Result<()> {
+        if UnixStream::connect(socket).is_ok() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "a passphrase agent is already running",
+            ));
+        }
+        if let Some(parent) = socket.parent() {
+            fs::DirBuilder::new()
+                .recursive(true)
+                .mode(0o700)
+                .create(parent)?;
+            if fs::metadata(parent)?.permissions().mode() & 0o077 != 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    format!(
+                        "'{}' is accessible by other users; refusing to put the agent socket there",
+                        parent.display()
+                    ),
+                ));
+            }
+        }
+        let _ = fs::remove_file(socket);
+
+        let listener = UnixListener::bind(socket)?;
+        fs::set_permissions(socket, fs::Permissions::from_mode(0o600))?;
+
+        let cache: Cache = Arc::default();
+        let stopped = Arc::new(AtomicBool::new(false));
+        let reaper = (
+            Arc::clone(&cache),
+            Arc::clone(&stopped),
+            socket.to_path_buf(),
+        );
+        thread::spawn(move || {
+            let (cache, stopped, socket) = reaper;
+            let started = Instant::now();
+            while !stopped.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_secs(1));
+                let mut entries = cache.lock().unwrap_or_else(|e| e.into_inner());
+                entries.retain(|_, (_, expires)| *expires > Instant::now());
+                if entries.is_empty() && started.elapsed() > STARTUP_GRACE {
+                    drop(entries);
+                    if let Ok(mut stream) = UnixStream::connect(&socket) {
+                        let _ = writeln!(stream, "stop");
+                    }
+                    return;
+                }
+            }
+        });
+
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else {
+                continue;
+            };
+            if handle(stream, &cache).unwrap_or(false) {
+                break;
+            }
+        }
+
+        stopped.store(true, Ordering::Relaxed);
+        fs::remove_file(socket)
+    }
+
+    fn handle(stream: UnixStream, cache: &Cache) -> io::Result<bool> {
+        stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+        stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line)?;
+        let line = line.trim_end_matches('\n');
+        let mut entries = cache.lock().unwrap_or_else(|e| e.into_inner());
+
+        let (reply, stop) = match line.split_once(' ') {
+            Some(("get", store)) => match entries.get(store) {
+                Some((passphrase, expires)) if *expires > Instant::now() => {
+                    (format!("+{}", passphrase), false)
+                }
+                _ => ("-".to_string(), false),
+            },
+            Some(("set", rest)) => match rest.splitn(3, ' ').collect::<Vec<_>>()[..] {
+                [ttl, store, passphrase] => match ttl
+                    .parse()
+                    .ok()
+                    .and_then(|secs| Instant::now().checked_add(Duration::from_secs(secs)))
+                {
+                    Some(expires) => {
+                        entries.insert(store.to_string(), (passphrase.to_string(), expires));
+                        ("+".to_string(), false)
+                    }
+                    None => ("-".to_string(), false),
+                },
+                _ => ("-".to_string(), false),
+            },
+            None if line == "stop" => {
+                entries.clear();
+                ("+".to_string(), true)
+            }
+            _ => ("-".to_string(), false),
+        };
+
+        writeln!(&stream, "{}", reply)?;
+        Ok(stop)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::agent::AgentClient;
+
+        fn start_agent(dir: &Path) -> (AgentClient, thread::JoinHandle<io::Result<()>>) {
+            let client = AgentClient::new(dir);
+            let socket = client.socket.clone();
+            let server = thread::spawn(move || serve(&socket));
+            while UnixStream::connect(&client.socket).is_err() {
+                thread::sleep(Duration::from_millis(10));
+            }
+            (client, server)
+        }
+
+        #[test]
+        fn test_agent_caches_passphrases_per_store() {
+            let dir = tempfile::tempdir().unwrap();
+            let (client, server) = start_agent(dir.path());
+
+            client
+                .remember("work", "correct horse battery", Duration::from_secs(60))
+                .unwrap();
+            client
+                .remember("old", "expired", Duration::from_secs(0))
+                .unwrap();
+
+            assert_eq!(
+                client.passphrase("work").as_deref(),
+                Some("correct horse battery")
+            );
+            assert_eq!(client.passphrase("old"), None);
+            assert_eq!(client.passphrase("default"), None);
+
+            assert_eq!(client.forget(), Ok(true));
+            server.join().unwrap().unwrap();
+            assert!(!client.socket.exists());
+            assert_eq!(client.passphrase("work"), None);
+            assert_eq!(client.forget(), Ok(false));
+        }
+
+        #[test]
+        fn test_socket_is_private() {
+            let dir = tempfile::tempdir().unwrap();
+            let (client, server) = start_agent(dir.path());
+
+            let mode = fs::metadata(&client.socket).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+            let parent = client.socket.parent().unwrap();
+            let mode = fs::metadata(parent).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+
+            client.forget().unwrap();
+            server.join().unwrap().unwrap();
+        }
+
+        #[test]
+        fn test_idle_client_does_not_block_the_agent() {
+            let dir = tempfile::tempdir().unwrap();
+            let (client, server) = start_agent(dir.path());
+            client
+                .remember("work", "hunter2", Duration::from_secs(60))
+                .unwrap();
+
+            let _idle = UnixStream::connect(&client.socket).unwrap();
+            assert_eq!(client.passphrase("work").as_deref(), Some("hunter2"));
+
+            client.forget().unwrap();
+            server.join().unwrap().unwrap();
+        }
+
+        #[test]
+        fn test_refuses_shared_socket_directory() {
+            let dir = tempfile::tempdir().unwrap();
+            fs::set_permissions(dir.path(), fs::Permissions::from_mode(0o755)).unwrap();
+
+            assert!(serve(&dir.path().join("agent.sock")).is_err());
+        }
+    }
+}
+
+#[cfg(unix)]
+pub use unix::serve;
+
+#[cfg(not(unix))]
+impl PassphraseCache for AgentClient {
+    fn passphrase(&self, _: &str) -> Option<String> {
+        None
+    }
+
+    fn remember(&self, _: &str, _: &str, _: Duration) -> Result<(), String> {
+        Err("The passphrase agent is only available on Unix.".to_string())
+    }
+
+    fn forget(&self) -> Result<bool, String> {
+        Ok(false)
+    }
+}
+
+#[cfg(not(unix))]
+pub fn serve(_: &Path) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "the passphrase agent is only available on Unix",
+    ))
+}
@@ -1,18 +1,101 @@
+use std::path::PathBuf;
+
+use chrono::Duration;
 use clap::{Parser, Subcommand};
 
+use crate::{
+    commands::{store::parse_ttl, trash::parse_age},
+    models::SnippetKind,
+    storage::{backend::BackendKind, merge::Side},
+    template::parse_assignment,
+};
+
 #[derive(Parser)]
 #[command(name = "markit")]
 #[command(about = "A CLI snippet runner/bookmarker", long_about = None)]
 #[command(version)]
 pub struct Cli {
+    #[arg(
+        long,
+        global = true,
+        value_name = "NAME",
+        help = "Snippet store to use (defaults to $MARKIT_STORE or the default store)"
+    )]
+    pub store: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "DIR",
+        help = "Keep all markit data in this directory (defaults to $MARKIT_HOME or XDG locations)"
+    )]
+    pub home: Option<PathBuf>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
-    #[command(about = "Save a new snippet interactively")]
-    Save { name: String },
+    #[command(
+        about = "Save a new snippet (interactively unless content is given via flags or stdin)"
+    )]
+    Save {
+        name: String,
+
+        #[arg(short, long, help = "Snippet description")]
+        description: Option<String>,
+
+        #[arg(
+            short,
+            long = "tag",
+            help = "Tag to add (repeatable, or comma-separated)"
+        )]
+        tag: Vec<String>,
+
+        #[arg(
+            short,
+            long,
+            conflicts_with_all = ["exec", "no_exec"],
+            help = "Snippet kind (shell, bash, sh, zsh, python, node, sql, plain)"
+        )]
+        kind: Option<SnippetKind>,
+
+        #[arg(
+            long,
+            conflicts_with = "no_exec",
+            help = "Save as an executable shell snippet"
+        )]
+        exec: bool,
+
+        #[arg(long, help = "Save as a plain, non-executable snippet")]
+        no_exec: bool,
+
+        #[arg(
+            short,
+            long,
+            conflicts_with_all = ["from_file", "from_history"],
+            help = "Snippet content"
+        )]
+        content: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            conflicts_with = "from_history",
+            help = "Read snippet content from a file"
+        )]
+        from_file: Option<PathBuf>,
+
+        #[arg(long, help = "Pick the snippet content from your shell history")]
+        from_history: bool,
+
+        #[arg(
+            long,
+            help = "Save to the project's .markit.yml (found by walking up from here) instead of the store"
+        )]
+        local: bool,
+    },
 
     #[command(about = "List all saved snippets (optionally filter by tag or search)")]
     List {
@@ -29,24 +112,123 @@ pub enum Commands {
     },
 
     #[command(about = "Show the full content of a snippet")]
-    Show { name: String },
+    Show {
+        #[arg(help = "Snippet name or ID")]
+        name: String,
+    },
 
     #[command(about = "Run a saved snippet")]
-    Run { name: String },
+    Run {
+        #[arg(help = "Snippet name or ID")]
+        name: String,
+
+        #[arg(
+            long = "set",
+            value_name = "KEY=VALUE",
+            value_parser = parse_assignment,
+            help = "Set a placeholder value instead of prompting for it"
+        )]
+        set: Vec<(String, String)>,
+
+        #[arg(long, help = "Database to run SQL snippets against")]
+        db: Option<String>,
+    },
 
     #[command(about = "Edit a saved snippet in your default editor")]
     Edit { name: String },
 
-    #[command(about = "Delete a snippet with confirmation prompt")]
+    #[command(about = "Move a snippet to the trash (or delete it permanently with --purge)")]
     Delete {
         name: String,
 
         #[arg(short, long, help = "Force delete without confirmation")]
         force: bool,
+
+        #[arg(long, help = "Delete permanently instead of moving to the trash")]
+        purge: bool,
+    },
+
+    #[command(about = "Move a snippet to another store")]
+    Move {
+        name: String,
+
+        #[arg(long, value_name = "STORE", help = "Store to move the snippet to")]
+        to: String,
+    },
+
+    #[command(about = "Manage snippet stores")]
+    Store {
+        #[command(subcommand)]
+        action: StoreAction,
+    },
+
+    #[command(
+        about = "Trust a project's .markit.yml so its snippets and variable commands are used"
+    )]
+    Allow {
+        #[arg(help = "Project file to trust (defaults to the nearest .markit.yml)")]
+        path: Option<PathBuf>,
+    },
+
+    #[command(about = "Stop trusting a project's .markit.yml")]
+    Deny {
+        #[arg(help = "Project file to stop trusting (defaults to the nearest .markit.yml)")]
+        path: Option<PathBuf>,
+    },
+
+    #[command(about = "Convert the store to another storage backend")]
+    Migrate {
+        #[arg(
+            long,
+            value_name = "BACKEND",
+            help = "Backend to convert to (yaml, dir, sqlite)"
+        )]
+        to: BackendKind,
+    },
+
+    #[command(about = "Manage secrets referenced from snippets as ${secret:NAME}")]
+    Secret {
+        #[command(subcommand)]
+        action: SecretAction,
+    },
+
+    #[command(about = "Pull and push snippets against the configured git remote")]
+    Sync {
+        #[arg(
+            long,
+            value_name = "SIDE",
+            help = "Resolve conflicting snippets by keeping this side (local, remote)"
+        )]
+        prefer: Option<Side>,
+    },
+
+    #[command(about = "Undo the last change to your snippets")]
+    Undo,
+
+    #[command(about = "Redo the last undone change")]
+    Redo,
+
+    #[command(about = "Restore a snippet from the trash")]
+    Undelete { name: String },
+
+    #[command(about = "Inspect or empty the trash")]
+    Trash {
+        #[command(subcommand)]
+        action: TrashAction,
     },
 
     #[command(about = "Copy a snippet's content to the clipboard")]
-    Copy { name: String },
+    Copy {
+        name: String,
+
+        #[arg(
+            long = "set",
+            value_name = "KEY=VALUE",
+            value_parser = parse_assignment,
+            help = "Set a placeholder value instead of prompting for it"
+        )]
+        set: Vec<(String, String)>,
+    },
 
     #[command(about = "Export all snippets to a YAML file")]
     Export { path: String },
@@ -54,6 +236,178 @@ pub enum Commands {
     #[command(about = "Import snippets from a YAML file")]
     Import { path: String },
 
+    #[command(about = "Three-way merge snippets from another YAML file into the store")]
+    Merge {
+        #[arg(help = "YAML file with the other copy of your snippets")]
+        path: String,
+
+        #[arg(
+            long,
+            value_name = "FILE",
+            help = "Common ancestor of both copies (without it, the newer updated_at wins)"
+        )]
+        base: Option<String>,
+    },
+
     #[command(about = "Restore a previous backup")]
-    Restore,
+    Restore {
+        #[arg(
+            short,
+            long,
+            help = "Pick individual snippets to restore instead of the whole backup"
+        )]
+        select: bool,
+    },
+
+    #[command(about = "Manage automatic backups")]
+    Backups {
+        #[command(subcommand)]
+        action: BackupsAction,
+    },
+
+    #[command(about = "List recorded revisions of a snippet")]
+    History { name: String },
+
+    #[command(about = "Show a unified diff between a revision and the current snippet")]
+    Diff {
+        name: String,
+
+        #[arg(help = "Revision to compare against (defaults to the previous one)")]
+        rev: Option<u32>,
+    },
+
+    #[command(about = "Revert a snippet to an earlier revision")]
+    Revert { name: String, rev: u32 },
+
+    #[command(hide = true)]
+    Agent {
+        #[arg(long)]
+        socket: PathBuf,
+    },
+}
+
+impl Commands {
+    pub fn operation(&self) -> &'static str {
+        match self {
+            Commands::Save { .. } => "save",
+            Commands::Edit { .. } => "edit",
+            Commands::Delete { .. } => "delete",
+            Commands::Undelete { .. } => "undelete",
+            Commands::Import { .. } => "import",
+            Commands::Merge { .. } => "merge",
+            Commands::Restore { .. } => "restore",
+            Commands::Revert { .. } => "revert",
+            Commands::Move { .. } => "move",
+            Commands::Sync { .. } => "sync",
+            _ => "change",
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum SecretAction {
+    #[command(about = "Add or replace a secret")]
+    Set {
+        name: String,
+
+        #[arg(help = "Secret value (prompted without echo when omitted)")]
+        value: Option<String>,
+    },
+
+    #[command(about = "Print a secret's value")]
+    Get { name: String },
+
+    #[command(about = "List secret names")]
+    List,
+
+    #[command(about = "Remove a secret")]
+    Rm { name: String },
+}
+
+#[derive(Subcommand)]
+pub enum BackupsAction {
+    #[command(about = "List backups with snippet counts and changes since the previous one")]
+    List,
+
+    #[command(about = "Show the snippets stored in a backup")]
+    Show {
+        #[arg(help = "Backup file name or a unique prefix of it, e.g. 2025-01-01T10")]
+        backup: String,
+    },
+
+    #[command(about = "Remove backups that fall outside the retention policy")]
+    Prune {
+        #[arg(long, help = "Only report what would be removed")]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TrashAction {
+    #[command(about = "List deleted snippets")]
+    List,
+
+    #[command(about = "Permanently delete snippets from the trash")]
+    Empty {
+        #[arg(
+            long,
+            value_name = "AGE",
+            value_parser = parse_age,
+            help = "Only remove snippets deleted longer ago than this (e.g. 30d, 12h, 2w)"
+        )]
+        older_than: Option<Duration>,
+
+        #[arg(short, long, help = "Skip the confirmation prompt")]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum StoreAction {
+    #[command(about = "List all stores")]
+    List,
+
+    #[command(about = "Create a new store")]
+    Create { name: String },
+
+    #[command(about = "Remove a store and everything in it")]
+    Remove {
+        name: String,
+
+        #[arg(short, long, help = "Skip the confirmation prompt")]
+        force: bool,
+    },
+
+    #[command(about = "Show or set the store used when --store is not given")]
+    Default { name: Option<String> },
+
+    #[command(about = "Encrypt a store with a passphrase")]
+    Encrypt {
+        #[arg(help = "Store to encrypt (defaults to the active store)")]
+        name: Option<String>,
+    },
+
+    #[command(about = "Remove the encryption from a store")]
+    Decrypt {
+        #[arg(help = "Store to decrypt (defaults to the active store)")]
+        name: Option<String>,
+    },
+
+    #[command(about = "Cache the passphrase of an encrypted store for a while")]
+    Unlock {
+        #[arg(help = "Store to unlock (defaults to the active store)")]
+        name: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "AGE",
+            default_value = "15m",
+            value_parser = parse_ttl,
+            help = "How long to remember the passphrase, e.g. 15m or 8h (at most 24h)"
+        )]
+        ttl: std::time::Duration,
+    },
+
+    #[command(about = "Forget all cached passphrases")]
+    Lock,
 }
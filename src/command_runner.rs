@@ -1,15 +1,334 @@
+use std::{
+    collections::HashMap,
+    io::Write,
+    process::{Command, ExitStatus, Stdio},
+};
+
+use crate::{config::SqlConfig, models::SnippetKind};
+
+pub struct RunRequest<'a> {
+    pub kind: SnippetKind,
+    pub content: &'a str,
+    pub database: Option<&'a str>,
+    pub env: &'a [(String, String)],
+}
+
 pub trait CommandRunner {
-    fn run(&self, command: &str) -> Result<std::process::ExitStatus, std::io::Error>;
+    fn run(&self, request: &RunRequest) -> Result<ExitStatus, std::io::Error>;
+    fn capture(&self, command: &str) -> Result<String, std::io::Error>;
+}
+
+enum ContentInput {
+    Argument,
+    Stdin,
+}
+
+pub struct Interpreter {
+    program: String,
+    args: Vec<String>,
+    input: ContentInput,
+    default_database: Option<String>,
 }
 
-pub struct ShellCommandRunner;
+impl Interpreter {
+    pub fn new(program: impl Into<String>, args: &[&str]) -> Self {
+        Self {
+            program: program.into(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            input: ContentInput::Argument,
+            default_database: None,
+        }
+    }
+
+    pub fn stdin(program: impl Into<String>, args: &[&str]) -> Self {
+        Self {
+            input: ContentInput::Stdin,
+            ..Self::new(program, args)
+        }
+    }
+
+    pub fn sql(config: &SqlConfig) -> Option<Self> {
+        let (program, args) = config.client.split_first()?;
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+        Some(Self {
+            default_database: config.database.clone(),
+            ..Self::stdin(program.clone(), &args)
+        })
+    }
+
+    fn command(&self, database: Option<&str>) -> Result<Command, std::io::Error> {
+        if database.is_some() && !self.args.iter().any(|arg| arg.contains("{db}")) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "'{}' takes no database (add a {{db}} argument to the client command)",
+                    self.program
+                ),
+            ));
+        }
+        let database = database.or(self.default_database.as_deref());
+        let mut process = Command::new(&self.program);
+
+        for arg in &self.args {
+            if arg.contains("{db}") {
+                let database = database.ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "no database selected (pass --db or set `database` on the snippet)",
+                    )
+                })?;
+                process.arg(arg.replace("{db}", database));
+            } else {
+                process.arg(arg);
+            }
+        }
+
+        Ok(process)
+    }
+
+    fn status(&self, request: &RunRequest) -> Result<ExitStatus, std::io::Error> {
+        let mut process = self.command(request.database)?;
+        process.envs(request.env.iter().map(|(k, v)| (k, v)));
+        let content = request.content;
 
-impl CommandRunner for ShellCommandRunner {
-    fn run(&self, command: &str) -> Result<std::process::ExitStatus, std::io::Error> {
+        match self.input {
+            ContentInput::Argument => process.arg(content).status(),
+            ContentInput::Stdin => {
+                let mut child = process.stdin(Stdio::piped()).spawn()?;
+                let written = match child.stdin.take() {
+                    Some(mut stdin) => stdin.write_all(content.as_bytes()),
+                    None => Ok(()),
+                };
+                let status = child.wait()?;
+                written.map(|_| status)
+            }
+        }
+    }
+}
+
+pub struct RunnerRegistry {
+    interpreters: HashMap<SnippetKind, Interpreter>,
+}
+
+impl RunnerRegistry {
+    pub fn new(sql: &SqlConfig) -> Self {
         let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".into());
-        std::process::Command::new(shell)
-            .arg("-c")
+
+        let mut registry = Self {
+            interpreters: HashMap::new(),
+        };
+        registry.register(SnippetKind::Shell, Interpreter::new(shell, &["-c"]));
+        registry.register(SnippetKind::Bash, Interpreter::new("bash", &["-c"]));
+        registry.register(SnippetKind::Sh, Interpreter::new("sh", &["-c"]));
+        registry.register(SnippetKind::Zsh, Interpreter::new("zsh", &["-c"]));
+        registry.register(SnippetKind::Python, Interpreter::new("python3", &["-c"]));
+        registry.register(SnippetKind::Node, Interpreter::new("node", &["-e"]));
+        if let Some(interpreter) = Interpreter::sql(sql) {
+            registry.register(SnippetKind::Sql, interpreter);
+        }
+        registry
+    }
+
+    pub fn register(&mut self, kind: SnippetKind, interpreter: Interpreter) {
+        self.interpreters.insert(kind, interpreter);
+    }
+
+    fn interpreter(&self, kind: SnippetKind) -> Result<&Interpreter, std::io::Error> {
+        self.interpreters.get(&kind).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                format!("no runner registered for '{}' snippets", kind),
+            )
+        })
+    }
+}
+
+impl CommandRunner for RunnerRegistry {
+    fn run(&self, request: &RunRequest) -> Result<ExitStatus, std::io::Error> {
+        self.interpreter(request.kind)?.status(request)
+    }
+
+    fn capture(&self, command: &str) -> Result<String, std::io::Error> {
+        let output = self
+            .interpreter(SnippetKind::Shell)?
+            .command(None)?
             .arg(command)
-            .status()
+            .stderr(Stdio::inherit())
+            .output()?;
+
+        if !output.status.success() {
+            return Err(std::io::Error::other(format!(
+                "'{}' exited with status {}",
+                command, output.status
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(kind: SnippetKind, content: &str) -> RunRequest<'_> {
+        RunRequest {
+            kind,
+            content,
+            database: None,
+            env: &[],
+        }
+    }
+
+    #[test]
+    fn test_plain_snippets_have_no_runner() {
+        let registry = RunnerRegistry::new(&SqlConfig::default());
+        let err = registry
+            .run(&request(SnippetKind::Plain, "echo hi"))
+            .unwrap_err();
+
+        assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn test_registered_interpreter_is_used() {
+        let mut registry = RunnerRegistry::new(&SqlConfig::default());
+        registry.register(SnippetKind::Python, Interpreter::new("sh", &["-c"]));
+
+        let status = registry
+            .run(&request(SnippetKind::Python, "exit 3"))
+            .unwrap();
+        assert_eq!(status.code(), Some(3));
+    }
+
+    #[test]
+    fn test_env_is_passed_to_the_child() {
+        let mut registry = RunnerRegistry::new(&SqlConfig::default());
+        registry.register(SnippetKind::Shell, Interpreter::new("sh", &["-c"]));
+        let env = [("MARKIT_TEST_TOKEN".to_string(), "abc".to_string())];
+
+        let status = registry
+            .run(&RunRequest {
+                kind: SnippetKind::Shell,
+                content: "test \"$MARKIT_TEST_TOKEN\" = abc",
+                database: None,
+                env: &env,
+            })
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn test_capture_returns_stdout() {
+        let mut registry = RunnerRegistry::new(&SqlConfig::default());
+        registry.register(SnippetKind::Shell, Interpreter::new("sh", &["-c"]));
+
+        let output = registry.capture("printf 'a\\nb\\n'").unwrap();
+        assert_eq!(output, "a\nb\n");
+    }
+
+    #[test]
+    fn test_capture_fails_on_non_zero_exit() {
+        let mut registry = RunnerRegistry::new(&SqlConfig::default());
+        registry.register(SnippetKind::Shell, Interpreter::new("sh", &["-c"]));
+
+        assert!(registry.capture("exit 1").is_err());
+    }
+
+    #[test]
+    fn test_sql_content_is_fed_over_stdin_to_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("out.sql");
+
+        let sql = SqlConfig {
+            client: vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "cat > \"$0\"".to_string(),
+                "{db}".to_string(),
+            ],
+            database: None,
+        };
+        let registry = RunnerRegistry::new(&sql);
+
+        let status = registry
+            .run(&RunRequest {
+                kind: SnippetKind::Sql,
+                content: "SELECT 1;",
+                database: Some(target.to_str().unwrap()),
+                env: &[],
+            })
+            .unwrap();
+
+        assert!(status.success());
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "SELECT 1;");
+    }
+
+    #[test]
+    fn test_sql_uses_configured_default_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("default.sql");
+
+        let sql = SqlConfig {
+            client: vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "cat > \"$0\"".to_string(),
+                "{db}".to_string(),
+            ],
+            database: Some(target.to_str().unwrap().to_string()),
+        };
+        let registry = RunnerRegistry::new(&sql);
+
+        registry
+            .run(&request(SnippetKind::Sql, "SELECT 2;"))
+            .unwrap();
+
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "SELECT 2;");
+    }
+
+    #[test]
+    fn test_sql_without_database_fails() {
+        let registry = RunnerRegistry::new(&SqlConfig::default());
+        let err = registry
+            .run(&request(SnippetKind::Sql, "SELECT 1;"))
+            .unwrap_err();
+
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_database_without_db_argument_fails() {
+        let sql = SqlConfig {
+            client: vec!["sh".to_string(), "-c".to_string(), "cat".to_string()],
+            database: None,
+        };
+        let registry = RunnerRegistry::new(&sql);
+
+        let err = registry
+            .run(&RunRequest {
+                kind: SnippetKind::Sql,
+                content: "SELECT 1;",
+                database: Some("app.db"),
+                env: &[],
+            })
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_stdin_write_error_is_reported_after_the_child_exits() {
+        let mut registry = RunnerRegistry::new(&SqlConfig::default());
+        registry.register(
+            SnippetKind::Sql,
+            Interpreter::stdin("sh", &["-c", "exit 0"]),
+        );
+        let content = "SELECT 1;\n".repeat(100_000);
+
+        let err = registry
+            .run(&request(SnippetKind::Sql, &content))
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::BrokenPipe);
     }
 }
@@ -0,0 +1,243 @@
+use std::path::PathBuf;
+
+use crate::{
+    models::{SnippetStore, StoreDiff},
+    storage::{BackupArchive, BackupRetention, Storage},
+    ui::TableUI,
+};
+
+pub fn list_command(storage: &dyn Storage, archive: &dyn BackupArchive) {
+    let backups = match storage.get_backups() {
+        Ok(b) if !b.is_empty() => b,
+        _ => {
+            println!("📭 No backups created yet.");
+            return;
+        }
+    };
+
+    let stores: Vec<Option<SnippetStore>> = backups
+        .iter()
+        .map(|path| archive.load_backup(path).ok())
+        .collect();
+
+    println!("🗄️ Backups (newest first):");
+    for (i, path) in backups.iter().enumerate() {
+        let summary = match (&stores[i], stores.get(i + 1)) {
+            (None, _) => "⚠️ unreadable".to_string(),
+            (Some(store), Some(Some(previous))) => format!(
+                "{:>3} snippets  {}",
+                store.snippets.len(),
+                StoreDiff::between(previous, store).summary()
+            ),
+            (Some(store), _) => format!("{:>3} snippets  initial", store.snippets.len()),
+        };
+
+        println!("  {}  {}", backup_name(path), summary);
+    }
+}
+
+pub fn show_command(
+    storage: &dyn Storage,
+    archive: &dyn BackupArchive,
+    table_ui: &mut dyn TableUI,
+    backup: String,
+) {
+    let backups = storage.get_backups().unwrap_or_default();
+    let path = match find_backup(&backups, &backup) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("⛔ {}", e);
+            return;
+        }
+    };
+
+    let store = match archive.load_backup(&path) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("⛔ Failed to read backup '{}': {}", backup_name(&path), e);
+            return;
+        }
+    };
+
+    println!(
+        "🗄️ Backup {} ({} snippets)",
+        backup_name(&path),
+        store.snippets.len()
+    );
+    if let Ok(current) = storage.load() {
+        let diff = StoreDiff::between(&current, &store);
+        if !diff.is_empty() {
+            println!("🔀 Compared to the current store:");
+            print_store_diff(&diff);
+        }
+    }
+
+    if !store.snippets.is_empty() {
+        let table = table_ui.with_snippet_list(store.snippets);
+        println!("{table}");
+    }
+}
+
+pub fn prune_command(retention: &dyn BackupRetention, dry_run: bool) {
+    let pruned = match retention.prune_backups(dry_run) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("⛔ Failed to prune backups: {}", e);
+            return;
+        }
+    };
+
+    if pruned.is_empty() {
+        println!("✅ No backups to prune.");
+        return;
+    }
+
+    let total: u64 = pruned.iter().map(|b| b.size).sum();
+    if dry_run {
+        println!(
+            "🧹 Would remove {} backup(s), freeing {}:",
+            pruned.len(),
+            format_size(total)
+        );
+    } else {
+        println!(
+            "🧹 Removed {} backup(s), freed {}:",
+            pruned.len(),
+            format_size(total)
+        );
+    }
+
+    for backup in &pruned {
+        println!(
+            "  - {} ({})",
+            backup_name(&backup.path),
+            format_size(backup.size)
+        );
+    }
+}
+
+pub fn find_backup(backups: &[PathBuf], query: &str) -> Result<PathBuf, String> {
+    let query = query.trim_end_matches(".yml");
+    if query.is_empty() {
+        return Err("No backup given.".to_string());
+    }
+
+    let matches: Vec<&PathBuf> = backups
+        .iter()
+        .filter(|p| {
+            p.file_stem()
+                .and_then(|s| s.to_str())
+                .is_some_and(|s| s.starts_with(query))
+        })
+        .collect();
+
+    match matches.as_slice() {
+        [] => Err(format!("Backup '{}' not found.", query)),
+        [path] => Ok((*path).clone()),
+        _ => match matches
+            .iter()
+            .find(|p| p.file_stem().and_then(|s| s.to_str()) == Some(query))
+        {
+            Some(path) => Ok((*path).clone()),
+            None => Err(format!(
+                "'{}' matches {} backups, be more specific.",
+                query,
+                matches.len()
+            )),
+        },
+    }
+}
+
+pub fn backup_name(path: &std::path::Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+pub fn print_store_diff(diff: &StoreDiff) {
+    for name in &diff.added {
+        println!("  + {}", name);
+    }
+    for name in &diff.modified {
+        println!("  ~ {}", name);
+    }
+    for name in &diff.removed {
+        println!("  - {}", name);
+    }
+}
+
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{StorageError, retention::BackupFile};
+    use std::cell::RefCell;
+
+    struct MockRetention {
+        dry_runs: RefCell<Vec<bool>>,
+    }
+
+    impl BackupRetention for MockRetention {
+        fn prune_backups(&self, dry_run: bool) -> Result<Vec<BackupFile>, StorageError> {
+            self.dry_runs.borrow_mut().push(dry_run);
+            Ok(vec![BackupFile {
+                path: "2024-01-01T00-00-00Z.yml".into(),
+                created_at: chrono::Utc::now(),
+                size: 2048,
+            }])
+        }
+    }
+
+    #[test]
+    fn test_prune_passes_dry_run_through() {
+        let retention = MockRetention {
+            dry_runs: RefCell::new(vec![]),
+        };
+
+        prune_command(&retention, true);
+        prune_command(&retention, false);
+
+        assert_eq!(*retention.dry_runs.borrow(), vec![true, false]);
+    }
+
+    #[test]
+    fn test_find_backup_by_prefix() {
+        let backups = vec![
+            PathBuf::from("/b/2025-01-02T10-00-00Z.yml"),
+            PathBuf::from("/b/2025-01-01T10-00-00Z.yml"),
+            PathBuf::from("/b/2025-01-01T09-00-00Z.yml"),
+        ];
+
+        assert_eq!(find_backup(&backups, "2025-01-02"), Ok(backups[0].clone()));
+        assert_eq!(
+            find_backup(&backups, "2025-01-01T09-00-00Z.yml"),
+            Ok(backups[2].clone())
+        );
+        assert!(find_backup(&backups, "2025-01-01").is_err());
+        assert!(find_backup(&backups, "2024").is_err());
+        assert!(find_backup(&backups, "").is_err());
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(2048), "2.0 KiB");
+        assert_eq!(format_size(5 * 1024 * 1024 + 512 * 1024), "5.5 MiB");
+    }
+}
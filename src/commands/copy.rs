@@ -1,13 +1,21 @@
+use std::collections::HashMap;
+
 use crate::{
-    clipboard_provider::ClipboardProvider, commands::helper::get_snippet, storage::Storage,
-    ui::SelectionUI,
+    clipboard_provider::ClipboardProvider,
+    command_runner::CommandRunner,
+    commands::helper::{get_snippet, render_content},
+    storage::Storage,
+    ui::{InputPrompt, SelectionUI},
 };
 
 pub fn copy_command(
     storage: &dyn Storage,
     selection_ui: &dyn SelectionUI,
+    prompt: &dyn InputPrompt,
+    runner: &dyn CommandRunner,
     clipboard: &mut dyn ClipboardProvider,
     name: String,
+    values: HashMap<String, String>,
 ) {
     let store = match storage.load() {
         Ok(s) => s,
@@ -21,7 +29,11 @@ pub fn copy_command(
         return;
     };
 
-    if let Err(e) = clipboard.set_text(snippet.content.as_str()) {
+    let Some(content) = render_content(&snippet, &values, selection_ui, prompt, runner) else {
+        return;
+    };
+
+    if let Err(e) = clipboard.set_text(content.as_str()) {
         eprintln!("⛔ Failed to copy to clipboard: {}", e);
         return;
     }
@@ -33,9 +45,10 @@ pub fn copy_command(
 mod tests {
     use super::*;
     use crate::clipboard_provider::ClipboardProvider;
-    use crate::models::{Snippet, SnippetStore};
+    use crate::command_runner::{CommandRunner, RunRequest};
+    use crate::models::{Snippet, SnippetKind, SnippetStore, Variable};
     use crate::storage::{Storage, StorageError};
-    use crate::ui::SelectionUI;
+    use crate::ui::{InputPrompt, SelectionUI};
     use std::cell::RefCell;
 
     struct MockStorage {
@@ -83,6 +96,30 @@ mod tests {
         fn with_backup_list(&self, _: &[String]) -> Option<usize> {
             Some(0)
         }
+
+        fn with_choice_list(&self, _: &str, choices: &[String]) -> Option<usize> {
+            choices.iter().position(|c| c == "production")
+        }
+    }
+
+    struct MockInputPrompt;
+
+    impl InputPrompt for MockInputPrompt {
+        fn input(&self, _: &str, default: Option<&str>) -> Option<String> {
+            default.map(String::from)
+        }
+    }
+
+    struct MockCommandRunner;
+
+    impl CommandRunner for MockCommandRunner {
+        fn run(&self, _: &RunRequest) -> Result<std::process::ExitStatus, std::io::Error> {
+            Err(std::io::Error::other("not used"))
+        }
+
+        fn capture(&self, _: &str) -> Result<String, std::io::Error> {
+            Ok("staging\nproduction\n".to_string())
+        }
     }
 
     struct MockClipboard {
@@ -103,11 +140,14 @@ mod tests {
 
     fn sample_snippet() -> Snippet {
         Snippet {
+            id: Snippet::generate_id(),
             name: "test".to_string(),
             description: "desc".to_string(),
             content: "echo hello".to_string(),
-            executable: true,
+            kind: SnippetKind::Shell,
             tags: vec!["dev".to_string()],
+            variables: vec![],
+            database: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         }
@@ -134,8 +174,11 @@ mod tests {
         copy_command(
             &storage,
             &selection_ui,
+            &MockInputPrompt,
+            &MockCommandRunner,
             &mut clipboard,
             snippet.name.clone(),
+            HashMap::new(),
         );
 
         assert_eq!(
@@ -160,7 +203,15 @@ mod tests {
             fail: false,
         };
 
-        copy_command(&storage, &selection_ui, &mut clipboard, "test".to_string());
+        copy_command(
+            &storage,
+            &selection_ui,
+            &MockInputPrompt,
+            &MockCommandRunner,
+            &mut clipboard,
+            "test".to_string(),
+            HashMap::new(),
+        );
 
         assert!(clipboard.last_text.borrow().is_none());
     }
@@ -183,7 +234,15 @@ mod tests {
             fail: false,
         };
 
-        copy_command(&storage, &selection_ui, &mut clipboard, "test".to_string());
+        copy_command(
+            &storage,
+            &selection_ui,
+            &MockInputPrompt,
+            &MockCommandRunner,
+            &mut clipboard,
+            "missing".to_string(),
+            HashMap::new(),
+        );
 
         assert!(clipboard.last_text.borrow().is_none());
     }
@@ -206,8 +265,90 @@ mod tests {
             fail: true,
         };
 
-        copy_command(&storage, &selection_ui, &mut clipboard, "test".to_string());
+        copy_command(
+            &storage,
+            &selection_ui,
+            &MockInputPrompt,
+            &MockCommandRunner,
+            &mut clipboard,
+            "test".to_string(),
+            HashMap::new(),
+        );
 
         assert!(clipboard.last_text.borrow().is_none());
     }
+
+    #[test]
+    fn test_copy_renders_placeholders() {
+        let mut snippet = sample_snippet();
+        snippet.content = "git push {{remote:origin}} {{branch}}".to_string();
+
+        let storage = MockStorage {
+            snippets: vec![snippet.clone()],
+            should_fail: false,
+        };
+
+        let selection_ui = MockSelectionUI {
+            selected: RefCell::new(Some(snippet)),
+        };
+
+        let mut clipboard = MockClipboard {
+            last_text: RefCell::new(None),
+            fail: false,
+        };
+
+        copy_command(
+            &storage,
+            &selection_ui,
+            &MockInputPrompt,
+            &MockCommandRunner,
+            &mut clipboard,
+            "test".to_string(),
+            HashMap::from([("branch".to_string(), "main".to_string())]),
+        );
+
+        assert_eq!(
+            clipboard.last_text.borrow().as_deref(),
+            Some("git push origin main")
+        );
+    }
+
+    #[test]
+    fn test_copy_selects_value_from_variable_command() {
+        let mut snippet = sample_snippet();
+        snippet.content = "deploy --env {{env}}".to_string();
+        snippet.variables = vec![Variable {
+            name: "env".to_string(),
+            command: "echo environments".to_string(),
+        }];
+
+        let storage = MockStorage {
+            snippets: vec![snippet.clone()],
+            should_fail: false,
+        };
+
+        let selection_ui = MockSelectionUI {
+            selected: RefCell::new(Some(snippet)),
+        };
+
+        let mut clipboard = MockClipboard {
+            last_text: RefCell::new(None),
+            fail: false,
+        };
+
+        copy_command(
+            &storage,
+            &selection_ui,
+            &MockInputPrompt,
+            &MockCommandRunner,
+            &mut clipboard,
+            "test".to_string(),
+            HashMap::new(),
+        );
+
+        assert_eq!(
+            clipboard.last_text.borrow().as_deref(),
+            Some("deploy --env production")
+        );
+    }
 }
@@ -1,15 +1,18 @@
 use crate::{
     commands::helper::get_snippet,
-    storage::Storage,
+    models::TrashEntry,
+    storage::{Storage, Trash},
     ui::{ConfirmPrompt, SelectionUI},
 };
 
 pub fn delete_command(
     storage: &dyn Storage,
+    trash: &dyn Trash,
     selection_ui: &dyn SelectionUI,
     confirm: &dyn ConfirmPrompt,
     name: String,
     force: bool,
+    purge: bool,
 ) {
     let mut store = match storage.load() {
         Ok(s) => s,
@@ -27,30 +30,65 @@ pub fn delete_command(
     };
 
     if !force {
-        let prompt = format!(
-            "❗ Are you sure you want to delete '{}'? This cannot be undone.",
-            delete_snippet.name
-        );
+        let prompt = if purge {
+            format!(
+                "❗ Are you sure you want to permanently delete '{}'? This cannot be undone.",
+                delete_snippet.name
+            )
+        } else {
+            format!("❗ Move '{}' to the trash?", delete_snippet.name)
+        };
         if !confirm.confirm(&prompt) {
             println!("🚫 Deletion cancelled.");
             return;
         }
     }
 
-    store.snippets.retain(|s| s.name != delete_snippet.name);
+    let previous_trash = if purge {
+        None
+    } else {
+        let mut entries = match trash.load_trash() {
+            Ok(e) => e,
+            Err(err) => {
+                eprintln!("⛔ Failed to load trash: {}", err);
+                return;
+            }
+        };
+        let previous = entries.clone();
+        entries.push(TrashEntry {
+            deleted_at: chrono::Utc::now(),
+            snippet: delete_snippet.clone(),
+        });
+
+        if let Err(err) = trash.save_trash(&entries) {
+            eprintln!("⛔ Failed to move snippet to trash: {}", err);
+            return;
+        }
+        Some(previous)
+    };
+
+    store.snippets.retain(|s| s.id != delete_snippet.id);
 
     if let Err(err) = storage.save_all(&store) {
         eprintln!("⛔ Failed to update snippets file: {:?}", err);
+        if let Some(previous) = previous_trash {
+            let _ = trash.save_trash(&previous);
+        }
+    } else if purge {
+        println!("🗑️ Snippet '{}' permanently deleted.", delete_snippet.name);
     } else {
-        println!("🗑️ Snippet '{}' deleted.", delete_snippet.name);
+        println!(
+            "🗑️ Snippet '{}' moved to the trash. Use `markit undelete {}` to bring it back.",
+            delete_snippet.name, delete_snippet.name
+        );
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{Snippet, SnippetStore};
-    use crate::storage::{Storage, StorageError};
+    use crate::models::{Snippet, SnippetKind, SnippetStore};
+    use crate::storage::{Storage, StorageError, Trash};
     use crate::ui::{ConfirmPrompt, SelectionUI};
     use std::cell::RefCell;
 
@@ -93,6 +131,22 @@ mod tests {
         }
     }
 
+    #[derive(Default)]
+    struct MockTrash {
+        entries: RefCell<Vec<TrashEntry>>,
+    }
+
+    impl Trash for MockTrash {
+        fn load_trash(&self) -> Result<Vec<TrashEntry>, StorageError> {
+            Ok(self.entries.borrow().clone())
+        }
+
+        fn save_trash(&self, entries: &[TrashEntry]) -> Result<(), StorageError> {
+            self.entries.replace(entries.to_vec());
+            Ok(())
+        }
+    }
+
     struct MockSelectionUI {
         snippet: Option<Snippet>,
     }
@@ -105,6 +159,10 @@ mod tests {
         fn with_backup_list(&self, _: &[String]) -> Option<usize> {
             Some(0)
         }
+
+        fn with_choice_list(&self, _: &str, _: &[String]) -> Option<usize> {
+            None
+        }
     }
 
     struct MockConfirmPrompt {
@@ -119,11 +177,14 @@ mod tests {
 
     fn sample_snippet(name: &str) -> Snippet {
         Snippet {
+            id: Snippet::generate_id(),
             name: name.to_string(),
             description: "desc".into(),
             content: "echo test".into(),
-            executable: true,
+            kind: SnippetKind::Shell,
             tags: vec!["tag".into()],
+            variables: vec![],
+            database: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         }
@@ -144,7 +205,15 @@ mod tests {
             confirm_result: false,
         }; // Should be skipped
 
-        delete_command(&storage, &selection_ui, &confirm, "test".to_string(), true);
+        delete_command(
+            &storage,
+            &MockTrash::default(),
+            &selection_ui,
+            &confirm,
+            "test".to_string(),
+            true,
+            false,
+        );
 
         assert!(storage.snippets.borrow().is_empty());
     }
@@ -164,7 +233,15 @@ mod tests {
             confirm_result: true,
         };
 
-        delete_command(&storage, &selection_ui, &confirm, "test".to_string(), false);
+        delete_command(
+            &storage,
+            &MockTrash::default(),
+            &selection_ui,
+            &confirm,
+            "test".to_string(),
+            false,
+            false,
+        );
 
         assert!(storage.snippets.borrow().is_empty());
     }
@@ -184,7 +261,15 @@ mod tests {
             confirm_result: false,
         };
 
-        delete_command(&storage, &selection_ui, &confirm, "test".to_string(), false);
+        delete_command(
+            &storage,
+            &MockTrash::default(),
+            &selection_ui,
+            &confirm,
+            "test".to_string(),
+            false,
+            false,
+        );
 
         assert_eq!(storage.snippets.borrow().len(), 1);
     }
@@ -203,10 +288,12 @@ mod tests {
 
         delete_command(
             &storage,
+            &MockTrash::default(),
             &selection_ui,
             &confirm,
             "missing".to_string(),
             true,
+            false,
         );
 
         assert!(storage.snippets.borrow().is_empty());
@@ -226,7 +313,15 @@ mod tests {
             confirm_result: true,
         };
 
-        delete_command(&storage, &selection_ui, &confirm, "test".to_string(), true);
+        delete_command(
+            &storage,
+            &MockTrash::default(),
+            &selection_ui,
+            &confirm,
+            "test".to_string(),
+            true,
+            false,
+        );
     }
 
     #[test]
@@ -244,6 +339,104 @@ mod tests {
             confirm_result: true,
         };
 
-        delete_command(&storage, &selection_ui, &confirm, "test".to_string(), false);
+        delete_command(
+            &storage,
+            &MockTrash::default(),
+            &selection_ui,
+            &confirm,
+            "test".to_string(),
+            false,
+            false,
+        );
+    }
+
+    #[test]
+    fn test_delete_moves_snippet_to_trash() {
+        let snippet = sample_snippet("test");
+        let storage = MockStorage {
+            snippets: RefCell::new(vec![snippet.clone()]),
+            should_fail_load: false,
+            should_fail_save: false,
+        };
+        let trash = MockTrash::default();
+        let selection_ui = MockSelectionUI {
+            snippet: Some(snippet.clone()),
+        };
+        let confirm = MockConfirmPrompt {
+            confirm_result: true,
+        };
+
+        delete_command(
+            &storage,
+            &trash,
+            &selection_ui,
+            &confirm,
+            "test".to_string(),
+            false,
+            false,
+        );
+
+        assert!(storage.snippets.borrow().is_empty());
+        assert_eq!(trash.entries.borrow().len(), 1);
+        assert_eq!(trash.entries.borrow()[0].snippet.id, snippet.id);
+    }
+
+    #[test]
+    fn test_delete_purge_bypasses_trash() {
+        let snippet = sample_snippet("test");
+        let storage = MockStorage {
+            snippets: RefCell::new(vec![snippet.clone()]),
+            should_fail_load: false,
+            should_fail_save: false,
+        };
+        let trash = MockTrash::default();
+        let selection_ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+        let confirm = MockConfirmPrompt {
+            confirm_result: true,
+        };
+
+        delete_command(
+            &storage,
+            &trash,
+            &selection_ui,
+            &confirm,
+            "test".to_string(),
+            true,
+            true,
+        );
+
+        assert!(storage.snippets.borrow().is_empty());
+        assert!(trash.entries.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_delete_save_failure_rolls_back_trash() {
+        let snippet = sample_snippet("test");
+        let storage = MockStorage {
+            snippets: RefCell::new(vec![snippet.clone()]),
+            should_fail_load: false,
+            should_fail_save: true,
+        };
+        let trash = MockTrash::default();
+        let selection_ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+        let confirm = MockConfirmPrompt {
+            confirm_result: true,
+        };
+
+        delete_command(
+            &storage,
+            &trash,
+            &selection_ui,
+            &confirm,
+            "test".to_string(),
+            true,
+            false,
+        );
+
+        assert!(trash.entries.borrow().is_empty());
     }
 }
@@ -0,0 +1,218 @@
+use similar::TextDiff;
+
+use crate::{
+    commands::helper::get_snippet,
+    models::PartialSnippet,
+    storage::{SnippetHistory, Storage},
+    ui::SelectionUI,
+};
+
+pub fn diff_command(
+    storage: &dyn Storage,
+    history: &dyn SnippetHistory,
+    selection_ui: &dyn SelectionUI,
+    name: String,
+    rev: Option<u32>,
+) {
+    let store = match storage.load() {
+        Ok(s) => s,
+        Err(_) => {
+            println!("📭 No snippets saved yet.");
+            return;
+        }
+    };
+
+    let Some(snippet) = get_snippet(&store, selection_ui, name) else {
+        return;
+    };
+
+    let revisions = match history.get_history(&snippet.id) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("⛔ Failed to load history: {}", e);
+            return;
+        }
+    };
+
+    let current = PartialSnippet::from(&snippet);
+    let revision = match rev {
+        Some(rev) => revisions.iter().find(|r| r.rev == rev),
+        None => revisions.iter().rev().find(|r| r.snippet != current),
+    };
+
+    let Some(revision) = revision else {
+        match rev {
+            Some(rev) => println!("⛔ Revision #{} not found for '{}'.", rev, snippet.name),
+            None => println!(
+                "📭 No earlier revision of '{}' to compare with.",
+                snippet.name
+            ),
+        }
+        return;
+    };
+
+    let diff = render_diff(
+        &revision.snippet,
+        &current,
+        &format!("rev #{}", revision.rev),
+        "current",
+    );
+
+    if diff.is_empty() {
+        println!("✅ Revision #{} matches the current snippet.", revision.rev);
+    } else {
+        print!("{}", diff);
+    }
+}
+
+pub fn render_diff(
+    old: &PartialSnippet,
+    new: &PartialSnippet,
+    old_label: &str,
+    new_label: &str,
+) -> String {
+    let old_yaml = serde_yaml::to_string(old).unwrap_or_default();
+    let new_yaml = serde_yaml::to_string(new).unwrap_or_default();
+
+    if old_yaml == new_yaml {
+        return String::new();
+    }
+
+    TextDiff::from_lines(&old_yaml, &new_yaml)
+        .unified_diff()
+        .header(old_label, new_label)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Revision, Snippet, SnippetKind, SnippetStore};
+    use crate::storage::StorageError;
+
+    struct MockStorage {
+        snippets: Vec<Snippet>,
+    }
+
+    impl Storage for MockStorage {
+        fn load(&self) -> Result<SnippetStore, StorageError> {
+            Ok(SnippetStore {
+                snippets: self.snippets.clone(),
+            })
+        }
+
+        fn save(&self, _: Snippet) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn save_all(&self, _: &SnippetStore) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn get_backups(&self) -> Result<Vec<std::path::PathBuf>, StorageError> {
+            Ok(vec![])
+        }
+
+        fn restore_backup(&self, _: &std::path::Path) -> Result<(), StorageError> {
+            Ok(())
+        }
+    }
+
+    struct MockHistory {
+        fail: bool,
+    }
+
+    impl SnippetHistory for MockHistory {
+        fn get_history(&self, _: &str) -> Result<Vec<Revision>, StorageError> {
+            if self.fail {
+                Err(StorageError::Io(std::io::Error::other("history failed")))
+            } else {
+                Ok(vec![])
+            }
+        }
+    }
+
+    struct MockSelectionUI {
+        snippet: Option<Snippet>,
+    }
+
+    impl SelectionUI for MockSelectionUI {
+        fn with_snippet_list(&self, _: Vec<Snippet>) -> Option<Snippet> {
+            self.snippet.clone()
+        }
+
+        fn with_backup_list(&self, _: &[String]) -> Option<usize> {
+            None
+        }
+
+        fn with_choice_list(&self, _: &str, _: &[String]) -> Option<usize> {
+            None
+        }
+    }
+
+    fn sample_partial() -> PartialSnippet {
+        PartialSnippet {
+            name: "deploy".into(),
+            description: "Deploy the app".into(),
+            content: "kubectl apply -f app.yml\n".into(),
+            kind: SnippetKind::Shell,
+            tags: vec!["k8s".into()],
+            variables: vec![],
+            database: None,
+        }
+    }
+
+    #[test]
+    fn test_render_diff_shows_changed_lines() {
+        let old = sample_partial();
+        let mut new = old.clone();
+        new.content = "kubectl apply -f prod.yml\n".into();
+        new.tags = vec!["k8s".into(), "prod".into()];
+
+        let diff = render_diff(&old, &new, "rev #1", "current");
+
+        assert!(diff.contains("--- rev #1"));
+        assert!(diff.contains("+++ current"));
+        assert!(diff.contains("-  kubectl apply -f app.yml"));
+        assert!(diff.contains("+  kubectl apply -f prod.yml"));
+        assert!(diff.contains("+- prod"));
+    }
+
+    #[test]
+    fn test_render_diff_identical_is_empty() {
+        let partial = sample_partial();
+        assert!(render_diff(&partial, &partial, "a", "b").is_empty());
+    }
+
+    #[test]
+    fn test_diff_command_history_failure() {
+        let snippet = Snippet {
+            id: Snippet::generate_id(),
+            name: "deploy".into(),
+            description: "Deploy the app".into(),
+            content: "kubectl apply -f app.yml".into(),
+            kind: SnippetKind::Shell,
+            tags: vec![],
+            variables: vec![],
+            database: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+
+        let storage = MockStorage {
+            snippets: vec![snippet.clone()],
+        };
+        let selection_ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+
+        // Should not panic
+        diff_command(
+            &storage,
+            &MockHistory { fail: true },
+            &selection_ui,
+            "deploy".into(),
+            None,
+        );
+    }
+}
@@ -19,7 +19,7 @@ pub fn export_command(storage: &dyn Storage, writer: &dyn FileWriter, file_path:
 mod tests {
     use super::*;
     use crate::file::FileWriter;
-    use crate::models::{Snippet, SnippetStore};
+    use crate::models::{Snippet, SnippetKind, SnippetStore};
     use crate::storage::{Storage, StorageError};
     use std::cell::RefCell;
 
@@ -75,11 +75,14 @@ mod tests {
     #[test]
     fn test_export_command_success() {
         let snippet = Snippet {
+            id: Snippet::generate_id(),
             name: "example".to_string(),
             description: "desc".to_string(),
             content: "echo hi".to_string(),
-            executable: true,
+            kind: SnippetKind::Shell,
             tags: vec!["tag".to_string()],
+            variables: vec![],
+            database: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
@@ -117,11 +120,14 @@ mod tests {
     #[test]
     fn test_export_command_writer_failure() {
         let snippet = Snippet {
+            id: Snippet::generate_id(),
             name: "example".to_string(),
             description: "desc".to_string(),
             content: "echo hi".to_string(),
-            executable: true,
+            kind: SnippetKind::Shell,
             tags: vec!["tag".to_string()],
+            variables: vec![],
+            database: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
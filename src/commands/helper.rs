@@ -1,7 +1,12 @@
+use std::collections::HashMap;
+
 use crate::{
-    models::{PartialSnippet, Snippet, SnippetStore},
+    command_runner::CommandRunner,
+    models::{PartialSnippet, Snippet, SnippetStore, Variable},
+    secret_scan,
     storage::filter::{self, Filter},
-    ui::SelectionUI,
+    template,
+    ui::{InputPrompt, SelectionUI},
 };
 
 pub fn get_snippet(
@@ -9,6 +14,26 @@ pub fn get_snippet(
     selection_ui: &dyn SelectionUI,
     name: String,
 ) -> Option<Snippet> {
+    let by_id = filter::apply_filter(store, Filter::Id(name.clone()));
+    let by_name: Vec<&Snippet> = store
+        .snippets
+        .iter()
+        .filter(|s| s.name.eq_ignore_ascii_case(&name))
+        .collect();
+
+    if let [snippet] = by_name.as_slice() {
+        if by_id.iter().any(|s| s.id != snippet.id) {
+            eprintln!(
+                "⚠️ '{}' is both a snippet name and an ID prefix; using the snippet named '{}'. Pass a longer ID to pick the other one.",
+                name, snippet.name
+            );
+        }
+        return Some((*snippet).clone());
+    }
+    if let ([], [snippet]) = (by_name.as_slice(), by_id.as_slice()) {
+        return Some(snippet.clone());
+    }
+
     let filtered = filter::apply_filter(store, Filter::Name(name.clone()));
 
     match selection_ui.with_snippet_list(filtered) {
@@ -20,12 +45,161 @@ pub fn get_snippet(
     }
 }
 
+pub fn text_fields<'a>(
+    description: &'a mut String,
+    content: &'a mut String,
+    variables: &'a mut [Variable],
+) -> Vec<(String, &'a mut String)> {
+    let mut fields = vec![
+        ("description".to_string(), description),
+        ("content".to_string(), content),
+    ];
+    for variable in variables {
+        fields.push((
+            format!("variable '{}'", variable.name),
+            &mut variable.command,
+        ));
+    }
+    fields
+}
+
+pub fn guard_secrets(
+    name: &str,
+    mut fields: Vec<(String, &mut String)>,
+    selection_ui: &dyn SelectionUI,
+) -> bool {
+    let findings: Vec<Vec<secret_scan::Finding>> = fields
+        .iter()
+        .map(|(_, text)| secret_scan::scan(text))
+        .collect();
+    let total: usize = findings.iter().map(Vec::len).sum();
+    if total == 0 {
+        return true;
+    }
+
+    eprintln!("⚠️ '{}' looks like it contains secrets:", name);
+    for ((label, text), found) in fields.iter().zip(&findings) {
+        for finding in found {
+            eprintln!("   {} {}", label, finding.describe(text));
+        }
+    }
+    eprintln!(
+        "💡 Keep them in the vault with `markit secret set NAME` and use ${{secret:NAME}} instead."
+    );
+
+    let choices = vec![
+        "Redact them as {{placeholders}}".to_string(),
+        "Keep them".to_string(),
+        "Abort".to_string(),
+    ];
+    match selection_ui.with_choice_list("🔐 What should be saved?", &choices) {
+        Some(0) => {
+            for ((_, text), found) in fields.iter_mut().zip(&findings) {
+                if !found.is_empty() {
+                    **text = secret_scan::redact(text, found);
+                }
+            }
+            println!("🧹 Redacted {} value(s).", total);
+            true
+        }
+        Some(1) => true,
+        _ => {
+            println!("🚫 Cancelled.");
+            false
+        }
+    }
+}
+
 pub fn redact_snippet(snippet: &Snippet) -> PartialSnippet {
-    PartialSnippet {
-        name: snippet.name.clone(),
-        description: snippet.description.clone(),
-        content: snippet.content.clone(),
-        executable: snippet.executable,
-        tags: snippet.tags.clone(),
+    PartialSnippet::from(snippet)
+}
+
+pub fn apply_edits(original: &mut Snippet, edited: PartialSnippet) {
+    original.name = edited.name;
+    original.description = edited.description;
+    original.content = edited.content;
+    original.kind = edited.kind;
+    original.updated_at = chrono::Utc::now();
+    original.tags = edited.tags;
+    original.variables = edited.variables;
+    original.database = edited.database;
+}
+
+pub fn render_content(
+    snippet: &Snippet,
+    values: &HashMap<String, String>,
+    selection_ui: &dyn SelectionUI,
+    prompt: &dyn InputPrompt,
+    runner: &dyn CommandRunner,
+) -> Option<String> {
+    let mut resolved = values.clone();
+    let placeholders = match template::parse_placeholders(&snippet.content) {
+        Ok(placeholders) => placeholders,
+        Err(e) => {
+            eprintln!("⛔ {}", e);
+            return None;
+        }
+    };
+
+    for placeholder in placeholders {
+        if resolved.contains_key(&placeholder.name) {
+            continue;
+        }
+
+        let choices = snippet
+            .variables
+            .iter()
+            .find(|v| v.name == placeholder.name)
+            .map(|v| load_choices(v, runner))
+            .unwrap_or_default();
+
+        let value = if choices.is_empty() {
+            let message = format!("🧩 Value for '{}'", placeholder.name);
+            prompt.input(&message, placeholder.default.as_deref())
+        } else {
+            let message = format!("🧩 Select a value for '{}':", placeholder.name);
+            selection_ui
+                .with_choice_list(&message, &choices)
+                .and_then(|i| choices.get(i).cloned())
+        };
+
+        match value {
+            Some(value) => {
+                resolved.insert(placeholder.name, value);
+            }
+            None => {
+                println!("🚫 Cancelled.");
+                return None;
+            }
+        }
+    }
+
+    Some(template::render(&snippet.content, &resolved))
+}
+
+fn load_choices(variable: &Variable, runner: &dyn CommandRunner) -> Vec<String> {
+    match runner.capture(&variable.command) {
+        Ok(output) => {
+            let choices: Vec<String> = output
+                .lines()
+                .map(|l| l.trim().to_string())
+                .filter(|l| !l.is_empty())
+                .collect();
+
+            if choices.is_empty() {
+                eprintln!(
+                    "⚠️ No choices returned for '{}', falling back to manual input.",
+                    variable.name
+                );
+            }
+            choices
+        }
+        Err(e) => {
+            eprintln!(
+                "⚠️ Failed to load choices for '{}': {}. Falling back to manual input.",
+                variable.name, e
+            );
+            vec![]
+        }
     }
 }
@@ -0,0 +1,214 @@
+use crate::{
+    commands::helper::get_snippet,
+    models::PartialSnippet,
+    storage::{SnippetHistory, Storage},
+    ui::SelectionUI,
+};
+
+pub fn history_command(
+    storage: &dyn Storage,
+    history: &dyn SnippetHistory,
+    selection_ui: &dyn SelectionUI,
+    name: String,
+) {
+    let store = match storage.load() {
+        Ok(s) => s,
+        Err(_) => {
+            println!("📭 No snippets saved yet.");
+            return;
+        }
+    };
+
+    let Some(snippet) = get_snippet(&store, selection_ui, name) else {
+        return;
+    };
+
+    let revisions = match history.get_history(&snippet.id) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("⛔ Failed to load history: {}", e);
+            return;
+        }
+    };
+
+    if revisions.is_empty() {
+        println!("📭 No revisions recorded for '{}'.", snippet.name);
+        return;
+    }
+
+    println!("🕘 History for '{}':", snippet.name);
+    for (i, revision) in revisions.iter().enumerate().rev() {
+        let summary = match i.checked_sub(1).and_then(|prev| revisions.get(prev)) {
+            Some(previous) => changed_fields(&previous.snippet, &revision.snippet).join(", "),
+            None => "initial".to_string(),
+        };
+
+        println!(
+            "  #{:<4} {}  {}",
+            revision.rev,
+            revision.recorded_at.format("%Y-%m-%d %H:%M:%S"),
+            summary
+        );
+    }
+}
+
+pub fn changed_fields(old: &PartialSnippet, new: &PartialSnippet) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+
+    if old.name != new.name {
+        fields.push("name");
+    }
+    if old.description != new.description {
+        fields.push("description");
+    }
+    if old.content != new.content {
+        fields.push("content");
+    }
+    if old.kind != new.kind {
+        fields.push("kind");
+    }
+    if old.tags != new.tags {
+        fields.push("tags");
+    }
+    if old.variables != new.variables {
+        fields.push("variables");
+    }
+    if old.database != new.database {
+        fields.push("database");
+    }
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Revision, Snippet, SnippetKind, SnippetStore};
+    use crate::storage::StorageError;
+    use std::cell::RefCell;
+
+    struct MockStorage {
+        snippets: Vec<Snippet>,
+    }
+
+    impl Storage for MockStorage {
+        fn load(&self) -> Result<SnippetStore, StorageError> {
+            Ok(SnippetStore {
+                snippets: self.snippets.clone(),
+            })
+        }
+
+        fn save(&self, _: Snippet) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn save_all(&self, _: &SnippetStore) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn get_backups(&self) -> Result<Vec<std::path::PathBuf>, StorageError> {
+            Ok(vec![])
+        }
+
+        fn restore_backup(&self, _: &std::path::Path) -> Result<(), StorageError> {
+            Ok(())
+        }
+    }
+
+    struct MockHistory {
+        revisions: Vec<Revision>,
+        requested: RefCell<Option<String>>,
+    }
+
+    impl SnippetHistory for MockHistory {
+        fn get_history(&self, id: &str) -> Result<Vec<Revision>, StorageError> {
+            self.requested.replace(Some(id.to_string()));
+            Ok(self.revisions.clone())
+        }
+    }
+
+    struct MockSelectionUI {
+        snippet: Option<Snippet>,
+    }
+
+    impl SelectionUI for MockSelectionUI {
+        fn with_snippet_list(&self, _: Vec<Snippet>) -> Option<Snippet> {
+            self.snippet.clone()
+        }
+
+        fn with_backup_list(&self, _: &[String]) -> Option<usize> {
+            None
+        }
+
+        fn with_choice_list(&self, _: &str, _: &[String]) -> Option<usize> {
+            None
+        }
+    }
+
+    fn sample_snippet() -> Snippet {
+        Snippet {
+            id: Snippet::generate_id(),
+            name: "test".into(),
+            description: "desc".into(),
+            content: "echo hi".into(),
+            kind: SnippetKind::Shell,
+            tags: vec!["tag".into()],
+            variables: vec![],
+            database: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_history_looks_up_by_snippet_id() {
+        let snippet = sample_snippet();
+        let storage = MockStorage {
+            snippets: vec![snippet.clone()],
+        };
+        let history = MockHistory {
+            revisions: vec![Revision {
+                rev: 1,
+                recorded_at: chrono::Utc::now(),
+                snippet: PartialSnippet::from(&snippet),
+            }],
+            requested: RefCell::new(None),
+        };
+        let selection_ui = MockSelectionUI {
+            snippet: Some(snippet.clone()),
+        };
+
+        history_command(&storage, &history, &selection_ui, "test".into());
+
+        assert_eq!(
+            history.requested.borrow().as_deref(),
+            Some(snippet.id.as_str())
+        );
+    }
+
+    #[test]
+    fn test_history_snippet_not_found() {
+        let storage = MockStorage { snippets: vec![] };
+        let history = MockHistory {
+            revisions: vec![],
+            requested: RefCell::new(None),
+        };
+        let selection_ui = MockSelectionUI { snippet: None };
+
+        history_command(&storage, &history, &selection_ui, "missing".into());
+
+        assert!(history.requested.borrow().is_none());
+    }
+
+    #[test]
+    fn test_changed_fields() {
+        let snippet = sample_snippet();
+        let old = PartialSnippet::from(&snippet);
+        let mut new = old.clone();
+        new.content = "echo bye".into();
+        new.tags = vec![];
+
+        assert_eq!(changed_fields(&old, &new), vec!["content", "tags"]);
+        assert!(changed_fields(&old, &old).is_empty());
+    }
+}
@@ -13,7 +13,19 @@ pub fn list_command(
     tag: Option<String>,
     search: Option<String>,
 ) {
-    let store = match storage.load() {
+    let loaded = match (tag.as_deref(), search.as_deref()) {
+        (Some(tag), None) => storage.find_by_tag(tag),
+        (tag, search) => storage.load().map(|store| match (tag, search) {
+            (None, Some(query)) => apply_filter(&store, Filter::FuzzySearch(query.to_string())),
+            (Some(_), Some(query)) => {
+                eprintln!("⚠️ Cannot use both --tag and --search. Using --search.");
+                apply_filter(&store, Filter::FuzzySearch(query.to_string()))
+            }
+            _ => apply_filter(&store, Filter::All),
+        }),
+    };
+
+    let snippets: Vec<Snippet> = match loaded {
         Ok(s) => s,
         Err(_) => {
             println!("📭 No snippets saved yet.");
@@ -21,16 +33,6 @@ pub fn list_command(
         }
     };
 
-    let snippets: Vec<Snippet> = match (tag.as_deref(), search.as_deref()) {
-        (Some(tag), None) => apply_filter(&store, Filter::Tag(tag.to_string())),
-        (None, Some(query)) => apply_filter(&store, Filter::FuzzySearch(query.to_string())),
-        (Some(_), Some(query)) => {
-            eprintln!("⚠️ Cannot use both --tag and --search. Using --search.");
-            apply_filter(&store, Filter::FuzzySearch(query.to_string()))
-        }
-        (None, None) => apply_filter(&store, Filter::All),
-    };
-
     if snippets.is_empty() {
         if let Some(tag) = tag {
             println!("📭 No snippets found for tag: {}.", tag);
@@ -52,7 +54,7 @@ mod tests {
 
     use crate::{
         commands::list::list_command,
-        models::{Snippet, SnippetStore},
+        models::{Snippet, SnippetKind, SnippetStore},
         storage::{Storage, StorageError},
         ui::TableUI,
     };
@@ -113,11 +115,14 @@ mod tests {
         let storage = MockStorage {
             store: SnippetStore {
                 snippets: vec![Snippet {
+                    id: Snippet::generate_id(),
                     name: "test".to_string(),
                     description: "test desc".to_string(),
                     content: "ls".to_string(),
-                    executable: true,
+                    kind: SnippetKind::Shell,
                     tags: vec!["dev".to_string()],
+                    variables: vec![],
+                    database: None,
                     created_at: chrono::Utc::now(),
                     updated_at: chrono::Utc::now(),
                 }],
@@ -137,11 +142,14 @@ mod tests {
         let storage = MockStorage {
             store: SnippetStore {
                 snippets: vec![Snippet {
+                    id: Snippet::generate_id(),
                     name: "test".to_string(),
                     description: "test desc".to_string(),
                     content: "ls".to_string(),
-                    executable: true,
+                    kind: SnippetKind::Shell,
                     tags: vec!["dev".to_string()],
+                    variables: vec![],
+                    database: None,
                     created_at: chrono::Utc::now(),
                     updated_at: chrono::Utc::now(),
                 }],
@@ -0,0 +1,446 @@
+use crate::{
+    commands::{backups::print_store_diff, helper::apply_edits, restore::unique_name},
+    file::{FileReader, TextEditor},
+    models::{PartialSnippet, Snippet, SnippetStore, StoreDiff},
+    storage::{
+        Storage,
+        merge::{
+            SnippetConflict, conflict_markers, has_conflict_markers, merge_stores, merge_two_way,
+        },
+    },
+    ui::SelectionUI,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Resolution {
+    KeepCurrent,
+    TakeOther,
+    KeepBoth,
+    Edit,
+}
+
+impl Resolution {
+    fn label(&self) -> &'static str {
+        match self {
+            Resolution::KeepCurrent => "Keep the current version",
+            Resolution::TakeOther => "Take the other version",
+            Resolution::KeepBoth => "Keep both (add the other under a new name)",
+            Resolution::Edit => "Edit with conflict markers",
+        }
+    }
+
+    fn available(conflict: &SnippetConflict) -> Vec<Resolution> {
+        if conflict.local.is_some() && conflict.remote.is_some() {
+            vec![
+                Resolution::KeepCurrent,
+                Resolution::TakeOther,
+                Resolution::KeepBoth,
+                Resolution::Edit,
+            ]
+        } else {
+            vec![Resolution::KeepCurrent, Resolution::TakeOther]
+        }
+    }
+}
+
+pub fn merge_command(
+    storage: &dyn Storage,
+    reader: &dyn FileReader,
+    selection_ui: &dyn SelectionUI,
+    editor: &dyn TextEditor,
+    other_path: &str,
+    base_path: Option<&str>,
+) {
+    let other = match reader.read_yaml(other_path) {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("⛔ Failed to read '{}': {}", other_path, e);
+            return;
+        }
+    };
+    let base = match base_path.map(|path| (path, reader.read_yaml(path))) {
+        None => None,
+        Some((_, Ok(store))) => Some(store),
+        Some((path, Err(e))) => {
+            eprintln!("⛔ Failed to read '{}': {}", path, e);
+            return;
+        }
+    };
+    let current = match storage.load() {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("⛔ Failed to load snippets: {}", e);
+            return;
+        }
+    };
+
+    let merge = match &base {
+        Some(base) => merge_stores(base, &current, &other),
+        None => merge_two_way(&current, &other),
+    };
+
+    let mut merged = merge.store;
+    for conflict in &merge.conflicts {
+        if !resolve_conflict(&mut merged, conflict, selection_ui, editor, other_path) {
+            println!("🚫 Merge cancelled, nothing was changed.");
+            return;
+        }
+    }
+
+    let diff = StoreDiff::between(&current, &merged);
+    if diff.is_empty() {
+        println!("✅ Already up to date with '{}'.", other_path);
+        return;
+    }
+
+    println!("🔀 Merging '{}' changes:", other_path);
+    print_store_diff(&diff);
+
+    match storage.save_all(&merged) {
+        Ok(_) => println!("✅ Merged {}.", diff.summary()),
+        Err(e) => eprintln!("⛔ Failed to save merged snippets: {}", e),
+    }
+}
+
+fn resolve_conflict(
+    store: &mut SnippetStore,
+    conflict: &SnippetConflict,
+    selection_ui: &dyn SelectionUI,
+    editor: &dyn TextEditor,
+    other_path: &str,
+) -> bool {
+    let resolutions = Resolution::available(conflict);
+    let choices: Vec<String> = resolutions.iter().map(|r| r.label().to_string()).collect();
+    let prompt = format!("⚔️ Conflict: {}", conflict.describe());
+
+    loop {
+        let Some(resolution) = selection_ui
+            .with_choice_list(&prompt, &choices)
+            .and_then(|c| resolutions.get(c).copied())
+        else {
+            return false;
+        };
+
+        match (resolution, &conflict.local, &conflict.remote) {
+            (Resolution::KeepCurrent, local, _) => store.snippets.extend(local.clone()),
+            (Resolution::TakeOther, _, remote) => store.snippets.extend(remote.clone()),
+            (Resolution::KeepBoth, Some(local), Some(remote)) => {
+                store.snippets.push(local.clone());
+                let mut copy = remote.clone();
+                copy.id = Snippet::generate_id();
+                copy.name = unique_name(store, &remote.name, "merged");
+                store.snippets.push(copy);
+            }
+            (Resolution::Edit, Some(local), Some(remote)) => {
+                match edit_conflict(editor, local, remote, other_path) {
+                    Ok(edited) => {
+                        let mut snippet = local.clone();
+                        apply_edits(&mut snippet, edited);
+                        store.snippets.push(snippet);
+                    }
+                    Err(e) => {
+                        eprintln!("⚠️ {}", e);
+                        continue;
+                    }
+                }
+            }
+            _ => continue,
+        }
+
+        return true;
+    }
+}
+
+fn edit_conflict(
+    editor: &dyn TextEditor,
+    local: &Snippet,
+    remote: &Snippet,
+    other_path: &str,
+) -> Result<PartialSnippet, String> {
+    let to_yaml = |snippet: &Snippet| {
+        serde_yaml::to_string(&PartialSnippet::from(snippet))
+            .map_err(|e| format!("Could not serialize snippet: {}", e))
+    };
+    let text = conflict_markers(&to_yaml(local)?, &to_yaml(remote)?, "current", other_path);
+
+    let edited = editor.edit_text(&text)?;
+    if has_conflict_markers(&edited) {
+        return Err("Conflict markers are still present; resolve them or pick a side.".into());
+    }
+    serde_yaml::from_str(&edited).map_err(|_| "Invalid YAML.".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::SnippetKind, storage::StorageError};
+    use chrono::{Duration, Utc};
+    use std::{
+        cell::RefCell,
+        collections::HashMap,
+        path::{Path, PathBuf},
+    };
+
+    struct MockStorage {
+        store: RefCell<SnippetStore>,
+    }
+
+    impl Storage for MockStorage {
+        fn load(&self) -> Result<SnippetStore, StorageError> {
+            Ok(self.store.borrow().clone())
+        }
+
+        fn save(&self, _: Snippet) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn save_all(&self, store: &SnippetStore) -> Result<(), StorageError> {
+            self.store.replace(store.clone());
+            Ok(())
+        }
+
+        fn get_backups(&self) -> Result<Vec<PathBuf>, StorageError> {
+            Ok(vec![])
+        }
+
+        fn restore_backup(&self, _: &Path) -> Result<(), StorageError> {
+            Ok(())
+        }
+    }
+
+    struct MockReader {
+        files: HashMap<String, SnippetStore>,
+    }
+
+    impl FileReader for MockReader {
+        fn read_yaml(&self, path: &str) -> Result<SnippetStore, StorageError> {
+            self.files
+                .get(path)
+                .cloned()
+                .ok_or_else(|| StorageError::Io(std::io::Error::other("missing")))
+        }
+    }
+
+    struct MockSelection {
+        choices: RefCell<Vec<usize>>,
+    }
+
+    impl SelectionUI for MockSelection {
+        fn with_snippet_list(&self, _: Vec<Snippet>) -> Option<Snippet> {
+            None
+        }
+
+        fn with_backup_list(&self, _: &[String]) -> Option<usize> {
+            None
+        }
+
+        fn with_choice_list(&self, _: &str, _: &[String]) -> Option<usize> {
+            let mut choices = self.choices.borrow_mut();
+            (!choices.is_empty()).then(|| choices.remove(0))
+        }
+    }
+
+    struct MockEditor {
+        replies: RefCell<Vec<String>>,
+        seen: RefCell<Vec<String>>,
+    }
+
+    impl TextEditor for MockEditor {
+        fn edit_text(&self, text: &str) -> Result<String, String> {
+            self.seen.borrow_mut().push(text.to_string());
+            Ok(self.replies.borrow_mut().remove(0))
+        }
+    }
+
+    fn sample_snippet(name: &str, content: &str) -> Snippet {
+        Snippet {
+            id: Snippet::generate_id(),
+            name: name.into(),
+            description: String::new(),
+            content: content.into(),
+            kind: SnippetKind::Shell,
+            tags: vec![],
+            variables: vec![],
+            database: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn store(snippets: &[&Snippet]) -> SnippetStore {
+        SnippetStore {
+            snippets: snippets.iter().map(|s| (*s).clone()).collect(),
+        }
+    }
+
+    fn selection(choices: &[usize]) -> MockSelection {
+        MockSelection {
+            choices: RefCell::new(choices.to_vec()),
+        }
+    }
+
+    fn editor(replies: &[&str]) -> MockEditor {
+        MockEditor {
+            replies: RefCell::new(replies.iter().map(|r| r.to_string()).collect()),
+            seen: RefCell::new(vec![]),
+        }
+    }
+
+    fn contents(storage: &MockStorage) -> Vec<String> {
+        storage
+            .store
+            .borrow()
+            .snippets
+            .iter()
+            .map(|s| format!("{}={}", s.name, s.content))
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_applies_newer_updates_and_additions() {
+        let deploy = sample_snippet("deploy", "make deploy");
+        let mut newer = deploy.clone();
+        newer.content = "./deploy.sh".into();
+        newer.updated_at = deploy.updated_at + Duration::minutes(1);
+        let seed = sample_snippet("seed", "make seed");
+
+        let storage = MockStorage {
+            store: RefCell::new(store(&[&deploy])),
+        };
+        let reader = MockReader {
+            files: HashMap::from([("other.yml".to_string(), store(&[&newer, &seed]))]),
+        };
+
+        merge_command(
+            &storage,
+            &reader,
+            &selection(&[]),
+            &editor(&[]),
+            "other.yml",
+            None,
+        );
+
+        assert_eq!(
+            contents(&storage),
+            vec!["deploy=./deploy.sh", "seed=make seed"]
+        );
+    }
+
+    #[test]
+    fn test_merge_with_base_keeps_local_deletions() {
+        let deploy = sample_snippet("deploy", "make deploy");
+        let seed = sample_snippet("seed", "make seed");
+
+        let storage = MockStorage {
+            store: RefCell::new(store(&[&deploy])),
+        };
+        let reader = MockReader {
+            files: HashMap::from([
+                ("other.yml".to_string(), store(&[&deploy, &seed])),
+                ("base.yml".to_string(), store(&[&deploy, &seed])),
+            ]),
+        };
+
+        merge_command(
+            &storage,
+            &reader,
+            &selection(&[]),
+            &editor(&[]),
+            "other.yml",
+            Some("base.yml"),
+        );
+
+        assert_eq!(contents(&storage), vec!["deploy=make deploy"]);
+    }
+
+    #[test]
+    fn test_conflict_keep_both() {
+        let local = sample_snippet("deploy", "make deploy");
+        let mut other = sample_snippet("deploy", "./deploy.sh");
+        other.updated_at = local.updated_at;
+
+        let storage = MockStorage {
+            store: RefCell::new(store(&[&local])),
+        };
+        let reader = MockReader {
+            files: HashMap::from([("other.yml".to_string(), store(&[&other]))]),
+        };
+
+        merge_command(
+            &storage,
+            &reader,
+            &selection(&[2]),
+            &editor(&[]),
+            "other.yml",
+            None,
+        );
+
+        assert_eq!(
+            contents(&storage),
+            vec!["deploy=make deploy", "deploy-merged=./deploy.sh"]
+        );
+    }
+
+    #[test]
+    fn test_conflict_edited_with_markers() {
+        let base = sample_snippet("deploy", "make deploy");
+        let mut local = base.clone();
+        local.content = "make deploy ENV=prod".into();
+        let mut other = base.clone();
+        other.content = "./deploy.sh".into();
+
+        let storage = MockStorage {
+            store: RefCell::new(store(&[&local])),
+        };
+        let reader = MockReader {
+            files: HashMap::from([
+                ("other.yml".to_string(), store(&[&other])),
+                ("base.yml".to_string(), store(&[&base])),
+            ]),
+        };
+        let resolved = serde_yaml::to_string(&PartialSnippet {
+            content: "./deploy.sh prod".into(),
+            ..PartialSnippet::from(&local)
+        })
+        .unwrap();
+        let editor = editor(&["<<<<<<< current\n", &resolved]);
+
+        merge_command(
+            &storage,
+            &reader,
+            &selection(&[3, 3]),
+            &editor,
+            "other.yml",
+            Some("base.yml"),
+        );
+
+        assert!(editor.seen.borrow()[0].contains(">>>>>>> other.yml"));
+        assert_eq!(contents(&storage), vec!["deploy=./deploy.sh prod"]);
+        assert_eq!(storage.store.borrow().snippets[0].id, local.id);
+    }
+
+    #[test]
+    fn test_cancelled_conflict_changes_nothing() {
+        let local = sample_snippet("deploy", "make deploy");
+        let mut other = sample_snippet("deploy", "./deploy.sh");
+        other.updated_at = local.updated_at;
+
+        let storage = MockStorage {
+            store: RefCell::new(store(&[&local])),
+        };
+        let reader = MockReader {
+            files: HashMap::from([("other.yml".to_string(), store(&[&other]))]),
+        };
+
+        merge_command(
+            &storage,
+            &reader,
+            &selection(&[]),
+            &editor(&[]),
+            "other.yml",
+            None,
+        );
+
+        assert_eq!(contents(&storage), vec!["deploy=make deploy"]);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::storage::{backend::SnippetBackend, journal::store_hash};
+
+pub fn migrate_command(source: &dyn SnippetBackend, target: &dyn SnippetBackend) {
+    if source.kind() == target.kind() {
+        println!("⚠️ Store already uses the '{}' backend.", target.kind());
+        return;
+    }
+
+    let store = match source.read() {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("⛔ Failed to read snippets: {}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = target.write(&store) {
+        eprintln!("⛔ Failed to write snippets: {}", e);
+        return;
+    }
+
+    let verified = target
+        .read()
+        .is_ok_and(|written| store_hash(&written) == store_hash(&store));
+    if !verified {
+        if let Err(e) = target.clear() {
+            eprintln!(
+                "⚠️ Failed to clean up the '{}' backend: {}",
+                target.kind(),
+                e
+            );
+        }
+        eprintln!("⛔ Migrated snippets did not match the originals; nothing was changed.");
+        return;
+    }
+
+    if let Err(e) = source.clear() {
+        eprintln!(
+            "⛔ Snippets were copied but the old '{}' data could not be removed: {}",
+            source.kind(),
+            e
+        );
+        return;
+    }
+
+    println!(
+        "✅ Migrated {} snippet(s) from '{}' to '{}'.",
+        store.snippets.len(),
+        source.kind(),
+        target.kind()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{Snippet, SnippetKind, SnippetStore},
+        storage::{StorageError, backend::BackendKind},
+    };
+    use chrono::Utc;
+    use std::cell::RefCell;
+
+    struct MockBackend {
+        kind: BackendKind,
+        store: RefCell<Option<SnippetStore>>,
+        lossy: bool,
+    }
+
+    impl MockBackend {
+        fn new(kind: BackendKind, store: Option<SnippetStore>) -> Self {
+            Self {
+                kind,
+                store: RefCell::new(store),
+                lossy: false,
+            }
+        }
+    }
+
+    impl SnippetBackend for MockBackend {
+        fn kind(&self) -> BackendKind {
+            self.kind
+        }
+
+        fn exists(&self) -> bool {
+            self.store.borrow().is_some()
+        }
+
+        fn read(&self) -> Result<SnippetStore, StorageError> {
+            Ok(self.store.borrow().clone().unwrap_or_default())
+        }
+
+        fn write(&self, store: &SnippetStore) -> Result<(), StorageError> {
+            let mut store = store.clone();
+            if self.lossy {
+                store.snippets.pop();
+            }
+            self.store.replace(Some(store));
+            Ok(())
+        }
+
+        fn clear(&self) -> Result<(), StorageError> {
+            self.store.replace(None);
+            Ok(())
+        }
+    }
+
+    fn sample_store() -> SnippetStore {
+        SnippetStore {
+            snippets: ["a", "b"]
+                .into_iter()
+                .map(|name| Snippet {
+                    id: Snippet::generate_id(),
+                    name: name.into(),
+                    description: String::new(),
+                    content: "ls".into(),
+                    kind: SnippetKind::Shell,
+                    tags: vec![],
+                    variables: vec![],
+                    database: None,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_migrate_moves_snippets() {
+        let source = MockBackend::new(BackendKind::Yaml, Some(sample_store()));
+        let target = MockBackend::new(BackendKind::Dir, None);
+
+        migrate_command(&source, &target);
+
+        assert!(!source.exists());
+        assert_eq!(target.read().unwrap().snippets.len(), 2);
+    }
+
+    #[test]
+    fn test_migrate_keeps_source_when_verification_fails() {
+        let source = MockBackend::new(BackendKind::Yaml, Some(sample_store()));
+        let mut target = MockBackend::new(BackendKind::Dir, None);
+        target.lossy = true;
+
+        migrate_command(&source, &target);
+
+        assert!(source.exists());
+        assert!(!target.exists());
+    }
+
+    #[test]
+    fn test_migrate_to_same_backend_is_a_no_op() {
+        let source = MockBackend::new(BackendKind::Yaml, Some(sample_store()));
+        let target = MockBackend::new(BackendKind::Yaml, Some(sample_store()));
+
+        migrate_command(&source, &target);
+
+        assert!(source.exists());
+    }
+}
@@ -1,12 +1,25 @@
+pub mod backups;
 pub mod copy;
 pub mod delete;
+pub mod diff;
 pub mod edit;
 pub mod export;
 pub mod helper;
+pub mod history;
 pub mod import;
 pub mod list;
+pub mod merge;
+pub mod migrate;
+pub mod move_snippet;
 pub mod restore;
+pub mod revert;
 pub mod run;
 pub mod save;
 pub mod search;
+pub mod secret;
 pub mod show;
+pub mod store;
+pub mod sync;
+pub mod trash;
+pub mod trust;
+pub mod undo;
@@ -0,0 +1,174 @@
+use crate::{commands::helper::get_snippet, storage::Storage, ui::SelectionUI};
+
+pub fn move_command(
+    source: &dyn Storage,
+    target: &dyn Storage,
+    selection_ui: &dyn SelectionUI,
+    name: String,
+    target_name: &str,
+) {
+    let mut source_store = match source.load() {
+        Ok(s) => s,
+        Err(_) => {
+            println!("📭 No snippets saved yet.");
+            return;
+        }
+    };
+
+    let Some(snippet) = get_snippet(&source_store, selection_ui, name) else {
+        return;
+    };
+
+    let mut target_store = match target.load() {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("⛔ Failed to load store '{}': {}", target_name, e);
+            return;
+        }
+    };
+
+    if target_store
+        .snippets
+        .iter()
+        .any(|s| s.id == snippet.id || s.name.eq_ignore_ascii_case(&snippet.name))
+    {
+        eprintln!(
+            "⛔ Store '{}' already has a snippet named '{}'.",
+            target_name, snippet.name
+        );
+        return;
+    }
+
+    target_store.snippets.push(snippet.clone());
+    if let Err(e) = target.save_all(&target_store) {
+        eprintln!("⛔ Failed to save to store '{}': {}", target_name, e);
+        return;
+    }
+
+    source_store.snippets.retain(|s| s.id != snippet.id);
+    match source.save_all(&source_store) {
+        Ok(_) => println!("📦 Snippet '{}' moved to '{}'.", snippet.name, target_name),
+        Err(e) => eprintln!(
+            "⚠️ Snippet '{}' was copied to '{}' but could not be removed here: {}",
+            snippet.name, target_name, e
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{Snippet, SnippetKind, SnippetStore},
+        storage::StorageError,
+    };
+    use std::cell::RefCell;
+
+    struct MockStorage {
+        store: RefCell<SnippetStore>,
+    }
+
+    impl MockStorage {
+        fn with(snippets: Vec<Snippet>) -> Self {
+            Self {
+                store: RefCell::new(SnippetStore { snippets }),
+            }
+        }
+
+        fn names(&self) -> Vec<String> {
+            self.store
+                .borrow()
+                .snippets
+                .iter()
+                .map(|s| s.name.clone())
+                .collect()
+        }
+    }
+
+    impl Storage for MockStorage {
+        fn load(&self) -> Result<SnippetStore, StorageError> {
+            Ok(self.store.borrow().clone())
+        }
+
+        fn save(&self, _: Snippet) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn save_all(&self, store: &SnippetStore) -> Result<(), StorageError> {
+            self.store.replace(store.clone());
+            Ok(())
+        }
+
+        fn get_backups(&self) -> Result<Vec<std::path::PathBuf>, StorageError> {
+            Ok(vec![])
+        }
+
+        fn restore_backup(&self, _: &std::path::Path) -> Result<(), StorageError> {
+            Ok(())
+        }
+    }
+
+    struct MockSelectionUI {
+        snippet: Option<Snippet>,
+    }
+
+    impl SelectionUI for MockSelectionUI {
+        fn with_snippet_list(&self, _: Vec<Snippet>) -> Option<Snippet> {
+            self.snippet.clone()
+        }
+
+        fn with_backup_list(&self, _: &[String]) -> Option<usize> {
+            None
+        }
+
+        fn with_choice_list(&self, _: &str, _: &[String]) -> Option<usize> {
+            None
+        }
+    }
+
+    fn sample_snippet(name: &str) -> Snippet {
+        Snippet {
+            id: Snippet::generate_id(),
+            name: name.into(),
+            description: String::new(),
+            content: "ls".into(),
+            kind: SnippetKind::Shell,
+            tags: vec![],
+            variables: vec![],
+            database: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_move_transfers_snippet() {
+        let snippet = sample_snippet("deploy");
+        let source = MockStorage::with(vec![snippet.clone(), sample_snippet("other")]);
+        let target = MockStorage::with(vec![]);
+        let selection_ui = MockSelectionUI {
+            snippet: Some(snippet.clone()),
+        };
+
+        move_command(&source, &target, &selection_ui, "deploy".into(), "work");
+
+        assert_eq!(source.names(), vec!["other"]);
+        assert_eq!(target.names(), vec!["deploy"]);
+        assert_eq!(target.store.borrow().snippets[0].id, snippet.id);
+    }
+
+    #[test]
+    fn test_move_refuses_name_conflict() {
+        let snippet = sample_snippet("deploy");
+        let source = MockStorage::with(vec![snippet.clone()]);
+        let target = MockStorage::with(vec![sample_snippet("Deploy")]);
+        let selection_ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+
+        move_command(&source, &target, &selection_ui, "deploy".into(), "work");
+
+        assert_eq!(source.names(), vec!["deploy"]);
+        assert_eq!(target.names(), vec!["Deploy"]);
+    }
+}
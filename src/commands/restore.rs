@@ -1,45 +1,255 @@
-use crate::storage::Storage;
-use crate::ui::SelectionUI;
+use std::path::PathBuf;
+
+use crate::{
+    commands::backups::{backup_name, print_store_diff},
+    models::{PartialSnippet, Snippet, SnippetStore, StoreDiff},
+    storage::{BackupArchive, Storage},
+    ui::{ConfirmPrompt, MultiSelectUI, SelectionUI},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Resolution {
+    Overwrite,
+    KeepBoth,
+    Skip,
+}
+
+impl Resolution {
+    const ALL: [Resolution; 3] = [
+        Resolution::Overwrite,
+        Resolution::KeepBoth,
+        Resolution::Skip,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Resolution::Overwrite => "Overwrite the current snippet",
+            Resolution::KeepBoth => "Keep both (restore under a new name)",
+            Resolution::Skip => "Skip",
+        }
+    }
+}
+
+pub fn restore_command(
+    storage: &dyn Storage,
+    archive: &dyn BackupArchive,
+    selection_ui: &dyn SelectionUI,
+    confirm_prompt: &dyn ConfirmPrompt,
+) {
+    let Some((full_path, backup)) = select_backup(storage, archive, selection_ui) else {
+        return;
+    };
+    let current = storage.load().unwrap_or_else(|_| SnippetStore::default());
+
+    let diff = StoreDiff::between(&current, &backup);
+    if diff.is_empty() {
+        println!("✅ Backup matches the current snippets, nothing to restore.");
+        return;
+    }
+
+    println!("🔀 Restoring {} will change:", backup_name(&full_path));
+    print_store_diff(&diff);
+
+    if !confirm_prompt.confirm("Restore this backup? The current snippets are backed up first.") {
+        println!("🚫 Cancelled.");
+        return;
+    }
+
+    match storage.restore_backup(&full_path) {
+        Ok(_) => println!("✅ Backup restored successfully."),
+        Err(e) => eprintln!("⛔ Failed to restore backup: {}", e),
+    };
+}
+
+pub fn restore_snippets_command(
+    storage: &dyn Storage,
+    archive: &dyn BackupArchive,
+    selection_ui: &dyn SelectionUI,
+    multi_select_ui: &dyn MultiSelectUI,
+) {
+    let Some((_, backup)) = select_backup(storage, archive, selection_ui) else {
+        return;
+    };
+
+    if backup.snippets.is_empty() {
+        println!("📭 This backup has no snippets.");
+        return;
+    }
+
+    let labels: Vec<String> = backup
+        .snippets
+        .iter()
+        .map(|s| {
+            if s.description.is_empty() {
+                s.name.clone()
+            } else {
+                format!("{} — {}", s.name, s.description)
+            }
+        })
+        .collect();
+
+    let selected =
+        match multi_select_ui.with_multi_choice_list("Select snippets to restore", &labels) {
+            Some(s) if !s.is_empty() => s,
+            _ => {
+                println!("🚫 Cancelled.");
+                return;
+            }
+        };
+
+    let mut store = storage.load().unwrap_or_else(|_| SnippetStore::default());
+    let mut restored = Vec::new();
+
+    for index in selected {
+        let Some(snippet) = backup.snippets.get(index).cloned() else {
+            continue;
+        };
+
+        let conflict = find_conflict(&store, &snippet);
+        let resolution = match conflict {
+            None => Resolution::Overwrite,
+            Some(i)
+                if PartialSnippet::from(&store.snippets[i]) == PartialSnippet::from(&snippet) =>
+            {
+                println!("✅ '{}' is already up to date.", snippet.name);
+                continue;
+            }
+            Some(i) => {
+                let choices: Vec<String> = Resolution::ALL
+                    .iter()
+                    .map(|r| r.label().to_string())
+                    .collect();
+                let prompt = format!(
+                    "'{}' conflicts with the current snippet '{}'",
+                    snippet.name, store.snippets[i].name
+                );
+                selection_ui
+                    .with_choice_list(&prompt, &choices)
+                    .and_then(|c| Resolution::ALL.get(c).copied())
+                    .unwrap_or(Resolution::Skip)
+            }
+        };
+
+        if let Some(name) = merge_snippet(&mut store, snippet, resolution) {
+            restored.push(name);
+        }
+    }
 
-pub fn restore_command(storage: &dyn Storage, selection_ui: &dyn SelectionUI) {
+    if restored.is_empty() {
+        println!("📭 Nothing restored.");
+        return;
+    }
+
+    match storage.save_all(&store) {
+        Ok(_) => println!("✅ Restored: {}", restored.join(", ")),
+        Err(e) => eprintln!("⛔ Failed to restore snippets: {}", e),
+    }
+}
+
+pub fn find_conflict(store: &SnippetStore, snippet: &Snippet) -> Option<usize> {
+    store
+        .snippets
+        .iter()
+        .position(|s| !snippet.id.is_empty() && s.id == snippet.id)
+        .or_else(|| {
+            store
+                .snippets
+                .iter()
+                .position(|s| s.name.eq_ignore_ascii_case(&snippet.name))
+        })
+}
+
+pub fn merge_snippet(
+    store: &mut SnippetStore,
+    mut snippet: Snippet,
+    resolution: Resolution,
+) -> Option<String> {
+    match (resolution, find_conflict(store, &snippet)) {
+        (Resolution::Skip, _) => return None,
+        (Resolution::Overwrite, Some(i)) => {
+            snippet.id = store.snippets[i].id.clone();
+            store.snippets[i] = snippet;
+            return Some(store.snippets[i].name.clone());
+        }
+        (Resolution::KeepBoth, Some(_)) => {
+            snippet.id = Snippet::generate_id();
+            snippet.name = unique_name(store, &snippet.name, "restored");
+        }
+        (_, None) => {
+            if snippet.id.is_empty() {
+                snippet.id = Snippet::generate_id();
+            }
+        }
+    }
+
+    let name = snippet.name.clone();
+    store.snippets.push(snippet);
+    Some(name)
+}
+
+pub fn unique_name(store: &SnippetStore, name: &str, suffix: &str) -> String {
+    let taken = |candidate: &str| {
+        store
+            .snippets
+            .iter()
+            .any(|s| s.name.eq_ignore_ascii_case(candidate))
+    };
+
+    let base = format!("{}-{}", name, suffix);
+    if !taken(&base) {
+        return base;
+    }
+
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|candidate| !taken(candidate))
+        .unwrap()
+}
+
+fn select_backup(
+    storage: &dyn Storage,
+    archive: &dyn BackupArchive,
+    selection_ui: &dyn SelectionUI,
+) -> Option<(PathBuf, SnippetStore)> {
     let backups = match storage.get_backups() {
         Ok(s) => s,
         Err(_) => {
             println!("📭 No backups created yet.");
-            return;
+            return None;
         }
     };
 
     if backups.is_empty() {
         println!("📭 No backups found.");
-        return;
+        return None;
     }
 
     let display_names: Vec<String> = backups
         .iter()
-        .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+        .map(|p| match archive.load_backup(p) {
+            Ok(store) => format!("{} ({} snippets)", backup_name(p), store.snippets.len()),
+            Err(_) => format!("{} (unreadable)", backup_name(p)),
+        })
         .collect();
 
-    let selected_index = match selection_ui.with_backup_list(&display_names) {
-        Some(i) => i,
-        None => return,
-    };
-
-    let full_path = backups.get(selected_index).unwrap().clone();
+    let selected_index = selection_ui.with_backup_list(&display_names)?;
+    let full_path = backups.get(selected_index)?.clone();
 
-    match storage.restore_backup(&full_path) {
-        Ok(_) => println!("✅ Backup restored successfully."),
-        Err(e) => eprintln!("⛔ Failed to restore backup: {}", e),
-    };
+    match archive.load_backup(&full_path) {
+        Ok(backup) => Some((full_path, backup)),
+        Err(e) => {
+            eprintln!("⛔ Failed to read backup: {}", e);
+            None
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        models::Snippet,
-        storage::{Storage, StorageError},
-        ui::SelectionUI,
+        models::{Snippet, SnippetKind},
+        storage::StorageError,
     };
     use std::{
         cell::RefCell,
@@ -51,6 +261,7 @@ mod tests {
         restore_called_with: RefCell<Option<PathBuf>>,
         fail_get: bool,
         fail_restore: bool,
+        backup: SnippetStore,
     }
 
     impl Storage for MockStorage {
@@ -86,6 +297,39 @@ mod tests {
         }
     }
 
+    impl BackupArchive for MockStorage {
+        fn load_backup(&self, _: &Path) -> Result<SnippetStore, StorageError> {
+            Ok(self.backup.clone())
+        }
+    }
+
+    struct MockConfirmPrompt {
+        confirm_result: bool,
+    }
+
+    impl ConfirmPrompt for MockConfirmPrompt {
+        fn confirm(&self, _: &str) -> bool {
+            self.confirm_result
+        }
+    }
+
+    fn sample_backup() -> SnippetStore {
+        SnippetStore {
+            snippets: vec![Snippet {
+                id: Snippet::generate_id(),
+                name: "restored".into(),
+                description: String::new(),
+                content: "ls".into(),
+                kind: SnippetKind::Shell,
+                tags: vec![],
+                variables: vec![],
+                database: None,
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+            }],
+        }
+    }
+
     struct MockSelectionUI {
         selected_index: Option<usize>,
     }
@@ -98,6 +342,10 @@ mod tests {
         fn with_backup_list(&self, _backups: &[String]) -> Option<usize> {
             self.selected_index
         }
+
+        fn with_choice_list(&self, _prompt: &str, _choices: &[String]) -> Option<usize> {
+            None
+        }
     }
 
     #[test]
@@ -109,13 +357,21 @@ mod tests {
             restore_called_with: RefCell::new(None),
             fail_get: false,
             fail_restore: false,
+            backup: sample_backup(),
         };
 
         let ui = MockSelectionUI {
             selected_index: Some(0),
         };
 
-        restore_command(&storage, &ui);
+        restore_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockConfirmPrompt {
+                confirm_result: true,
+            },
+        );
 
         assert_eq!(*storage.restore_called_with.borrow(), Some(path));
     }
@@ -127,13 +383,21 @@ mod tests {
             restore_called_with: RefCell::new(None),
             fail_get: false,
             fail_restore: false,
+            backup: sample_backup(),
         };
 
         let ui = MockSelectionUI {
             selected_index: Some(0),
         };
 
-        restore_command(&storage, &ui);
+        restore_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockConfirmPrompt {
+                confirm_result: true,
+            },
+        );
 
         assert!(storage.restore_called_with.borrow().is_none());
     }
@@ -145,13 +409,21 @@ mod tests {
             restore_called_with: RefCell::new(None),
             fail_get: false,
             fail_restore: false,
+            backup: sample_backup(),
         };
 
         let ui = MockSelectionUI {
             selected_index: None,
         };
 
-        restore_command(&storage, &ui);
+        restore_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockConfirmPrompt {
+                confirm_result: true,
+            },
+        );
 
         assert!(storage.restore_called_with.borrow().is_none());
     }
@@ -163,13 +435,21 @@ mod tests {
             restore_called_with: RefCell::new(None),
             fail_get: true,
             fail_restore: false,
+            backup: sample_backup(),
         };
 
         let ui = MockSelectionUI {
             selected_index: Some(0),
         };
 
-        restore_command(&storage, &ui);
+        restore_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockConfirmPrompt {
+                confirm_result: true,
+            },
+        );
 
         assert!(storage.restore_called_with.borrow().is_none());
     }
@@ -183,15 +463,219 @@ mod tests {
             restore_called_with: RefCell::new(None),
             fail_get: false,
             fail_restore: true,
+            backup: sample_backup(),
         };
 
         let ui = MockSelectionUI {
             selected_index: Some(0),
         };
 
-        restore_command(&storage, &ui);
+        restore_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockConfirmPrompt {
+                confirm_result: true,
+            },
+        );
 
         // Called but failed internally
         assert_eq!(*storage.restore_called_with.borrow(), Some(path));
     }
+
+    #[test]
+    fn test_restore_cancelled_at_confirmation() {
+        let storage = MockStorage {
+            backups: vec![PathBuf::from("backup.yml")],
+            restore_called_with: RefCell::new(None),
+            fail_get: false,
+            fail_restore: false,
+            backup: sample_backup(),
+        };
+        let ui = MockSelectionUI {
+            selected_index: Some(0),
+        };
+        let confirm = MockConfirmPrompt {
+            confirm_result: false,
+        };
+
+        restore_command(&storage, &storage, &ui, &confirm);
+
+        assert!(storage.restore_called_with.borrow().is_none());
+    }
+
+    #[test]
+    fn test_restore_skipped_when_backup_matches_current() {
+        let storage = MockStorage {
+            backups: vec![PathBuf::from("backup.yml")],
+            restore_called_with: RefCell::new(None),
+            fail_get: false,
+            fail_restore: false,
+            backup: SnippetStore::default(),
+        };
+        let ui = MockSelectionUI {
+            selected_index: Some(0),
+        };
+        let confirm = MockConfirmPrompt {
+            confirm_result: true,
+        };
+
+        restore_command(&storage, &storage, &ui, &confirm);
+
+        assert!(storage.restore_called_with.borrow().is_none());
+    }
+
+    struct MockMultiSelectUI {
+        selected: Option<Vec<usize>>,
+    }
+
+    impl MultiSelectUI for MockMultiSelectUI {
+        fn with_multi_choice_list(&self, _: &str, _: &[String]) -> Option<Vec<usize>> {
+            self.selected.clone()
+        }
+    }
+
+    struct MockChoiceUI {
+        choice: Option<usize>,
+    }
+
+    impl SelectionUI for MockChoiceUI {
+        fn with_snippet_list(&self, _: Vec<Snippet>) -> Option<Snippet> {
+            None
+        }
+
+        fn with_backup_list(&self, _: &[String]) -> Option<usize> {
+            Some(0)
+        }
+
+        fn with_choice_list(&self, _: &str, _: &[String]) -> Option<usize> {
+            self.choice
+        }
+    }
+
+    struct MergeStorage {
+        current: RefCell<SnippetStore>,
+        backup: SnippetStore,
+    }
+
+    impl Storage for MergeStorage {
+        fn load(&self) -> Result<SnippetStore, StorageError> {
+            Ok(self.current.borrow().clone())
+        }
+
+        fn save(&self, _: Snippet) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn save_all(&self, store: &SnippetStore) -> Result<(), StorageError> {
+            self.current.replace(store.clone());
+            Ok(())
+        }
+
+        fn get_backups(&self) -> Result<Vec<PathBuf>, StorageError> {
+            Ok(vec![PathBuf::from("backup.yml")])
+        }
+
+        fn restore_backup(&self, _: &Path) -> Result<(), StorageError> {
+            panic!("selective restore must not replace the whole store")
+        }
+    }
+
+    impl BackupArchive for MergeStorage {
+        fn load_backup(&self, _: &Path) -> Result<SnippetStore, StorageError> {
+            Ok(self.backup.clone())
+        }
+    }
+
+    fn named(name: &str, content: &str) -> Snippet {
+        let mut snippet = sample_backup().snippets.remove(0);
+        snippet.id = Snippet::generate_id();
+        snippet.name = name.into();
+        snippet.content = content.into();
+        snippet
+    }
+
+    #[test]
+    fn test_selective_restore_merges_only_selected() {
+        let storage = MergeStorage {
+            current: RefCell::new(SnippetStore {
+                snippets: vec![named("kept", "pwd")],
+            }),
+            backup: SnippetStore {
+                snippets: vec![named("one", "ls"), named("two", "id")],
+            },
+        };
+
+        restore_snippets_command(
+            &storage,
+            &storage,
+            &MockChoiceUI { choice: None },
+            &MockMultiSelectUI {
+                selected: Some(vec![1]),
+            },
+        );
+
+        let names: Vec<String> = storage
+            .current
+            .borrow()
+            .snippets
+            .iter()
+            .map(|s| s.name.clone())
+            .collect();
+        assert_eq!(names, vec!["kept", "two"]);
+    }
+
+    #[test]
+    fn test_selective_restore_conflict_resolutions() {
+        let current = named("deploy", "new");
+        let mut old = current.clone();
+        old.content = "old".into();
+
+        let mut store = SnippetStore {
+            snippets: vec![current.clone()],
+        };
+        assert_eq!(
+            merge_snippet(&mut store, old.clone(), Resolution::Skip),
+            None
+        );
+        assert_eq!(store.snippets[0].content, "new");
+
+        merge_snippet(&mut store, old.clone(), Resolution::KeepBoth);
+        merge_snippet(&mut store, old.clone(), Resolution::KeepBoth);
+        assert_eq!(store.snippets[1].name, "deploy-restored");
+        assert_eq!(store.snippets[2].name, "deploy-restored-2");
+        assert_ne!(store.snippets[1].id, current.id);
+
+        merge_snippet(&mut store, old, Resolution::Overwrite);
+        assert_eq!(store.snippets.len(), 3);
+        assert_eq!(store.snippets[0].id, current.id);
+        assert_eq!(store.snippets[0].content, "old");
+    }
+
+    #[test]
+    fn test_selective_restore_overwrites_name_conflict_keeping_current_id() {
+        let current = named("Deploy", "new");
+        let storage = MergeStorage {
+            current: RefCell::new(SnippetStore {
+                snippets: vec![current.clone()],
+            }),
+            backup: SnippetStore {
+                snippets: vec![named("deploy", "old")],
+            },
+        };
+
+        restore_snippets_command(
+            &storage,
+            &storage,
+            &MockChoiceUI { choice: Some(0) },
+            &MockMultiSelectUI {
+                selected: Some(vec![0]),
+            },
+        );
+
+        let store = storage.current.borrow();
+        assert_eq!(store.snippets.len(), 1);
+        assert_eq!(store.snippets[0].id, current.id);
+        assert_eq!(store.snippets[0].content, "old");
+    }
 }
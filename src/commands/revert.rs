@@ -0,0 +1,231 @@
+use crate::{
+    commands::helper::{apply_edits, get_snippet},
+    storage::{SnippetHistory, Storage},
+    ui::SelectionUI,
+};
+
+pub fn revert_command(
+    storage: &dyn Storage,
+    history: &dyn SnippetHistory,
+    selection_ui: &dyn SelectionUI,
+    name: String,
+    rev: u32,
+) {
+    let mut store = match storage.load() {
+        Ok(s) => s,
+        Err(_) => {
+            println!("📭 No snippets saved yet.");
+            return;
+        }
+    };
+
+    let Some(mut snippet) = get_snippet(&store, selection_ui, name) else {
+        return;
+    };
+
+    let revisions = match history.get_history(&snippet.id) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("⛔ Failed to load history: {}", e);
+            return;
+        }
+    };
+
+    let Some(revision) = revisions.into_iter().find(|r| r.rev == rev) else {
+        println!("⛔ Revision #{} not found for '{}'.", rev, snippet.name);
+        return;
+    };
+
+    if store
+        .snippets
+        .iter()
+        .any(|s| s.name.eq_ignore_ascii_case(&revision.snippet.name) && s.id != snippet.id)
+    {
+        eprintln!(
+            "⛔ Another snippet with the name '{}' already exists.",
+            revision.snippet.name
+        );
+        return;
+    }
+
+    apply_edits(&mut snippet, revision.snippet);
+    if let Some(slot) = store.snippets.iter_mut().find(|s| s.id == snippet.id) {
+        *slot = snippet.clone();
+    }
+
+    if let Err(err) = storage.save_all(&store) {
+        eprintln!("⛔ Failed to revert snippet: {}", err);
+    } else {
+        println!(
+            "⏪ Snippet '{}' reverted to revision #{}.",
+            snippet.name, rev
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{PartialSnippet, Revision, Snippet, SnippetKind, SnippetStore};
+    use crate::storage::StorageError;
+    use std::cell::RefCell;
+
+    struct MockStorage {
+        store: RefCell<SnippetStore>,
+        save_calls: RefCell<usize>,
+    }
+
+    impl Storage for MockStorage {
+        fn load(&self) -> Result<SnippetStore, StorageError> {
+            Ok(self.store.borrow().clone())
+        }
+
+        fn save(&self, _: Snippet) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn save_all(&self, store: &SnippetStore) -> Result<(), StorageError> {
+            *self.save_calls.borrow_mut() += 1;
+            self.store.replace(store.clone());
+            Ok(())
+        }
+
+        fn get_backups(&self) -> Result<Vec<std::path::PathBuf>, StorageError> {
+            Ok(vec![])
+        }
+
+        fn restore_backup(&self, _: &std::path::Path) -> Result<(), StorageError> {
+            Ok(())
+        }
+    }
+
+    struct MockHistory {
+        revisions: Vec<Revision>,
+    }
+
+    impl SnippetHistory for MockHistory {
+        fn get_history(&self, _: &str) -> Result<Vec<Revision>, StorageError> {
+            Ok(self.revisions.clone())
+        }
+    }
+
+    struct MockSelectionUI {
+        snippet: Option<Snippet>,
+    }
+
+    impl SelectionUI for MockSelectionUI {
+        fn with_snippet_list(&self, _: Vec<Snippet>) -> Option<Snippet> {
+            self.snippet.clone()
+        }
+
+        fn with_backup_list(&self, _: &[String]) -> Option<usize> {
+            None
+        }
+
+        fn with_choice_list(&self, _: &str, _: &[String]) -> Option<usize> {
+            None
+        }
+    }
+
+    fn sample_snippet(name: &str) -> Snippet {
+        Snippet {
+            id: Snippet::generate_id(),
+            name: name.into(),
+            description: "current".into(),
+            content: "echo current".into(),
+            kind: SnippetKind::Shell,
+            tags: vec![],
+            variables: vec![],
+            database: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    fn revision(rev: u32, snippet: &Snippet, content: &str) -> Revision {
+        let mut partial = PartialSnippet::from(snippet);
+        partial.content = content.into();
+        partial.description = format!("rev {}", rev);
+
+        Revision {
+            rev,
+            recorded_at: chrono::Utc::now(),
+            snippet: partial,
+        }
+    }
+
+    #[test]
+    fn test_revert_restores_revision_fields() {
+        let snippet = sample_snippet("test");
+        let storage = MockStorage {
+            store: RefCell::new(SnippetStore {
+                snippets: vec![snippet.clone()],
+            }),
+            save_calls: RefCell::new(0),
+        };
+        let history = MockHistory {
+            revisions: vec![
+                revision(1, &snippet, "echo first"),
+                revision(2, &snippet, "echo current"),
+            ],
+        };
+        let selection_ui = MockSelectionUI {
+            snippet: Some(snippet.clone()),
+        };
+
+        revert_command(&storage, &history, &selection_ui, "test".into(), 1);
+
+        let store = storage.store.borrow();
+        assert_eq!(store.snippets.len(), 1);
+        assert_eq!(store.snippets[0].id, snippet.id);
+        assert_eq!(store.snippets[0].content, "echo first");
+        assert_eq!(store.snippets[0].description, "rev 1");
+    }
+
+    #[test]
+    fn test_revert_unknown_revision() {
+        let snippet = sample_snippet("test");
+        let storage = MockStorage {
+            store: RefCell::new(SnippetStore {
+                snippets: vec![snippet.clone()],
+            }),
+            save_calls: RefCell::new(0),
+        };
+        let history = MockHistory {
+            revisions: vec![revision(1, &snippet, "echo first")],
+        };
+        let selection_ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+
+        revert_command(&storage, &history, &selection_ui, "test".into(), 7);
+
+        assert_eq!(*storage.save_calls.borrow(), 0);
+    }
+
+    #[test]
+    fn test_revert_refuses_name_conflict() {
+        let snippet = sample_snippet("test");
+        let other = sample_snippet("other");
+        let storage = MockStorage {
+            store: RefCell::new(SnippetStore {
+                snippets: vec![snippet.clone(), other],
+            }),
+            save_calls: RefCell::new(0),
+        };
+
+        let mut old = revision(1, &snippet, "echo first");
+        old.snippet.name = "Other".into();
+
+        let history = MockHistory {
+            revisions: vec![old],
+        };
+        let selection_ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+
+        revert_command(&storage, &history, &selection_ui, "test".into(), 1);
+
+        assert_eq!(*storage.save_calls.borrow(), 0);
+    }
+}
@@ -1,12 +1,25 @@
+use std::collections::HashMap;
+
 use crate::{
-    command_runner::CommandRunner, commands::helper::get_snippet, storage::Storage, ui::SelectionUI,
+    command_runner::{CommandRunner, RunRequest},
+    commands::helper::{get_snippet, render_content},
+    models::SnippetKind,
+    storage::{SecretStore, SnippetSources, Storage},
+    template,
+    ui::{InputPrompt, SelectionUI},
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn run_command(
     storage: &dyn Storage,
+    sources: &dyn SnippetSources,
     selection_ui: &dyn SelectionUI,
+    prompt: &dyn InputPrompt,
     runner: &dyn CommandRunner,
+    secrets: &dyn SecretStore,
     name: String,
+    values: HashMap<String, String>,
+    database: Option<String>,
 ) {
     let store = match storage.load() {
         Ok(s) => s,
@@ -16,42 +29,104 @@ pub fn run_command(
         }
     };
 
-    let snippet = match get_snippet(&store, selection_ui, name) {
+    let mut snippet = match get_snippet(&store, selection_ui, name) {
         Some(s) => s,
         None => return,
     };
 
-    if !snippet.executable {
+    if !snippet.kind.is_executable() {
         println!("⛔ Snippet '{}' not executable.", snippet.name);
         return;
     }
 
-    println!("🚀 Running: {}", snippet.name);
-    println!("📋 {}", snippet.content);
+    let Some(env) = resolve_secrets(&snippet.content, snippet.kind, secrets) else {
+        return;
+    };
+    snippet.content = template::secrets_to_env(&snippet.content);
+
+    let Some(content) = render_content(&snippet, &values, selection_ui, prompt, runner) else {
+        return;
+    };
+
+    match sources.source_of(&snippet.id) {
+        Some(source) => println!("🚀 Running: {} (from {})", snippet.name, source),
+        None => println!("🚀 Running: {}", snippet.name),
+    }
+    println!("📋 {}", content);
+
+    let request = RunRequest {
+        kind: snippet.kind,
+        content: &content,
+        database: database.as_deref().or(snippet.database.as_deref()),
+        env: &env,
+    };
 
-    match runner.run(&snippet.content) {
+    match runner.run(&request) {
         Ok(code) if code.success() => println!("✅ Command ran successfully."),
         Ok(code) => println!("⚠️ Command exited with status: {}", code),
         Err(err) => println!("⛔ Failed to run command: {}", err),
     }
 }
 
+fn resolve_secrets(
+    content: &str,
+    kind: SnippetKind,
+    secrets: &dyn SecretStore,
+) -> Option<Vec<(String, String)>> {
+    let names = template::parse_secret_refs(content);
+    if names.is_empty() {
+        return Some(vec![]);
+    }
+    if !kind.is_shell() {
+        eprintln!(
+            "⛔ Secret references only work in shell snippets; '{}' snippets can't read them.",
+            kind
+        );
+        return None;
+    }
+
+    let mut env = Vec::new();
+    for name in names {
+        match secrets.get_secret(&name) {
+            Ok(Some(value)) => env.push((template::secret_env_name(&name), value)),
+            Ok(None) => {
+                eprintln!(
+                    "⛔ Secret '{}' not found. Add it with `markit secret set {}`.",
+                    name, name
+                );
+                return None;
+            }
+            Err(e) => {
+                eprintln!("⛔ Failed to read secrets: {}", e);
+                return None;
+            }
+        }
+    }
+    Some(env)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        models::Snippet,
+        models::{Snippet, SnippetKind, Variable},
         storage::{Storage, StorageError},
-        ui::SelectionUI,
+        ui::{InputPrompt, SelectionUI},
     };
     use chrono::Utc;
-    use std::process::ExitStatus;
+    use std::{cell::RefCell, process::ExitStatus};
 
     struct MockStorage {
         snippet: Option<Snippet>,
         fail_load: bool,
     }
 
+    impl SnippetSources for MockStorage {
+        fn source_of(&self, _: &str) -> Option<String> {
+            None
+        }
+    }
+
     impl Storage for MockStorage {
         fn load(&self) -> Result<crate::models::SnippetStore, StorageError> {
             if self.fail_load {
@@ -92,28 +167,108 @@ mod tests {
         fn with_backup_list(&self, _backups: &[String]) -> Option<usize> {
             Some(0)
         }
+
+        fn with_choice_list(&self, _prompt: &str, _choices: &[String]) -> Option<usize> {
+            Some(0)
+        }
+    }
+
+    struct MockInputPrompt {
+        value: Option<String>,
+    }
+
+    impl InputPrompt for MockInputPrompt {
+        fn input(&self, _message: &str, default: Option<&str>) -> Option<String> {
+            self.value.clone().or(default.map(String::from))
+        }
     }
 
     struct MockCommandRunner {
         result: Result<ExitStatus, std::io::Error>,
+        last_kind: RefCell<Option<SnippetKind>>,
+        last_command: RefCell<Option<String>>,
+        last_database: RefCell<Option<String>>,
+        last_env: RefCell<Vec<(String, String)>>,
+    }
+
+    impl MockCommandRunner {
+        fn new(result: Result<ExitStatus, std::io::Error>) -> Self {
+            Self {
+                result,
+                last_kind: RefCell::new(None),
+                last_command: RefCell::new(None),
+                last_database: RefCell::new(None),
+                last_env: RefCell::new(vec![]),
+            }
+        }
     }
 
     impl CommandRunner for MockCommandRunner {
-        fn run(&self, _command: &str) -> Result<ExitStatus, std::io::Error> {
+        fn run(&self, request: &RunRequest) -> Result<ExitStatus, std::io::Error> {
+            self.last_kind.replace(Some(request.kind));
+            self.last_command.replace(Some(request.content.to_string()));
+            self.last_database
+                .replace(request.database.map(String::from));
+            self.last_env.replace(request.env.to_vec());
             match &self.result {
                 Ok(status) => Ok(*status),
                 Err(e) => Err(std::io::Error::new(e.kind(), e.to_string())),
             }
         }
+
+        fn capture(&self, command: &str) -> Result<String, std::io::Error> {
+            match command {
+                "kubectl get pods -o name" => Ok("pod/api-1\npod/api-2\n".to_string()),
+                _ => Err(std::io::Error::other("command failed")),
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct MockSecrets {
+        secrets: HashMap<String, String>,
+    }
+
+    impl SecretStore for MockSecrets {
+        fn is_initialized(&self) -> bool {
+            true
+        }
+
+        fn initialize(&self, _: &str) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn secret_names(&self) -> Result<Vec<String>, StorageError> {
+            Ok(self.secrets.keys().cloned().collect())
+        }
+
+        fn get_secret(&self, name: &str) -> Result<Option<String>, StorageError> {
+            Ok(self.secrets.get(name).cloned())
+        }
+
+        fn set_secret(&self, _: &str, _: &str) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn remove_secret(&self, _: &str) -> Result<bool, StorageError> {
+            Ok(false)
+        }
     }
 
     fn test_snippet(name: &str, executable: bool) -> Snippet {
         Snippet {
+            id: Snippet::generate_id(),
             name: name.to_string(),
             description: "desc".to_string(),
             content: "echo test".to_string(),
-            executable,
+            kind: if executable {
+                SnippetKind::Shell
+            } else {
+                SnippetKind::Plain
+            },
             tags: vec![],
+            variables: vec![],
+            database: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -140,11 +295,19 @@ mod tests {
             snippet: Some(snippet),
         };
 
-        let runner = MockCommandRunner {
-            result: Ok(fake_exit_status(true)),
-        };
-
-        run_command(&storage, &ui, &runner, "test".to_string());
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        run_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockInputPrompt { value: None },
+            &runner,
+            &MockSecrets::default(),
+            "test".to_string(),
+            HashMap::new(),
+            None,
+        );
     }
 
     #[test]
@@ -160,11 +323,19 @@ mod tests {
             snippet: Some(snippet),
         };
 
-        let runner = MockCommandRunner {
-            result: Ok(fake_exit_status(false)),
-        };
-
-        run_command(&storage, &ui, &runner, "test".to_string());
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(false)));
+
+        run_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockInputPrompt { value: None },
+            &runner,
+            &MockSecrets::default(),
+            "test".to_string(),
+            HashMap::new(),
+            None,
+        );
     }
 
     #[test]
@@ -180,11 +351,19 @@ mod tests {
             snippet: Some(snippet),
         };
 
-        let runner = MockCommandRunner {
-            result: Err(std::io::Error::other("Mock error")),
-        };
-
-        run_command(&storage, &ui, &runner, "test".to_string());
+        let runner = MockCommandRunner::new(Err(std::io::Error::other("Mock error")));
+
+        run_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockInputPrompt { value: None },
+            &runner,
+            &MockSecrets::default(),
+            "test".to_string(),
+            HashMap::new(),
+            None,
+        );
     }
 
     #[test]
@@ -200,11 +379,19 @@ mod tests {
             snippet: Some(snippet),
         };
 
-        let runner = MockCommandRunner {
-            result: Ok(fake_exit_status(true)),
-        };
-
-        run_command(&storage, &ui, &runner, "test".to_string());
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        run_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockInputPrompt { value: None },
+            &runner,
+            &MockSecrets::default(),
+            "test".to_string(),
+            HashMap::new(),
+            None,
+        );
     }
 
     #[test]
@@ -216,11 +403,19 @@ mod tests {
 
         let ui = MockSelectionUI { snippet: None };
 
-        let runner = MockCommandRunner {
-            result: Ok(fake_exit_status(true)),
-        };
-
-        run_command(&storage, &ui, &runner, "test".to_string());
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        run_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockInputPrompt { value: None },
+            &runner,
+            &MockSecrets::default(),
+            "test".to_string(),
+            HashMap::new(),
+            None,
+        );
     }
 
     #[test]
@@ -232,10 +427,438 @@ mod tests {
 
         let ui = MockSelectionUI { snippet: None };
 
-        let runner = MockCommandRunner {
-            result: Ok(fake_exit_status(true)),
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        run_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockInputPrompt { value: None },
+            &runner,
+            &MockSecrets::default(),
+            "test".to_string(),
+            HashMap::new(),
+            None,
+        );
+    }
+
+    #[test]
+    fn test_run_renders_placeholders_from_values() {
+        let mut snippet = test_snippet("deploy", true);
+        snippet.content = "kubectl -n {{ns:default}} rollout restart {{app}}".to_string();
+
+        let storage = MockStorage {
+            snippet: Some(snippet.clone()),
+            fail_load: false,
+        };
+
+        let ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        let values = HashMap::from([
+            ("ns".to_string(), "prod".to_string()),
+            ("app".to_string(), "api".to_string()),
+        ]);
+
+        run_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockInputPrompt { value: None },
+            &runner,
+            &MockSecrets::default(),
+            "deploy".to_string(),
+            values,
+            None,
+        );
+
+        assert_eq!(
+            runner.last_command.borrow().as_deref(),
+            Some("kubectl -n prod rollout restart api")
+        );
+    }
+
+    #[test]
+    fn test_run_prompts_for_missing_placeholders() {
+        let mut snippet = test_snippet("ping", true);
+        snippet.content = "ping {{host}} -c {{count:3}}".to_string();
+
+        let storage = MockStorage {
+            snippet: Some(snippet.clone()),
+            fail_load: false,
+        };
+
+        let ui = MockSelectionUI {
+            snippet: Some(snippet),
         };
 
-        run_command(&storage, &ui, &runner, "test".to_string());
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        run_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockInputPrompt {
+                value: Some("example.com".to_string()),
+            },
+            &runner,
+            &MockSecrets::default(),
+            "ping".to_string(),
+            HashMap::from([("count".to_string(), "1".to_string())]),
+            None,
+        );
+
+        assert_eq!(
+            runner.last_command.borrow().as_deref(),
+            Some("ping example.com -c 1")
+        );
+    }
+
+    #[test]
+    fn test_run_cancelled_prompt_does_not_run() {
+        let mut snippet = test_snippet("ping", true);
+        snippet.content = "ping {{host}}".to_string();
+
+        let storage = MockStorage {
+            snippet: Some(snippet.clone()),
+            fail_load: false,
+        };
+
+        let ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        run_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockInputPrompt { value: None },
+            &runner,
+            &MockSecrets::default(),
+            "ping".to_string(),
+            HashMap::new(),
+            None,
+        );
+
+        assert!(runner.last_command.borrow().is_none());
+    }
+
+    #[test]
+    fn test_run_selects_variable_from_command_output() {
+        let mut snippet = test_snippet("logs", true);
+        snippet.content = "kubectl logs {{pod}}".to_string();
+        snippet.variables = vec![Variable {
+            name: "pod".to_string(),
+            command: "kubectl get pods -o name".to_string(),
+        }];
+
+        let storage = MockStorage {
+            snippet: Some(snippet.clone()),
+            fail_load: false,
+        };
+
+        let ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        run_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockInputPrompt { value: None },
+            &runner,
+            &MockSecrets::default(),
+            "logs".to_string(),
+            HashMap::new(),
+            None,
+        );
+
+        assert_eq!(
+            runner.last_command.borrow().as_deref(),
+            Some("kubectl logs pod/api-1")
+        );
+    }
+
+    #[test]
+    fn test_run_falls_back_to_prompt_when_variable_command_fails() {
+        let mut snippet = test_snippet("logs", true);
+        snippet.content = "kubectl logs {{pod}}".to_string();
+        snippet.variables = vec![Variable {
+            name: "pod".to_string(),
+            command: "broken".to_string(),
+        }];
+
+        let storage = MockStorage {
+            snippet: Some(snippet.clone()),
+            fail_load: false,
+        };
+
+        let ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        run_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockInputPrompt {
+                value: Some("pod/manual".to_string()),
+            },
+            &runner,
+            &MockSecrets::default(),
+            "logs".to_string(),
+            HashMap::new(),
+            None,
+        );
+
+        assert_eq!(
+            runner.last_command.borrow().as_deref(),
+            Some("kubectl logs pod/manual")
+        );
+    }
+
+    #[test]
+    fn test_run_dispatches_snippet_kind() {
+        let mut snippet = test_snippet("script", true);
+        snippet.kind = SnippetKind::Python;
+        snippet.content = "print('hi')".to_string();
+
+        let storage = MockStorage {
+            snippet: Some(snippet.clone()),
+            fail_load: false,
+        };
+
+        let ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        run_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockInputPrompt { value: None },
+            &runner,
+            &MockSecrets::default(),
+            "script".to_string(),
+            HashMap::new(),
+            None,
+        );
+
+        assert_eq!(*runner.last_kind.borrow(), Some(SnippetKind::Python));
+        assert_eq!(runner.last_command.borrow().as_deref(), Some("print('hi')"));
+    }
+
+    #[test]
+    fn test_run_plain_snippet_is_not_run() {
+        let snippet = test_snippet("notes", false);
+
+        let storage = MockStorage {
+            snippet: Some(snippet.clone()),
+            fail_load: false,
+        };
+
+        let ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        run_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockInputPrompt { value: None },
+            &runner,
+            &MockSecrets::default(),
+            "notes".to_string(),
+            HashMap::new(),
+            None,
+        );
+
+        assert!(runner.last_kind.borrow().is_none());
+    }
+
+    #[test]
+    fn test_run_sql_prefers_db_flag_over_snippet_database() {
+        let mut snippet = test_snippet("report", true);
+        snippet.kind = SnippetKind::Sql;
+        snippet.content = "SELECT count(*) FROM users;".to_string();
+        snippet.database = Some("snippet.db".to_string());
+
+        let storage = MockStorage {
+            snippet: Some(snippet.clone()),
+            fail_load: false,
+        };
+
+        let ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        run_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockInputPrompt { value: None },
+            &runner,
+            &MockSecrets::default(),
+            "report".to_string(),
+            HashMap::new(),
+            Some("override.db".to_string()),
+        );
+
+        assert_eq!(*runner.last_kind.borrow(), Some(SnippetKind::Sql));
+        assert_eq!(
+            runner.last_database.borrow().as_deref(),
+            Some("override.db")
+        );
+    }
+
+    #[test]
+    fn test_run_sql_uses_snippet_database() {
+        let mut snippet = test_snippet("report", true);
+        snippet.kind = SnippetKind::Sql;
+        snippet.database = Some("snippet.db".to_string());
+
+        let storage = MockStorage {
+            snippet: Some(snippet.clone()),
+            fail_load: false,
+        };
+
+        let ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        run_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockInputPrompt { value: None },
+            &runner,
+            &MockSecrets::default(),
+            "report".to_string(),
+            HashMap::new(),
+            None,
+        );
+
+        assert_eq!(runner.last_database.borrow().as_deref(), Some("snippet.db"));
+    }
+
+    #[test]
+    fn test_run_injects_secrets_as_env() {
+        let mut snippet = test_snippet("gh", true);
+        snippet.content = "curl -H \"Authorization: token ${secret:GH_TOKEN}\" {{url}}".into();
+
+        let storage = MockStorage {
+            snippet: Some(snippet.clone()),
+            fail_load: false,
+        };
+        let ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+        let secrets = MockSecrets {
+            secrets: HashMap::from([("GH_TOKEN".to_string(), "ghp_abc".to_string())]),
+        };
+
+        run_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockInputPrompt { value: None },
+            &runner,
+            &secrets,
+            "gh".to_string(),
+            HashMap::from([("url".to_string(), "api.github.com".to_string())]),
+            None,
+        );
+
+        assert_eq!(
+            runner.last_command.borrow().as_deref(),
+            Some("curl -H \"Authorization: token ${MARKIT_SECRET_GH_TOKEN}\" api.github.com")
+        );
+        assert_eq!(
+            *runner.last_env.borrow(),
+            vec![("MARKIT_SECRET_GH_TOKEN".to_string(), "ghp_abc".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_run_does_not_resolve_secrets_in_placeholder_values() {
+        let mut snippet = test_snippet("echo", true);
+        snippet.content = "echo {{message}}".into();
+
+        let storage = MockStorage {
+            snippet: Some(snippet.clone()),
+            fail_load: false,
+        };
+        let ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+        let secrets = MockSecrets {
+            secrets: HashMap::from([("GH_TOKEN".to_string(), "ghp_abc".to_string())]),
+        };
+
+        run_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockInputPrompt { value: None },
+            &runner,
+            &secrets,
+            "echo".to_string(),
+            HashMap::from([("message".to_string(), "${secret:GH_TOKEN}".to_string())]),
+            None,
+        );
+
+        assert_eq!(
+            runner.last_command.borrow().as_deref(),
+            Some("echo ${secret:GH_TOKEN}")
+        );
+        assert!(runner.last_env.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_run_stops_on_missing_secret() {
+        let mut snippet = test_snippet("gh", true);
+        snippet.content = "gh api user --token ${secret:GH_TOKEN}".into();
+
+        let storage = MockStorage {
+            snippet: Some(snippet.clone()),
+            fail_load: false,
+        };
+        let ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        run_command(
+            &storage,
+            &storage,
+            &ui,
+            &MockInputPrompt { value: None },
+            &runner,
+            &MockSecrets::default(),
+            "gh".to_string(),
+            HashMap::new(),
+            None,
+        );
+
+        assert!(runner.last_command.borrow().is_none());
     }
 }
@@ -1,26 +1,134 @@
+use std::{io::Read, path::PathBuf};
+
 use chrono::Utc;
 
-use crate::{input::SaveInput, models::Snippet, storage::Storage};
+use crate::{
+    commands::helper::{guard_secrets, text_fields},
+    input::{
+        SaveInput,
+        args_save::{ArgsSaveInput, ContentSource},
+        history_save::HistorySaveInput,
+    },
+    models::{Snippet, SnippetKind},
+    storage::Storage,
+    ui::SelectionUI,
+};
+
+pub struct SaveArgs {
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    pub kind: Option<SnippetKind>,
+    pub content: Option<String>,
+    pub from_file: Option<PathBuf>,
+    pub piped: bool,
+    pub history: Option<Vec<String>>,
+}
+
+struct ResolvedInput<'a> {
+    details: &'a dyn SaveInput,
+    content: Option<String>,
+    prompt: &'a dyn SaveInput,
+}
+
+impl SaveInput for ResolvedInput<'_> {
+    fn get_description(&self) -> String {
+        self.details.get_description()
+    }
+
+    fn get_kind(&self) -> SnippetKind {
+        self.details.get_kind()
+    }
+
+    fn get_content(&self) -> String {
+        match &self.content {
+            Some(content) => content.clone(),
+            None => self.prompt.get_content(),
+        }
+    }
 
-pub fn save_command(storage: &dyn Storage, input: &dyn SaveInput, name: String) {
-    if let Ok(store) = storage.load() {
-        if store
-            .snippets
-            .iter()
-            .any(|s| s.name.eq_ignore_ascii_case(&name))
-        {
-            eprintln!("⛔ A snippet with the name '{}' already exists.", name);
+    fn get_tags(&self) -> Vec<String> {
+        self.details.get_tags()
+    }
+}
+
+pub fn save_from_args(
+    storage: &dyn Storage,
+    prompt: &dyn SaveInput,
+    selection_ui: &dyn SelectionUI,
+    stdin: &mut dyn Read,
+    name: String,
+    args: SaveArgs,
+) {
+    let has_details = args.description.is_some() || !args.tags.is_empty() || args.kind.is_some();
+    let flags = ArgsSaveInput::new(args.description, String::new(), args.kind, args.tags);
+
+    if let Some(entries) = args.history {
+        let details: &dyn SaveInput = if has_details { &flags } else { prompt };
+        match HistorySaveInput::pick(&entries, selection_ui, details) {
+            Some(input) => save_command(storage, &input, selection_ui, name),
+            None => println!("🚫 Cancelled."),
+        }
+        return;
+    }
+
+    let source = match (args.content, args.from_file) {
+        (Some(content), _) => Some(ContentSource::Inline(content)),
+        (None, Some(path)) => Some(ContentSource::File(path)),
+        (None, None) if args.piped => Some(ContentSource::Stdin),
+        (None, None) => None,
+    };
+    let content = match source.map(|source| ArgsSaveInput::read_content(source, stdin)) {
+        Some(Ok(content)) => Some(content),
+        Some(Err(e)) => {
+            eprintln!("⛔ {}", e);
             return;
         }
+        None => None,
+    };
+
+    let input = ResolvedInput {
+        details: if has_details || content.is_some() {
+            &flags
+        } else {
+            prompt
+        },
+        content,
+        prompt,
+    };
+    save_command(storage, &input, selection_ui, name);
+}
+
+pub fn save_command(
+    storage: &dyn Storage,
+    input: &dyn SaveInput,
+    selection_ui: &dyn SelectionUI,
+    name: String,
+) {
+    if let Ok(Some(_)) = storage.find_by_name(&name) {
+        eprintln!("⛔ A snippet with the name '{}' already exists.", name);
+        return;
+    }
+
+    let mut description = input.get_description();
+    let mut content = input.get_content();
+    if !guard_secrets(
+        &name,
+        text_fields(&mut description, &mut content, &mut []),
+        selection_ui,
+    ) {
+        return;
     }
 
     let now = Utc::now();
     let entry = Snippet {
+        id: Snippet::generate_id(),
         name,
-        description: input.get_description(),
-        content: input.get_content(),
-        executable: input.get_executable(),
+        description,
+        content,
+        kind: input.get_kind(),
         tags: input.get_tags(),
+        variables: vec![],
+        database: None,
         created_at: now,
         updated_at: now,
     };
@@ -37,7 +145,7 @@ mod tests {
     use std::cell::RefCell;
     use std::io;
 
-    use crate::models::{Snippet, SnippetStore};
+    use crate::models::{Snippet, SnippetKind, SnippetStore};
     use crate::storage::{Storage, StorageError};
 
     // Mock SaveInput
@@ -48,8 +156,8 @@ mod tests {
             "Test description".to_string()
         }
 
-        fn get_executable(&self) -> bool {
-            true
+        fn get_kind(&self) -> SnippetKind {
+            SnippetKind::Shell
         }
 
         fn get_content(&self) -> String {
@@ -68,8 +176,8 @@ mod tests {
             "No tag snippet".to_string()
         }
 
-        fn get_executable(&self) -> bool {
-            false
+        fn get_kind(&self) -> SnippetKind {
+            SnippetKind::Plain
         }
 
         fn get_content(&self) -> String {
@@ -81,6 +189,50 @@ mod tests {
         }
     }
 
+    struct MockSelectionUI {
+        choice: Option<usize>,
+    }
+
+    impl MockSelectionUI {
+        fn choosing(choice: Option<usize>) -> Self {
+            Self { choice }
+        }
+    }
+
+    impl SelectionUI for MockSelectionUI {
+        fn with_snippet_list(&self, _: Vec<Snippet>) -> Option<Snippet> {
+            None
+        }
+
+        fn with_backup_list(&self, _: &[String]) -> Option<usize> {
+            None
+        }
+
+        fn with_choice_list(&self, _: &str, _: &[String]) -> Option<usize> {
+            self.choice
+        }
+    }
+
+    struct MockSecretInput;
+
+    impl SaveInput for MockSecretInput {
+        fn get_description(&self) -> String {
+            "Login".to_string()
+        }
+
+        fn get_kind(&self) -> SnippetKind {
+            SnippetKind::Shell
+        }
+
+        fn get_content(&self) -> String {
+            "mysql -u root --password=hunter2".to_string()
+        }
+
+        fn get_tags(&self) -> Vec<String> {
+            vec![]
+        }
+    }
+
     // Mock storage
     struct MockStorage {
         saved_snippets: RefCell<Vec<Snippet>>,
@@ -144,7 +296,12 @@ mod tests {
         let input = MockInput;
         let name = "test_snippet".to_string();
 
-        save_command(&storage, &input, name.clone());
+        save_command(
+            &storage,
+            &input,
+            &MockSelectionUI::choosing(None),
+            name.clone(),
+        );
 
         let snippets = storage.saved_snippets.borrow();
         assert_eq!(snippets.len(), 1);
@@ -153,18 +310,21 @@ mod tests {
         assert_eq!(saved.name, name);
         assert_eq!(saved.description, "Test description");
         assert_eq!(saved.content, "echo 'Hello World'");
-        assert!(saved.executable);
+        assert_eq!(saved.kind, SnippetKind::Shell);
         assert_eq!(saved.tags, vec!["test"]);
     }
 
     #[test]
     fn test_save_command_rejects_duplicate() {
         let existing_snippet = Snippet {
+            id: Snippet::generate_id(),
             name: "duplicate".to_string(),
             description: "Existing".to_string(),
             content: "echo test".to_string(),
-            executable: false,
+            kind: SnippetKind::Plain,
             tags: vec![],
+            variables: vec![],
+            database: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -172,7 +332,12 @@ mod tests {
         let storage = MockStorage::with_existing(existing_snippet);
         let input = MockInput;
 
-        save_command(&storage, &input, "duplicate".to_string());
+        save_command(
+            &storage,
+            &input,
+            &MockSelectionUI::choosing(None),
+            "duplicate".to_string(),
+        );
 
         // Should not save another
         let snippets = storage.saved_snippets.borrow();
@@ -186,7 +351,12 @@ mod tests {
         let input = MockInput;
 
         // Should not panic
-        save_command(&storage, &input, "fail_test".to_string());
+        save_command(
+            &storage,
+            &input,
+            &MockSelectionUI::choosing(None),
+            "fail_test".to_string(),
+        );
 
         let snippets = storage.saved_snippets.borrow();
         assert!(snippets.is_empty());
@@ -197,10 +367,134 @@ mod tests {
         let storage = MockStorage::new();
         let input = MockInputNoTags;
 
-        save_command(&storage, &input, "no_tags".to_string());
+        save_command(
+            &storage,
+            &input,
+            &MockSelectionUI::choosing(None),
+            "no_tags".to_string(),
+        );
 
         let snippets = storage.saved_snippets.borrow();
         assert_eq!(snippets.len(), 1);
         assert!(snippets[0].tags.is_empty());
     }
+
+    #[test]
+    fn test_save_command_redacts_or_aborts_on_secrets() {
+        let storage = MockStorage::new();
+
+        save_command(
+            &storage,
+            &MockSecretInput,
+            &MockSelectionUI::choosing(Some(2)),
+            "aborted".to_string(),
+        );
+        assert!(storage.saved_snippets.borrow().is_empty());
+
+        save_command(
+            &storage,
+            &MockSecretInput,
+            &MockSelectionUI::choosing(Some(0)),
+            "redacted".to_string(),
+        );
+        save_command(
+            &storage,
+            &MockSecretInput,
+            &MockSelectionUI::choosing(Some(1)),
+            "kept".to_string(),
+        );
+
+        let snippets = storage.saved_snippets.borrow();
+        assert_eq!(snippets[0].content, "mysql -u root --password={{password}}");
+        assert_eq!(snippets[1].content, "mysql -u root --password=hunter2");
+    }
+
+    fn args() -> SaveArgs {
+        SaveArgs {
+            description: None,
+            tags: vec![],
+            kind: None,
+            content: None,
+            from_file: None,
+            piped: false,
+            history: None,
+        }
+    }
+
+    #[test]
+    fn test_save_from_args_prompts_only_for_missing_content() {
+        let storage = MockStorage::new();
+
+        save_from_args(
+            &storage,
+            &MockInput,
+            &MockSelectionUI::choosing(None),
+            &mut io::empty(),
+            "flags".to_string(),
+            SaveArgs {
+                description: Some("From flags".into()),
+                tags: vec!["cli".into()],
+                ..args()
+            },
+        );
+
+        let snippets = storage.saved_snippets.borrow();
+        assert_eq!(snippets[0].content, "echo 'Hello World'");
+        assert_eq!(snippets[0].description, "From flags");
+        assert_eq!(snippets[0].tags, vec!["cli"]);
+        assert_eq!(snippets[0].kind, SnippetKind::Plain);
+    }
+
+    #[test]
+    fn test_save_from_args_uses_given_content_without_prompting() {
+        let storage = MockStorage::new();
+
+        save_from_args(
+            &storage,
+            &MockInput,
+            &MockSelectionUI::choosing(None),
+            &mut "docker ps\n".as_bytes(),
+            "piped".to_string(),
+            SaveArgs {
+                piped: true,
+                ..args()
+            },
+        );
+        save_from_args(
+            &storage,
+            &MockInput,
+            &MockSelectionUI::choosing(None),
+            &mut io::empty(),
+            "interactive".to_string(),
+            args(),
+        );
+
+        let snippets = storage.saved_snippets.borrow();
+        assert_eq!(snippets[0].content, "docker ps\n");
+        assert_eq!(snippets[0].description, "");
+        assert_eq!(snippets[1].content, "echo 'Hello World'");
+        assert_eq!(snippets[1].description, "Test description");
+    }
+
+    #[test]
+    fn test_save_from_args_picks_history_entry() {
+        let storage = MockStorage::new();
+
+        save_from_args(
+            &storage,
+            &MockInput,
+            &MockSelectionUI::choosing(Some(1)),
+            &mut io::empty(),
+            "history".to_string(),
+            SaveArgs {
+                kind: Some(SnippetKind::Bash),
+                history: Some(vec!["ls".into(), "git status".into()]),
+                ..args()
+            },
+        );
+
+        let snippets = storage.saved_snippets.borrow();
+        assert_eq!(snippets[0].content, "git status\n");
+        assert_eq!(snippets[0].kind, SnippetKind::Bash);
+    }
 }
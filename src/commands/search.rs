@@ -32,7 +32,7 @@ pub fn search_command(storage: &dyn Storage, table_ui: &mut dyn TableUI, query:
 mod tests {
     use super::*;
     use crate::{
-        models::{Snippet, SnippetStore},
+        models::{Snippet, SnippetKind, SnippetStore},
         storage::{Storage, StorageError},
         ui::TableUI,
     };
@@ -84,11 +84,14 @@ mod tests {
 
     fn create_test_snippet(name: &str, description: &str, content: &str, tags: Vec<&str>) -> Snippet {
         Snippet {
+            id: Snippet::generate_id(),
             name: name.to_string(),
             description: description.to_string(),
             content: content.to_string(),
-            executable: true,
+            kind: SnippetKind::Shell,
             tags: tags.iter().map(|s| s.to_string()).collect(),
+            variables: vec![],
+            database: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -0,0 +1,179 @@
+use crate::{storage::SecretStore, template::is_valid_secret_name, ui::SecretPrompt};
+
+pub fn set_command(
+    secrets: &dyn SecretStore,
+    prompt: &dyn SecretPrompt,
+    name: String,
+    value: Option<String>,
+) {
+    if !is_valid_secret_name(&name) {
+        eprintln!(
+            "⛔ Invalid secret name '{}': use letters, digits and '_', not starting with a digit.",
+            name
+        );
+        return;
+    }
+
+    if !secrets.is_initialized() {
+        println!("🔑 Creating the secrets vault.");
+        let Some(passphrase) = prompt.secret("New passphrase for your secrets", true) else {
+            println!("🚫 Cancelled.");
+            return;
+        };
+        if passphrase.is_empty() {
+            eprintln!("⛔ The passphrase cannot be empty.");
+            return;
+        }
+        if let Err(e) = secrets.initialize(&passphrase) {
+            eprintln!("⛔ Failed to create the secrets vault: {}", e);
+            return;
+        }
+    }
+
+    let value = match value {
+        Some(value) => value,
+        None => match prompt.secret(&format!("Value for '{}'", name), false) {
+            Some(value) => value,
+            None => {
+                println!("🚫 Cancelled.");
+                return;
+            }
+        },
+    };
+
+    match secrets.set_secret(&name, &value) {
+        Ok(_) => println!(
+            "🔑 Secret '{}' saved. Use it in snippets as ${{secret:{}}}.",
+            name, name
+        ),
+        Err(e) => eprintln!("⛔ Failed to save secret: {}", e),
+    }
+}
+
+pub fn get_command(secrets: &dyn SecretStore, name: String) {
+    match secrets.get_secret(&name) {
+        Ok(Some(value)) => println!("{}", value),
+        Ok(None) => eprintln!("⛔ Secret '{}' not found.", name),
+        Err(e) => eprintln!("⛔ Failed to read secrets: {}", e),
+    }
+}
+
+pub fn list_command(secrets: &dyn SecretStore) {
+    if !secrets.is_initialized() {
+        println!("📭 No secrets saved yet.");
+        return;
+    }
+
+    match secrets.secret_names() {
+        Ok(names) if names.is_empty() => println!("📭 No secrets saved yet."),
+        Ok(names) => {
+            println!("🔑 Secrets:");
+            for name in names {
+                println!("  {}", name);
+            }
+        }
+        Err(e) => eprintln!("⛔ Failed to read secrets: {}", e),
+    }
+}
+
+pub fn remove_command(secrets: &dyn SecretStore, name: String) {
+    match secrets.remove_secret(&name) {
+        Ok(true) => println!("🗑️ Secret '{}' removed.", name),
+        Ok(false) => eprintln!("⛔ Secret '{}' not found.", name),
+        Err(e) => eprintln!("⛔ Failed to remove secret: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StorageError;
+    use std::{cell::RefCell, collections::BTreeMap};
+
+    #[derive(Default)]
+    struct MockSecrets {
+        passphrase: RefCell<Option<String>>,
+        secrets: RefCell<BTreeMap<String, String>>,
+    }
+
+    impl SecretStore for MockSecrets {
+        fn is_initialized(&self) -> bool {
+            self.passphrase.borrow().is_some()
+        }
+
+        fn initialize(&self, passphrase: &str) -> Result<(), StorageError> {
+            self.passphrase.replace(Some(passphrase.to_string()));
+            Ok(())
+        }
+
+        fn secret_names(&self) -> Result<Vec<String>, StorageError> {
+            Ok(self.secrets.borrow().keys().cloned().collect())
+        }
+
+        fn get_secret(&self, name: &str) -> Result<Option<String>, StorageError> {
+            Ok(self.secrets.borrow().get(name).cloned())
+        }
+
+        fn set_secret(&self, name: &str, value: &str) -> Result<(), StorageError> {
+            self.secrets
+                .borrow_mut()
+                .insert(name.to_string(), value.to_string());
+            Ok(())
+        }
+
+        fn remove_secret(&self, name: &str) -> Result<bool, StorageError> {
+            Ok(self.secrets.borrow_mut().remove(name).is_some())
+        }
+    }
+
+    struct MockSecretPrompt {
+        secret: Option<String>,
+    }
+
+    impl SecretPrompt for MockSecretPrompt {
+        fn secret(&self, _: &str, _: bool) -> Option<String> {
+            self.secret.clone()
+        }
+    }
+
+    #[test]
+    fn test_set_creates_vault_and_prompts_for_value() {
+        let secrets = MockSecrets::default();
+        let prompt = MockSecretPrompt {
+            secret: Some("hunter2".into()),
+        };
+
+        set_command(&secrets, &prompt, "GH_TOKEN".into(), None);
+
+        assert_eq!(secrets.passphrase.borrow().as_deref(), Some("hunter2"));
+        assert_eq!(
+            secrets.get_secret("GH_TOKEN").unwrap().as_deref(),
+            Some("hunter2")
+        );
+    }
+
+    #[test]
+    fn test_set_rejects_invalid_names() {
+        let secrets = MockSecrets::default();
+        let prompt = MockSecretPrompt {
+            secret: Some("pw".into()),
+        };
+
+        set_command(&secrets, &prompt, "bad-name".into(), Some("x".into()));
+        set_command(&secrets, &prompt, "1TOKEN".into(), Some("x".into()));
+
+        assert!(!secrets.is_initialized());
+        assert!(secrets.secrets.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_remove_secret() {
+        let secrets = MockSecrets::default();
+        secrets.set_secret("TOKEN", "abc").unwrap();
+
+        remove_command(&secrets, "TOKEN".into());
+        remove_command(&secrets, "TOKEN".into());
+
+        assert!(secrets.secrets.borrow().is_empty());
+    }
+}
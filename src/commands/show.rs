@@ -14,8 +14,9 @@ pub fn show_command(storage: &dyn Storage, selection_ui: &dyn SelectionUI, name:
     };
 
     println!("🔎 Snippet: {}", snippet.name);
+    println!("🆔 ID: {}", snippet.id);
     println!("📄 Description: {}", snippet.description);
-    println!("🚀 Executable: {}", snippet.executable);
+    println!("🚀 Kind: {}", snippet.kind);
     println!("🕒 Created at: {}", snippet.created_at);
     println!("🕒 Updated at: {}", snippet.updated_at);
     println!("📋 Content:\n{}", snippet.content);
@@ -25,7 +26,7 @@ pub fn show_command(storage: &dyn Storage, selection_ui: &dyn SelectionUI, name:
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{Snippet, SnippetStore};
+    use crate::models::{Snippet, SnippetKind, SnippetStore};
     use crate::storage::{Storage, StorageError};
     use crate::ui::SelectionUI;
     use std::cell::RefCell;
@@ -75,16 +76,23 @@ mod tests {
         fn with_backup_list(&self, _: &[String]) -> Option<usize> {
             Some(0)
         }
+
+        fn with_choice_list(&self, _: &str, _: &[String]) -> Option<usize> {
+            None
+        }
     }
 
     #[test]
     fn test_show_command_success() {
         let snippet = Snippet {
+            id: Snippet::generate_id(),
             name: "test".to_string(),
             description: "desc".to_string(),
             content: "echo hello".to_string(),
-            executable: true,
+            kind: SnippetKind::Shell,
             tags: vec!["tag1".to_string()],
+            variables: vec![],
+            database: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
@@ -118,11 +126,14 @@ mod tests {
     #[test]
     fn test_show_command_not_found() {
         let snippet = Snippet {
+            id: Snippet::generate_id(),
             name: "test".to_string(),
             description: "desc".to_string(),
             content: "echo hello".to_string(),
-            executable: true,
+            kind: SnippetKind::Shell,
             tags: vec!["tag1".to_string()],
+            variables: vec![],
+            database: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
@@ -0,0 +1,398 @@
+use std::time::Duration;
+
+use crate::{
+    agent::PassphraseCache,
+    commands::trash::parse_age,
+    storage::{StoreCatalog, StoreEncryption, stores::validate_store_name},
+    ui::{ConfirmPrompt, SecretPrompt},
+};
+
+pub fn list_command(catalog: &dyn StoreCatalog, active: &str) {
+    let stores = match catalog.list_stores() {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("⛔ Failed to list stores: {}", e);
+            return;
+        }
+    };
+
+    let default = catalog.default_store();
+    println!("🗂️ Stores:");
+    for store in stores {
+        let marker = if store == active { "*" } else { " " };
+        let note = if store == default { "  (default)" } else { "" };
+        println!("  {} {}{}", marker, store, note);
+    }
+}
+
+pub fn create_command(catalog: &dyn StoreCatalog, name: String) {
+    if let Err(e) = validate_store_name(&name) {
+        eprintln!("⛔ {}", e);
+        return;
+    }
+
+    match catalog.create_store(&name) {
+        Ok(_) => println!(
+            "✅ Store '{}' created. Use it with `--store {}`.",
+            name, name
+        ),
+        Err(e) => eprintln!("⛔ Failed to create store: {}", e),
+    }
+}
+
+pub fn remove_command(
+    catalog: &dyn StoreCatalog,
+    confirm: &dyn ConfirmPrompt,
+    name: String,
+    force: bool,
+) {
+    if !force {
+        let prompt = format!(
+            "❗ Remove store '{}' with all its snippets, backups and history? This cannot be undone.",
+            name
+        );
+        if !confirm.confirm(&prompt) {
+            println!("🚫 Cancelled.");
+            return;
+        }
+    }
+
+    match catalog.remove_store(&name) {
+        Ok(_) => println!("🗑️ Store '{}' removed.", name),
+        Err(e) => eprintln!("⛔ Failed to remove store: {}", e),
+    }
+}
+
+pub fn default_command(catalog: &dyn StoreCatalog, name: Option<String>) {
+    let Some(name) = name else {
+        println!("{}", catalog.default_store());
+        return;
+    };
+
+    match catalog.set_default_store(&name) {
+        Ok(_) => println!("✅ '{}' is now the default store.", name),
+        Err(e) => eprintln!("⛔ Failed to set default store: {}", e),
+    }
+}
+
+pub fn encrypt_command(store: &dyn StoreEncryption, prompt: &dyn SecretPrompt, name: &str) {
+    if store.is_encrypted() {
+        println!("🔐 Store '{}' is already encrypted.", name);
+        return;
+    }
+
+    let Some(passphrase) = prompt.secret(&format!("New passphrase for store '{}'", name), true)
+    else {
+        println!("🚫 Cancelled.");
+        return;
+    };
+    if passphrase.is_empty() {
+        eprintln!("⛔ The passphrase cannot be empty.");
+        return;
+    }
+
+    match store.encrypt(&passphrase) {
+        Ok(_) => println!(
+            "🔐 Store '{}' is encrypted. Keep the passphrase safe, it cannot be recovered.",
+            name
+        ),
+        Err(e) => eprintln!("⛔ Failed to encrypt store: {}", e),
+    }
+}
+
+pub fn decrypt_command(store: &dyn StoreEncryption, name: &str) {
+    if !store.is_encrypted() {
+        println!("📭 Store '{}' is not encrypted.", name);
+        return;
+    }
+
+    match store.decrypt() {
+        Ok(_) => println!("🔓 Store '{}' is no longer encrypted.", name),
+        Err(e) => eprintln!("⛔ Failed to decrypt store: {}", e),
+    }
+}
+
+const MAX_TTL: Duration = Duration::from_secs(24 * 3600);
+
+pub fn parse_ttl(s: &str) -> Result<Duration, String> {
+    let ttl = parse_age(s)?
+        .to_std()
+        .map_err(|_| format!("invalid ttl '{}'", s))?;
+    if ttl > MAX_TTL {
+        return Err(format!("ttl '{}' is longer than the 24h maximum", s));
+    }
+    Ok(ttl)
+}
+
+pub fn unlock_command(
+    store: &dyn StoreEncryption,
+    prompt: &dyn SecretPrompt,
+    cache: &dyn PassphraseCache,
+    name: &str,
+    ttl: Duration,
+) {
+    if !store.is_encrypted() {
+        println!("📭 Store '{}' is not encrypted.", name);
+        return;
+    }
+
+    let Some(passphrase) = prompt.secret(&format!("Passphrase for store '{}'", name), false) else {
+        println!("🚫 Cancelled.");
+        return;
+    };
+    if let Err(e) = store.check_passphrase(&passphrase) {
+        eprintln!("⛔ {}", e);
+        return;
+    }
+
+    match cache.remember(name, &passphrase, ttl) {
+        Ok(_) => println!("🔓 Store '{}' unlocked for {}.", name, format_ttl(ttl)),
+        Err(e) => eprintln!("⛔ Failed to cache the passphrase: {}", e),
+    }
+}
+
+fn format_ttl(ttl: Duration) -> String {
+    match ttl.as_secs() {
+        secs if secs >= 3600 && secs % 3600 == 0 => format!("{}h", secs / 3600),
+        secs => format!("{}m", secs.div_ceil(60)),
+    }
+}
+
+pub fn lock_command(cache: &dyn PassphraseCache) {
+    match cache.forget() {
+        Ok(true) => println!("🔒 Cached passphrases forgotten."),
+        Ok(false) => println!("📭 No passphrases are cached."),
+        Err(e) => eprintln!("⛔ Failed to reach the passphrase agent: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StorageError;
+    use std::cell::RefCell;
+
+    struct MockCatalog {
+        stores: RefCell<Vec<String>>,
+        default: RefCell<String>,
+    }
+
+    impl MockCatalog {
+        fn new() -> Self {
+            Self {
+                stores: RefCell::new(vec!["default".into()]),
+                default: RefCell::new("default".into()),
+            }
+        }
+    }
+
+    impl StoreCatalog for MockCatalog {
+        fn list_stores(&self) -> Result<Vec<String>, StorageError> {
+            Ok(self.stores.borrow().clone())
+        }
+
+        fn create_store(&self, name: &str) -> Result<(), StorageError> {
+            self.stores.borrow_mut().push(name.to_string());
+            Ok(())
+        }
+
+        fn remove_store(&self, name: &str) -> Result<(), StorageError> {
+            self.stores.borrow_mut().retain(|s| s != name);
+            Ok(())
+        }
+
+        fn default_store(&self) -> String {
+            self.default.borrow().clone()
+        }
+
+        fn set_default_store(&self, name: &str) -> Result<(), StorageError> {
+            self.default.replace(name.to_string());
+            Ok(())
+        }
+    }
+
+    struct MockEncryption {
+        encrypted: RefCell<bool>,
+        passphrase: RefCell<Option<String>>,
+    }
+
+    impl MockEncryption {
+        fn new(passphrase: Option<&str>) -> Self {
+            Self {
+                encrypted: RefCell::new(passphrase.is_some()),
+                passphrase: RefCell::new(passphrase.map(str::to_string)),
+            }
+        }
+    }
+
+    impl StoreEncryption for MockEncryption {
+        fn is_encrypted(&self) -> bool {
+            *self.encrypted.borrow()
+        }
+
+        fn check_passphrase(&self, passphrase: &str) -> Result<(), StorageError> {
+            match self.passphrase.borrow().as_deref() == Some(passphrase) {
+                true => Ok(()),
+                false => Err(StorageError::Conflict("Wrong passphrase.".into())),
+            }
+        }
+
+        fn encrypt(&self, passphrase: &str) -> Result<(), StorageError> {
+            self.encrypted.replace(true);
+            self.passphrase.replace(Some(passphrase.to_string()));
+            Ok(())
+        }
+
+        fn decrypt(&self) -> Result<(), StorageError> {
+            self.encrypted.replace(false);
+            self.passphrase.replace(None);
+            Ok(())
+        }
+    }
+
+    struct MockSecretPrompt {
+        secret: Option<String>,
+    }
+
+    impl SecretPrompt for MockSecretPrompt {
+        fn secret(&self, _: &str, _: bool) -> Option<String> {
+            self.secret.clone()
+        }
+    }
+
+    #[derive(Default)]
+    struct MockCache {
+        cached: RefCell<Vec<(String, String, Duration)>>,
+    }
+
+    impl PassphraseCache for MockCache {
+        fn passphrase(&self, store: &str) -> Option<String> {
+            let cached = self.cached.borrow();
+            let entry = cached.iter().find(|(name, _, _)| name == store)?;
+            Some(entry.1.clone())
+        }
+
+        fn remember(&self, store: &str, passphrase: &str, ttl: Duration) -> Result<(), String> {
+            self.cached
+                .borrow_mut()
+                .push((store.into(), passphrase.into(), ttl));
+            Ok(())
+        }
+
+        fn forget(&self) -> Result<bool, String> {
+            Ok(!self.cached.take().is_empty())
+        }
+    }
+
+    struct MockConfirmPrompt {
+        confirm_result: bool,
+    }
+
+    impl ConfirmPrompt for MockConfirmPrompt {
+        fn confirm(&self, _: &str) -> bool {
+            self.confirm_result
+        }
+    }
+
+    #[test]
+    fn test_create_rejects_invalid_names() {
+        let catalog = MockCatalog::new();
+
+        create_command(&catalog, "../oops".into());
+        create_command(&catalog, "work".into());
+
+        assert_eq!(*catalog.stores.borrow(), vec!["default", "work"]);
+    }
+
+    #[test]
+    fn test_remove_requires_confirmation() {
+        let catalog = MockCatalog::new();
+        catalog.create_store("work").unwrap();
+
+        remove_command(
+            &catalog,
+            &MockConfirmPrompt {
+                confirm_result: false,
+            },
+            "work".into(),
+            false,
+        );
+        assert_eq!(catalog.stores.borrow().len(), 2);
+
+        remove_command(
+            &catalog,
+            &MockConfirmPrompt {
+                confirm_result: false,
+            },
+            "work".into(),
+            true,
+        );
+        assert_eq!(catalog.stores.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_default_sets_store() {
+        let catalog = MockCatalog::new();
+
+        default_command(&catalog, Some("work".into()));
+
+        assert_eq!(catalog.default_store(), "work");
+    }
+
+    #[test]
+    fn test_encrypt_rejects_empty_passphrase() {
+        let store = MockEncryption::new(None);
+
+        encrypt_command(&store, &MockSecretPrompt { secret: None }, "default");
+        encrypt_command(
+            &store,
+            &MockSecretPrompt {
+                secret: Some(String::new()),
+            },
+            "default",
+        );
+        assert!(!store.is_encrypted());
+
+        encrypt_command(
+            &store,
+            &MockSecretPrompt {
+                secret: Some("hunter2".into()),
+            },
+            "default",
+        );
+        assert!(store.is_encrypted());
+
+        decrypt_command(&store, "default");
+        assert!(!store.is_encrypted());
+    }
+
+    #[test]
+    fn test_unlock_caches_only_correct_passphrase() {
+        let store = MockEncryption::new(Some("hunter2"));
+        let cache = MockCache::default();
+        let ttl = Duration::from_secs(900);
+
+        let wrong = MockSecretPrompt {
+            secret: Some("wrong".into()),
+        };
+        unlock_command(&store, &wrong, &cache, "work", ttl);
+        assert_eq!(cache.passphrase("work"), None);
+
+        let right = MockSecretPrompt {
+            secret: Some("hunter2".into()),
+        };
+        unlock_command(&store, &right, &cache, "work", ttl);
+        assert_eq!(cache.passphrase("work").as_deref(), Some("hunter2"));
+
+        lock_command(&cache);
+        assert_eq!(cache.passphrase("work"), None);
+    }
+
+    #[test]
+    fn test_parse_ttl_is_capped() {
+        assert_eq!(parse_ttl("15m"), Ok(Duration::from_secs(900)));
+        assert_eq!(parse_ttl("24h"), Ok(MAX_TTL));
+        assert!(parse_ttl("2d").is_err());
+        assert!(parse_ttl("99999999999w").is_err());
+    }
+}
@@ -0,0 +1,84 @@
+use crate::storage::{SnippetSync, merge::Side};
+
+pub fn sync_command(sync: &dyn SnippetSync, prefer: Option<Side>) {
+    let report = match sync.sync(prefer) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("⛔ Sync failed: {}", e);
+            return;
+        }
+    };
+
+    if !report.conflicts.is_empty() {
+        eprintln!("⚔️ Conflicting changes, nothing was pulled or pushed:");
+        for conflict in &report.conflicts {
+            eprintln!("  - {}", conflict);
+        }
+        eprintln!(
+            "Resolve them with `markit sync --prefer local` or `markit sync --prefer remote`."
+        );
+        return;
+    }
+
+    match (report.pulled, report.pushed) {
+        (false, false) => println!("✅ Already up to date."),
+        (true, false) => println!("✅ Pulled remote snippets."),
+        (false, true) => println!("✅ Pushed local snippets."),
+        (true, true) => println!("✅ Pulled remote snippets and pushed local changes."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{StorageError, git::SyncReport};
+    use std::cell::RefCell;
+
+    struct MockSync {
+        report: SyncReport,
+        prefer: RefCell<Option<Side>>,
+    }
+
+    impl SnippetSync for MockSync {
+        fn sync(&self, prefer: Option<Side>) -> Result<SyncReport, StorageError> {
+            self.prefer.replace(prefer);
+            Ok(SyncReport {
+                pulled: self.report.pulled,
+                pushed: self.report.pushed,
+                conflicts: self.report.conflicts.clone(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_sync_passes_preference() {
+        let sync = MockSync {
+            report: SyncReport {
+                pulled: true,
+                pushed: true,
+                conflicts: vec![],
+            },
+            prefer: RefCell::new(None),
+        };
+
+        sync_command(&sync, Some(Side::Remote));
+
+        assert_eq!(*sync.prefer.borrow(), Some(Side::Remote));
+    }
+
+    #[test]
+    fn test_sync_reports_conflicts() {
+        let sync = MockSync {
+            report: SyncReport {
+                pulled: false,
+                pushed: false,
+                conflicts: vec!["deploy (changed locally, deleted remotely)".into()],
+            },
+            prefer: RefCell::new(None),
+        };
+
+        sync_command(&sync, None);
+
+        assert_eq!(*sync.prefer.borrow(), None);
+    }
+}
@@ -0,0 +1,398 @@
+use chrono::{Duration, Utc};
+
+use crate::{
+    models::TrashEntry,
+    storage::{Storage, Trash},
+    ui::{ConfirmPrompt, SelectionUI},
+};
+
+pub fn list_command(trash: &dyn Trash) {
+    let entries = match trash.load_trash() {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("⛔ Failed to load trash: {}", e);
+            return;
+        }
+    };
+
+    if entries.is_empty() {
+        println!("📭 The trash is empty.");
+        return;
+    }
+
+    println!("🗑️ Trash (most recently deleted first):");
+    for entry in entries.iter().rev() {
+        println!(
+            "  {}  {:<24} deleted {}",
+            entry.snippet.short_id(),
+            entry.snippet.name,
+            entry.deleted_at.format("%Y-%m-%d %H:%M:%S")
+        );
+    }
+}
+
+pub fn empty_command(
+    trash: &dyn Trash,
+    confirm: &dyn ConfirmPrompt,
+    older_than: Option<Duration>,
+    force: bool,
+) {
+    let entries = match trash.load_trash() {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("⛔ Failed to load trash: {}", e);
+            return;
+        }
+    };
+
+    let cutoff = match older_than {
+        Some(age) => match Utc::now().checked_sub_signed(age) {
+            Some(cutoff) => Some(cutoff),
+            None => {
+                eprintln!("⛔ Age is too large.");
+                return;
+            }
+        },
+        None => None,
+    };
+    let (expired, kept): (Vec<TrashEntry>, Vec<TrashEntry>) = entries
+        .into_iter()
+        .partition(|e| cutoff.is_none_or(|cutoff| e.deleted_at < cutoff));
+
+    if expired.is_empty() {
+        println!("📭 Nothing to remove from the trash.");
+        return;
+    }
+
+    if !force {
+        let prompt = format!(
+            "❗ Permanently delete {} snippet(s) from the trash? This cannot be undone.",
+            expired.len()
+        );
+        if !confirm.confirm(&prompt) {
+            println!("🚫 Cancelled.");
+            return;
+        }
+    }
+
+    match trash.save_trash(&kept) {
+        Ok(_) => println!("🧹 Removed {} snippet(s) from the trash.", expired.len()),
+        Err(e) => eprintln!("⛔ Failed to empty trash: {}", e),
+    }
+}
+
+pub fn undelete_command(
+    storage: &dyn Storage,
+    trash: &dyn Trash,
+    selection_ui: &dyn SelectionUI,
+    name: String,
+) {
+    let mut entries = match trash.load_trash() {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("⛔ Failed to load trash: {}", e);
+            return;
+        }
+    };
+
+    let candidates: Vec<usize> = entries
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| {
+            e.snippet.name.eq_ignore_ascii_case(&name)
+                || (name.len() >= 4 && e.snippet.id.starts_with(&name))
+        })
+        .map(|(i, _)| i)
+        .collect();
+
+    let index = match candidates.as_slice() {
+        [] => {
+            println!("⛔ Snippet '{}' not found in the trash.", name);
+            return;
+        }
+        [index] => *index,
+        _ => {
+            let labels: Vec<String> = candidates
+                .iter()
+                .map(|&i| {
+                    format!(
+                        "{} ({}, deleted {})",
+                        entries[i].snippet.name,
+                        entries[i].snippet.short_id(),
+                        entries[i].deleted_at.format("%Y-%m-%d %H:%M:%S")
+                    )
+                })
+                .collect();
+            match selection_ui.with_choice_list("Select the snippet to restore", &labels) {
+                Some(choice) => candidates[choice],
+                None => return,
+            }
+        }
+    };
+
+    let mut store = storage.load().unwrap_or_default();
+    let snippet = &entries[index].snippet;
+
+    if let Some(existing) = store
+        .snippets
+        .iter()
+        .find(|s| s.id == snippet.id || s.name.eq_ignore_ascii_case(&snippet.name))
+    {
+        eprintln!(
+            "⛔ A snippet named '{}' already exists. Rename or delete it before restoring.",
+            existing.name
+        );
+        return;
+    }
+
+    let entry = entries.remove(index);
+    let restored_name = entry.snippet.name.clone();
+    store.snippets.push(entry.snippet);
+
+    if let Err(e) = storage.save_all(&store) {
+        eprintln!("⛔ Failed to restore snippet: {}", e);
+        return;
+    }
+
+    match trash.save_trash(&entries) {
+        Ok(_) => println!("♻️ Snippet '{}' restored from the trash.", restored_name),
+        Err(e) => eprintln!(
+            "⚠️ Snippet '{}' restored, but it could not be removed from the trash: {}",
+            restored_name, e
+        ),
+    }
+}
+
+pub fn parse_age(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+
+    let number: i64 = number
+        .parse()
+        .map_err(|_| format!("invalid age '{}', expected e.g. 30d, 12h or 2w", s))?;
+
+    let age = match unit {
+        "m" => Duration::try_minutes(number),
+        "h" => Duration::try_hours(number),
+        "d" | "" => Duration::try_days(number),
+        "w" => Duration::try_weeks(number),
+        other => return Err(format!("unknown age unit '{}', use m, h, d or w", other)),
+    };
+    age.ok_or_else(|| format!("age '{}' is too large", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Snippet, SnippetKind, SnippetStore};
+    use crate::storage::StorageError;
+    use std::cell::RefCell;
+
+    struct MockStorage {
+        store: RefCell<SnippetStore>,
+    }
+
+    impl Storage for MockStorage {
+        fn load(&self) -> Result<SnippetStore, StorageError> {
+            Ok(self.store.borrow().clone())
+        }
+
+        fn save(&self, _: Snippet) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn save_all(&self, store: &SnippetStore) -> Result<(), StorageError> {
+            self.store.replace(store.clone());
+            Ok(())
+        }
+
+        fn get_backups(&self) -> Result<Vec<std::path::PathBuf>, StorageError> {
+            Ok(vec![])
+        }
+
+        fn restore_backup(&self, _: &std::path::Path) -> Result<(), StorageError> {
+            Ok(())
+        }
+    }
+
+    struct MockTrash {
+        entries: RefCell<Vec<TrashEntry>>,
+    }
+
+    impl Trash for MockTrash {
+        fn load_trash(&self) -> Result<Vec<TrashEntry>, StorageError> {
+            Ok(self.entries.borrow().clone())
+        }
+
+        fn save_trash(&self, entries: &[TrashEntry]) -> Result<(), StorageError> {
+            self.entries.replace(entries.to_vec());
+            Ok(())
+        }
+    }
+
+    struct MockSelectionUI {
+        choice: Option<usize>,
+    }
+
+    impl SelectionUI for MockSelectionUI {
+        fn with_snippet_list(&self, _: Vec<Snippet>) -> Option<Snippet> {
+            None
+        }
+
+        fn with_backup_list(&self, _: &[String]) -> Option<usize> {
+            None
+        }
+
+        fn with_choice_list(&self, _: &str, _: &[String]) -> Option<usize> {
+            self.choice
+        }
+    }
+
+    struct MockConfirmPrompt {
+        confirm_result: bool,
+    }
+
+    impl ConfirmPrompt for MockConfirmPrompt {
+        fn confirm(&self, _: &str) -> bool {
+            self.confirm_result
+        }
+    }
+
+    fn entry(name: &str, age_days: i64) -> TrashEntry {
+        TrashEntry {
+            deleted_at: Utc::now() - Duration::days(age_days),
+            snippet: Snippet {
+                id: Snippet::generate_id(),
+                name: name.into(),
+                description: String::new(),
+                content: format!("echo {}", name),
+                kind: SnippetKind::Shell,
+                tags: vec![],
+                variables: vec![],
+                database: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            },
+        }
+    }
+
+    fn trash_with(entries: Vec<TrashEntry>) -> MockTrash {
+        MockTrash {
+            entries: RefCell::new(entries),
+        }
+    }
+
+    #[test]
+    fn test_undelete_moves_snippet_back() {
+        let storage = MockStorage {
+            store: RefCell::new(SnippetStore::default()),
+        };
+        let trash = trash_with(vec![entry("a", 1), entry("b", 1)]);
+
+        undelete_command(
+            &storage,
+            &trash,
+            &MockSelectionUI { choice: None },
+            "B".into(),
+        );
+
+        assert_eq!(storage.store.borrow().snippets[0].name, "b");
+        assert_eq!(trash.entries.borrow().len(), 1);
+        assert_eq!(trash.entries.borrow()[0].snippet.name, "a");
+    }
+
+    #[test]
+    fn test_undelete_picks_between_duplicates() {
+        let storage = MockStorage {
+            store: RefCell::new(SnippetStore::default()),
+        };
+        let older = entry("a", 5);
+        let newer = entry("a", 1);
+        let trash = trash_with(vec![older, newer.clone()]);
+
+        undelete_command(
+            &storage,
+            &trash,
+            &MockSelectionUI { choice: Some(1) },
+            "a".into(),
+        );
+
+        assert_eq!(storage.store.borrow().snippets[0].id, newer.snippet.id);
+    }
+
+    #[test]
+    fn test_undelete_refuses_name_conflict() {
+        let existing = entry("a", 0).snippet;
+        let storage = MockStorage {
+            store: RefCell::new(SnippetStore {
+                snippets: vec![existing],
+            }),
+        };
+        let trash = trash_with(vec![entry("a", 1)]);
+
+        undelete_command(
+            &storage,
+            &trash,
+            &MockSelectionUI { choice: None },
+            "a".into(),
+        );
+
+        assert_eq!(storage.store.borrow().snippets.len(), 1);
+        assert_eq!(trash.entries.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_empty_older_than_keeps_recent() {
+        let trash = trash_with(vec![entry("old", 40), entry("new", 2)]);
+        let confirm = MockConfirmPrompt {
+            confirm_result: true,
+        };
+
+        empty_command(&trash, &confirm, Some(Duration::days(30)), false);
+
+        let entries = trash.entries.borrow();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].snippet.name, "new");
+    }
+
+    #[test]
+    fn test_empty_rejects_age_before_any_date() {
+        let trash = trash_with(vec![entry("old", 40)]);
+        let confirm = MockConfirmPrompt {
+            confirm_result: true,
+        };
+
+        empty_command(&trash, &confirm, parse_age("100000000d").ok(), false);
+
+        assert_eq!(trash.entries.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_empty_cancelled() {
+        let trash = trash_with(vec![entry("old", 40)]);
+        let confirm = MockConfirmPrompt {
+            confirm_result: false,
+        };
+
+        empty_command(&trash, &confirm, None, false);
+
+        assert_eq!(trash.entries.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("30d"), Ok(Duration::days(30)));
+        assert_eq!(parse_age("12h"), Ok(Duration::hours(12)));
+        assert_eq!(parse_age("2w"), Ok(Duration::weeks(2)));
+        assert_eq!(parse_age("7"), Ok(Duration::days(7)));
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("3y").is_err());
+        assert_eq!(
+            parse_age("999999999999999d"),
+            Err("age '999999999999999d' is too large".to_string())
+        );
+        assert!(parse_age("99999999999w").is_err());
+        assert!(parse_age("99999999999999999999").is_err());
+    }
+}
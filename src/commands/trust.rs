@@ -0,0 +1,81 @@
+use std::path::Path;
+
+use crate::storage::ProjectTrust;
+
+pub fn allow_command(trust: &dyn ProjectTrust, path: Option<&Path>) {
+    let Some(path) = path else {
+        eprintln!("⛔ No .markit.yml found here or in any parent directory.");
+        return;
+    };
+
+    match trust.trust(path) {
+        Ok(_) => println!(
+            "✅ Trusted '{}'. Its snippets are used until the file changes.",
+            path.display()
+        ),
+        Err(e) => eprintln!("⛔ Failed to trust '{}': {}", path.display(), e),
+    }
+}
+
+pub fn deny_command(trust: &dyn ProjectTrust, path: Option<&Path>) {
+    let Some(path) = path else {
+        eprintln!("⛔ No .markit.yml found here or in any parent directory.");
+        return;
+    };
+
+    match trust.revoke(path) {
+        Ok(true) => println!("🚫 '{}' is no longer trusted.", path.display()),
+        Ok(false) => println!("📭 '{}' was not trusted.", path.display()),
+        Err(e) => eprintln!("⛔ Failed to update trusted projects: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StorageError;
+    use std::{cell::RefCell, path::PathBuf};
+
+    #[derive(Default)]
+    struct MockTrust {
+        trusted: RefCell<Vec<PathBuf>>,
+    }
+
+    impl ProjectTrust for MockTrust {
+        fn is_trusted(&self, path: &Path) -> Result<bool, StorageError> {
+            Ok(self.trusted.borrow().iter().any(|p| p == path))
+        }
+
+        fn trust(&self, path: &Path) -> Result<(), StorageError> {
+            self.trusted.borrow_mut().push(path.to_path_buf());
+            Ok(())
+        }
+
+        fn revoke(&self, path: &Path) -> Result<bool, StorageError> {
+            let mut trusted = self.trusted.borrow_mut();
+            let before = trusted.len();
+            trusted.retain(|p| p != path);
+            Ok(trusted.len() != before)
+        }
+    }
+
+    #[test]
+    fn test_allow_then_deny() {
+        let trust = MockTrust::default();
+        let path = Path::new("/repo/.markit.yml");
+
+        allow_command(&trust, Some(path));
+        assert!(trust.is_trusted(path).unwrap());
+
+        deny_command(&trust, Some(path));
+        assert!(!trust.is_trusted(path).unwrap());
+    }
+
+    #[test]
+    fn test_allow_without_project_file_does_nothing() {
+        let trust = MockTrust::default();
+
+        allow_command(&trust, None);
+        assert!(trust.trusted.borrow().is_empty());
+    }
+}
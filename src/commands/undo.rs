@@ -0,0 +1,113 @@
+use crate::storage::{Journal, StorageError, journal::JournalEntry};
+
+pub fn undo_command(journal: &dyn Journal) {
+    match journal.undo() {
+        Ok(Some(entry)) => println!("↩️ Undid '{}': {}", entry.operation, describe(&entry)),
+        Ok(None) => println!("📭 Nothing to undo."),
+        Err(e) => report(e, "undo"),
+    }
+}
+
+pub fn redo_command(journal: &dyn Journal) {
+    match journal.redo() {
+        Ok(Some(entry)) => println!("↪️ Redid '{}': {}", entry.operation, describe(&entry)),
+        Ok(None) => println!("📭 Nothing to redo."),
+        Err(e) => report(e, "redo"),
+    }
+}
+
+fn report(error: StorageError, action: &str) {
+    match error {
+        StorageError::Conflict(msg) => eprintln!("⛔ {}", msg),
+        e => eprintln!("⛔ Failed to {}: {}", action, e),
+    }
+}
+
+pub fn describe(entry: &JournalEntry) -> String {
+    let names: Vec<&str> = entry.changes.iter().map(|c| c.name()).collect();
+
+    match names.len() {
+        0 => "no snippets changed".to_string(),
+        1..=3 => names.join(", "),
+        n => format!("{} and {} more", names[..3].join(", "), n - 3),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{Snippet, SnippetKind},
+        storage::journal::SnippetChange,
+    };
+    use std::cell::RefCell;
+
+    struct MockJournal {
+        calls: RefCell<Vec<&'static str>>,
+        conflict: bool,
+    }
+
+    impl Journal for MockJournal {
+        fn undo(&self) -> Result<Option<JournalEntry>, StorageError> {
+            self.calls.borrow_mut().push("undo");
+            if self.conflict {
+                return Err(StorageError::Conflict("changed".into()));
+            }
+            Ok(None)
+        }
+
+        fn redo(&self) -> Result<Option<JournalEntry>, StorageError> {
+            self.calls.borrow_mut().push("redo");
+            Ok(None)
+        }
+    }
+
+    fn change(name: &str) -> SnippetChange {
+        SnippetChange {
+            id: Snippet::generate_id(),
+            before: None,
+            after: Some(Snippet {
+                id: Snippet::generate_id(),
+                name: name.into(),
+                description: String::new(),
+                content: String::new(),
+                kind: SnippetKind::Plain,
+                tags: vec![],
+                variables: vec![],
+                database: None,
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_commands_call_journal() {
+        let journal = MockJournal {
+            calls: RefCell::new(vec![]),
+            conflict: true,
+        };
+
+        undo_command(&journal);
+        redo_command(&journal);
+
+        assert_eq!(*journal.calls.borrow(), vec!["undo", "redo"]);
+    }
+
+    #[test]
+    fn test_describe_lists_changed_snippets() {
+        let mut entry = JournalEntry {
+            operation: "import".into(),
+            recorded_at: chrono::Utc::now(),
+            changes: vec![change("a"), change("b")],
+            before_hash: String::new(),
+            after_hash: String::new(),
+        };
+        assert_eq!(describe(&entry), "a, b");
+
+        entry
+            .changes
+            .extend([change("c"), change("d"), change("e")]);
+        assert_eq!(describe(&entry), "a, b, c and 2 more");
+    }
+}
@@ -0,0 +1,317 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::storage::backend::BackendKind;
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct Config {
+    #[serde(default)]
+    pub sql: SqlConfig,
+    #[serde(default)]
+    pub backups: BackupConfig,
+    #[serde(default)]
+    pub include: Vec<IncludeConfig>,
+    #[serde(default)]
+    pub storage: StorageConfig,
+    #[serde(default)]
+    pub git: GitConfig,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GitConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub remote: Option<String>,
+    #[serde(default = "default_git_branch")]
+    pub branch: String,
+}
+
+impl Default for GitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            remote: None,
+            branch: default_git_branch(),
+        }
+    }
+}
+
+fn default_git_branch() -> String {
+    "main".to_string()
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct StorageConfig {
+    #[serde(default)]
+    pub backend: Option<BackendKind>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct IncludeConfig {
+    pub path: PathBuf,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+impl IncludeConfig {
+    pub fn label(&self) -> String {
+        self.name.clone().unwrap_or_else(|| {
+            let path = if self.path.ends_with("bookmarks.yml") {
+                self.path.parent().unwrap_or(&self.path)
+            } else {
+                &self.path
+            };
+            path.file_stem()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "shared".to_string())
+        })
+    }
+
+    pub fn resolve_path(&self, config_dir: &Path) -> PathBuf {
+        match self.path.strip_prefix("~") {
+            Ok(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+            Err(_) => config_dir.join(&self.path),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SqlConfig {
+    #[serde(default = "default_sql_client")]
+    pub client: Vec<String>,
+    #[serde(default)]
+    pub database: Option<String>,
+}
+
+impl Default for SqlConfig {
+    fn default() -> Self {
+        Self {
+            client: default_sql_client(),
+            database: None,
+        }
+    }
+}
+
+fn default_sql_client() -> Vec<String> {
+    vec!["sqlite3".to_string(), "{db}".to_string()]
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BackupConfig {
+    #[serde(default = "default_keep_last")]
+    pub keep_last: usize,
+    #[serde(default = "default_keep_daily_days")]
+    pub keep_daily_days: u32,
+    #[serde(default)]
+    pub max_total_size: Option<ByteSize>,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            keep_last: default_keep_last(),
+            keep_daily_days: default_keep_daily_days(),
+            max_total_size: None,
+        }
+    }
+}
+
+fn default_keep_last() -> usize {
+    50
+}
+
+fn default_keep_daily_days() -> u32 {
+    30
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(try_from = "RawByteSize", into = "u64")]
+pub struct ByteSize(pub u64);
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawByteSize {
+    Bytes(u64),
+    Text(String),
+}
+
+impl TryFrom<RawByteSize> for ByteSize {
+    type Error = String;
+
+    fn try_from(raw: RawByteSize) -> Result<Self, Self::Error> {
+        match raw {
+            RawByteSize::Bytes(bytes) => Ok(ByteSize(bytes)),
+            RawByteSize::Text(text) => text.parse(),
+        }
+    }
+}
+
+impl From<ByteSize> for u64 {
+    fn from(size: ByteSize) -> Self {
+        size.0
+    }
+}
+
+impl std::str::FromStr for ByteSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (number, unit) = s.split_at(split);
+
+        let number: u64 = number
+            .parse()
+            .map_err(|_| format!("invalid size '{}'", s))?;
+        let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+            "" | "b" => 1,
+            "k" | "kb" | "kib" => 1 << 10,
+            "m" | "mb" | "mib" => 1 << 20,
+            "g" | "gb" | "gib" => 1 << 30,
+            other => return Err(format!("unknown size unit '{}'", other)),
+        };
+
+        number
+            .checked_mul(multiplier)
+            .map(ByteSize)
+            .ok_or_else(|| format!("size '{}' is too large", s))
+    }
+}
+
+impl Config {
+    pub fn load_from(path: &Path) -> Self {
+        let contents = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => return Self::default(),
+        };
+
+        serde_yaml::from_str(&contents).unwrap_or_else(|e| {
+            eprintln!(
+                "⚠️ Invalid config at '{}', using defaults: {}",
+                path.display(),
+                e
+            );
+            Self::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_missing_config_uses_defaults() {
+        let config = Config::load_from(Path::new("/nonexistent/markit/config.yml"));
+
+        assert_eq!(config.sql.client, vec!["sqlite3", "{db}"]);
+        assert!(config.sql.database.is_none());
+    }
+
+    #[test]
+    fn test_partial_config_fills_defaults() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "sql:\n  database: app.db").unwrap();
+
+        let config = Config::load_from(file.path());
+
+        assert_eq!(config.sql.client, vec!["sqlite3", "{db}"]);
+        assert_eq!(config.sql.database.as_deref(), Some("app.db"));
+    }
+
+    #[test]
+    fn test_custom_sql_client() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "sql:\n  client: [psql, \"{{db}}\", -f, \"-\"]").unwrap();
+
+        let config = Config::load_from(file.path());
+
+        assert_eq!(config.sql.client, vec!["psql", "{db}", "-f", "-"]);
+    }
+
+    #[test]
+    fn test_backup_retention_config() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "backups:\n  keep_last: 5\n  max_total_size: 10MB").unwrap();
+
+        let config = Config::load_from(file.path());
+
+        assert_eq!(config.backups.keep_last, 5);
+        assert_eq!(config.backups.keep_daily_days, 30);
+        assert_eq!(config.backups.max_total_size, Some(ByteSize(10 << 20)));
+    }
+
+    #[test]
+    fn test_include_config() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            "include:\n  - path: /srv/team-snippets/bookmarks.yml\n  - path: shared/runbooks.yml\n    name: ops"
+        )
+        .unwrap();
+
+        let config = Config::load_from(file.path());
+
+        assert_eq!(config.include.len(), 2);
+        assert_eq!(config.include[0].label(), "team-snippets");
+        assert_eq!(config.include[1].label(), "ops");
+        assert_eq!(
+            config.include[0].resolve_path(Path::new("/etc/markit")),
+            PathBuf::from("/srv/team-snippets/bookmarks.yml")
+        );
+        assert_eq!(
+            config.include[1].resolve_path(Path::new("/etc/markit")),
+            PathBuf::from("/etc/markit/shared/runbooks.yml")
+        );
+    }
+
+    #[test]
+    fn test_storage_backend_config() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "storage:\n  backend: dir").unwrap();
+
+        assert_eq!(
+            Config::load_from(file.path()).storage.backend,
+            Some(BackendKind::Dir)
+        );
+        assert_eq!(Config::default().storage.backend, None);
+    }
+
+    #[test]
+    fn test_git_config() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "git:\n  enabled: true\n  remote: /srv/snippets.git").unwrap();
+
+        let config = Config::load_from(file.path());
+
+        assert!(config.git.enabled);
+        assert_eq!(config.git.remote.as_deref(), Some("/srv/snippets.git"));
+        assert_eq!(config.git.branch, "main");
+    }
+
+    #[test]
+    fn test_byte_size_parsing() {
+        assert_eq!("512".parse::<ByteSize>(), Ok(ByteSize(512)));
+        assert_eq!("4 KiB".parse::<ByteSize>(), Ok(ByteSize(4096)));
+        assert_eq!("1g".parse::<ByteSize>(), Ok(ByteSize(1 << 30)));
+        assert!("ten MB".parse::<ByteSize>().is_err());
+        assert!("10 TB".parse::<ByteSize>().is_err());
+        assert!("99999999999G".parse::<ByteSize>().is_err());
+    }
+
+    #[test]
+    fn test_invalid_config_uses_defaults() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "sql: [not, a, map]").unwrap();
+
+        let config = Config::load_from(file.path());
+
+        assert_eq!(config.sql.client, vec!["sqlite3", "{db}"]);
+    }
+}
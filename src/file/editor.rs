@@ -1,18 +1,18 @@
-use crate::{file::EditorLauncher, models::PartialSnippet};
+use crate::{
+    file::{EditorLauncher, TextEditor},
+    models::PartialSnippet,
+};
 use std::io::Write;
 
 pub struct Editor;
 
-impl EditorLauncher for Editor {
-    fn open_editor(&self, snippet: &PartialSnippet) -> Result<PartialSnippet, String> {
+impl TextEditor for Editor {
+    fn edit_text(&self, text: &str) -> Result<String, String> {
         let mut tmpfile = tempfile::NamedTempFile::new()
             .map_err(|e| format!("Could not create temp file: {}", e))?;
 
-        let yaml = serde_yaml::to_string(snippet)
-            .map_err(|e| format!("Could not serialize snippet: {}", e))?;
-
         tmpfile
-            .write_all(yaml.as_bytes())
+            .write_all(text.as_bytes())
             .map_err(|e| format!("Could not write to temp file: {}", e))?;
 
         let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vim".to_string());
@@ -26,8 +26,17 @@ impl EditorLauncher for Editor {
             return Err("Editor exited with an error.".to_string());
         }
 
-        let contents = std::fs::read_to_string(tmpfile.path())
-            .map_err(|e| format!("Could not read edited file: {}", e))?;
+        std::fs::read_to_string(tmpfile.path())
+            .map_err(|e| format!("Could not read edited file: {}", e))
+    }
+}
+
+impl EditorLauncher for Editor {
+    fn open_editor(&self, snippet: &PartialSnippet) -> Result<PartialSnippet, String> {
+        let yaml = serde_yaml::to_string(snippet)
+            .map_err(|e| format!("Could not serialize snippet: {}", e))?;
+
+        let contents = self.edit_text(&yaml)?;
 
         let edited: PartialSnippet =
             serde_yaml::from_str(&contents).map_err(|_| "Invalid YAML.".to_string())?;
@@ -9,6 +9,10 @@ pub trait EditorLauncher {
     fn open_editor(&self, snippet: &PartialSnippet) -> Result<PartialSnippet, String>;
 }
 
+pub trait TextEditor {
+    fn edit_text(&self, text: &str) -> Result<String, String>;
+}
+
 pub trait FileWriter {
     fn write_yaml(&self, path: &str, store: &SnippetStore) -> Result<(), String>;
 }
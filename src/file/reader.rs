@@ -6,7 +6,9 @@ pub struct Reader;
 impl FileReader for Reader {
     fn read_yaml(&self, path: &str) -> Result<SnippetStore, StorageError> {
         let file = File::open(Path::new(path)).map_err(StorageError::Io)?;
-        let store = serde_yaml::from_reader(file).map_err(StorageError::Serde)?;
+        let mut store: SnippetStore =
+            serde_yaml::from_reader(file).map_err(StorageError::Serde)?;
+        store.backfill_ids();
         Ok(store)
     }
 }
@@ -0,0 +1,148 @@
+use std::{fs, io::Read, path::PathBuf};
+
+use crate::{input::SaveInput, models::SnippetKind};
+
+pub enum ContentSource {
+    Inline(String),
+    File(PathBuf),
+    Stdin,
+}
+
+pub struct ArgsSaveInput {
+    description: String,
+    content: String,
+    kind: SnippetKind,
+    tags: Vec<String>,
+}
+
+impl ArgsSaveInput {
+    pub fn new(
+        description: Option<String>,
+        content: String,
+        kind: Option<SnippetKind>,
+        tags: Vec<String>,
+    ) -> Self {
+        let tags = tags
+            .iter()
+            .flat_map(|t| t.split(','))
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        Self {
+            description: description.unwrap_or_default().trim().to_string(),
+            content,
+            kind: kind.unwrap_or_default(),
+            tags,
+        }
+    }
+
+    pub fn read_content(source: ContentSource, stdin: &mut dyn Read) -> Result<String, String> {
+        let content = match source {
+            ContentSource::Inline(content) => content,
+            ContentSource::File(path) => fs::read_to_string(&path)
+                .map_err(|e| format!("Could not read '{}': {}", path.display(), e))?,
+            ContentSource::Stdin => {
+                let mut content = String::new();
+                stdin
+                    .read_to_string(&mut content)
+                    .map_err(|e| format!("Could not read stdin: {}", e))?;
+                content
+            }
+        };
+
+        if content.trim().is_empty() {
+            return Err("Snippet content is empty.".to_string());
+        }
+
+        Ok(content)
+    }
+}
+
+impl SaveInput for ArgsSaveInput {
+    fn get_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_kind(&self) -> SnippetKind {
+        self.kind
+    }
+
+    fn get_content(&self) -> String {
+        self.content.clone()
+    }
+
+    fn get_tags(&self) -> Vec<String> {
+        self.tags.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_defaults_when_flags_missing() {
+        let input = ArgsSaveInput::new(None, "ls -la".into(), None, vec![]);
+
+        assert_eq!(input.get_description(), "");
+        assert_eq!(input.get_kind(), SnippetKind::Plain);
+        assert_eq!(input.get_content(), "ls -la");
+        assert!(input.get_tags().is_empty());
+    }
+
+    #[test]
+    fn test_tags_are_split_and_trimmed() {
+        let input = ArgsSaveInput::new(
+            Some(" List files ".into()),
+            "ls".into(),
+            Some(SnippetKind::Shell),
+            vec!["fs, unix".into(), "".into(), "daily".into()],
+        );
+
+        assert_eq!(input.get_description(), "List files");
+        assert_eq!(input.get_kind(), SnippetKind::Shell);
+        assert_eq!(input.get_tags(), vec!["fs", "unix", "daily"]);
+    }
+
+    #[test]
+    fn test_read_inline_content() {
+        let content = ArgsSaveInput::read_content(
+            ContentSource::Inline("echo hi".into()),
+            &mut std::io::empty(),
+        );
+        assert_eq!(content, Ok("echo hi".to_string()));
+    }
+
+    #[test]
+    fn test_read_content_from_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "docker system prune -af").unwrap();
+
+        let content = ArgsSaveInput::read_content(
+            ContentSource::File(file.path().to_path_buf()),
+            &mut std::io::empty(),
+        );
+        assert_eq!(content, Ok("docker system prune -af\n".to_string()));
+    }
+
+    #[test]
+    fn test_read_content_from_stdin() {
+        let mut stdin = "git log --oneline\n".as_bytes();
+        let content = ArgsSaveInput::read_content(ContentSource::Stdin, &mut stdin);
+        assert_eq!(content, Ok("git log --oneline\n".to_string()));
+    }
+
+    #[test]
+    fn test_read_content_rejects_empty_and_missing() {
+        assert!(ArgsSaveInput::read_content(ContentSource::Stdin, &mut "  \n".as_bytes()).is_err());
+        assert!(
+            ArgsSaveInput::read_content(
+                ContentSource::File(PathBuf::from("/nonexistent/snippet.sh")),
+                &mut std::io::empty(),
+            )
+            .is_err()
+        );
+    }
+}
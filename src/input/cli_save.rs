@@ -1,4 +1,4 @@
-use crate::input::SaveInput;
+use crate::{input::SaveInput, models::SnippetKind};
 use std::io::{self, BufRead, Write};
 
 pub struct CliSaveInput;
@@ -13,15 +13,26 @@ impl SaveInput for CliSaveInput {
         description.trim().to_string()
     }
 
-    fn get_executable(&self) -> bool {
-        print!("🚀 Executable? (y/N): ");
-        io::stdout().flush().unwrap();
+    fn get_kind(&self) -> SnippetKind {
+        let kinds: Vec<String> = SnippetKind::ALL.iter().map(|k| k.to_string()).collect();
+
+        loop {
+            print!("🚀 Kind ({}) [plain]: ", kinds.join("/"));
+            io::stdout().flush().unwrap();
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input).unwrap();
+            let trimmed = input.trim();
 
-        let mut input = String::new();
-        io::stdin().read_line(&mut input).unwrap();
-        let trimmed = input.trim().to_lowercase();
+            if trimmed.is_empty() {
+                return SnippetKind::Plain;
+            }
 
-        matches!(trimmed.as_str(), "y" | "yes")
+            match trimmed.parse() {
+                Ok(kind) => return kind,
+                Err(e) => eprintln!("⚠️ {}", e),
+            }
+        }
     }
 
     fn get_content(&self) -> String {
@@ -0,0 +1,438 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{input::SaveInput, models::SnippetKind, ui::SelectionUI};
+
+const MAX_ENTRIES: usize = 50;
+const ZSH_META: u8 = 0x83;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HistoryFormat {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+pub struct HistorySaveInput<'a> {
+    content: String,
+    details: &'a dyn SaveInput,
+}
+
+impl<'a> HistorySaveInput<'a> {
+    pub fn pick(
+        entries: &[String],
+        selection_ui: &dyn SelectionUI,
+        details: &'a dyn SaveInput,
+    ) -> Option<Self> {
+        let labels: Vec<String> = entries.iter().map(|e| entry_label(e)).collect();
+        let index = selection_ui.with_choice_list("Select a command from history", &labels)?;
+
+        entries.get(index).map(|content| Self {
+            content: format!("{}\n", content),
+            details,
+        })
+    }
+}
+
+impl SaveInput for HistorySaveInput<'_> {
+    fn get_description(&self) -> String {
+        self.details.get_description()
+    }
+
+    fn get_kind(&self) -> SnippetKind {
+        self.details.get_kind()
+    }
+
+    fn get_content(&self) -> String {
+        self.content.clone()
+    }
+
+    fn get_tags(&self) -> Vec<String> {
+        self.details.get_tags()
+    }
+}
+
+pub fn load_recent_history() -> Result<Vec<String>, String> {
+    let home = dirs::home_dir().ok_or("Could not determine the home directory.")?;
+    let shell = std::env::var("SHELL").ok();
+    let histfile = std::env::var_os("HISTFILE").map(PathBuf::from);
+
+    let (path, format) = locate_history(&home, shell.as_deref(), histfile)
+        .ok_or("No bash, zsh or fish history file found.")?;
+    let raw = fs::read(&path).map_err(|e| format!("Could not read '{}': {}", path.display(), e))?;
+
+    let entries = recent_entries(parse_history(&raw, format), MAX_ENTRIES);
+    if entries.is_empty() {
+        return Err(format!("No commands found in '{}'.", path.display()));
+    }
+
+    Ok(entries)
+}
+
+pub fn locate_history(
+    home: &Path,
+    shell: Option<&str>,
+    histfile: Option<PathBuf>,
+) -> Option<(PathBuf, HistoryFormat)> {
+    let fish = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".local/share"))
+        .join("fish/fish_history");
+
+    let mut candidates = vec![
+        (home.join(".zsh_history"), HistoryFormat::Zsh),
+        (home.join(".bash_history"), HistoryFormat::Bash),
+        (fish, HistoryFormat::Fish),
+    ];
+
+    let shell_format = shell
+        .and_then(|s| Path::new(s).file_name())
+        .and_then(|s| s.to_str())
+        .and_then(|s| match s {
+            "zsh" => Some(HistoryFormat::Zsh),
+            "bash" => Some(HistoryFormat::Bash),
+            "fish" => Some(HistoryFormat::Fish),
+            _ => None,
+        });
+
+    if let Some(format) = shell_format {
+        if let Some(path) = histfile.filter(|_| format != HistoryFormat::Fish) {
+            candidates.insert(0, (path, format));
+        }
+        candidates.sort_by_key(|(_, f)| *f != format);
+    }
+
+    candidates.into_iter().find(|(path, _)| path.is_file())
+}
+
+pub fn parse_history(raw: &[u8], format: HistoryFormat) -> Vec<String> {
+    match format {
+        HistoryFormat::Bash => parse_bash(&String::from_utf8_lossy(raw)),
+        HistoryFormat::Zsh => parse_zsh(&String::from_utf8_lossy(&unmetafy(raw))),
+        HistoryFormat::Fish => parse_fish(&String::from_utf8_lossy(raw)),
+    }
+}
+
+pub fn recent_entries(entries: Vec<String>, limit: usize) -> Vec<String> {
+    let mut recent: Vec<String> = Vec::new();
+
+    for entry in entries.into_iter().rev() {
+        let trimmed = entry.trim();
+        if trimmed.is_empty() || trimmed.starts_with("markit save") {
+            continue;
+        }
+        if recent.iter().any(|e| e == trimmed) {
+            continue;
+        }
+
+        recent.push(trimmed.to_string());
+        if recent.len() == limit {
+            break;
+        }
+    }
+
+    recent
+}
+
+fn parse_bash(text: &str) -> Vec<String> {
+    let is_timestamp =
+        |l: &str| l.len() > 1 && l.starts_with('#') && l[1..].bytes().all(|b| b.is_ascii_digit());
+
+    if !text.lines().any(is_timestamp) {
+        return text.lines().map(str::to_string).collect();
+    }
+
+    let mut entries: Vec<String> = Vec::new();
+    let mut current: Option<String> = None;
+
+    for line in text.lines() {
+        if is_timestamp(line) {
+            entries.extend(current.take());
+            current = Some(String::new());
+            continue;
+        }
+
+        match current.as_mut() {
+            Some(entry) if !entry.is_empty() => {
+                entry.push('\n');
+                entry.push_str(line);
+            }
+            Some(entry) => entry.push_str(line),
+            None => entries.push(line.to_string()),
+        }
+    }
+
+    entries.extend(current);
+    entries
+}
+
+fn parse_zsh(text: &str) -> Vec<String> {
+    let mut entries = Vec::new();
+    let mut current: Option<String> = None;
+
+    for line in text.lines() {
+        let line = match current {
+            Some(_) => line,
+            None => strip_zsh_extended(line),
+        };
+
+        let (line, continues) = match line.strip_suffix('\\') {
+            Some(rest) => (rest, true),
+            None => (line, false),
+        };
+
+        let entry = current.get_or_insert_with(String::new);
+        entry.push_str(line);
+
+        if continues {
+            entry.push('\n');
+        } else {
+            entries.extend(current.take());
+        }
+    }
+
+    entries.extend(current);
+    entries
+}
+
+fn strip_zsh_extended(line: &str) -> &str {
+    let Some(rest) = line.strip_prefix(": ") else {
+        return line;
+    };
+    let Some((meta, command)) = rest.split_once(';') else {
+        return line;
+    };
+
+    let is_meta = meta.split_once(':').is_some_and(|(ts, duration)| {
+        !ts.is_empty()
+            && ts.bytes().all(|b| b.is_ascii_digit())
+            && duration.bytes().all(|b| b.is_ascii_digit())
+    });
+
+    if is_meta { command } else { line }
+}
+
+fn unmetafy(raw: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(raw.len());
+    let mut iter = raw.iter();
+
+    while let Some(&b) = iter.next() {
+        if b == ZSH_META {
+            if let Some(&next) = iter.next() {
+                bytes.push(next ^ 0x20);
+            }
+        } else {
+            bytes.push(b);
+        }
+    }
+
+    bytes
+}
+
+fn parse_fish(text: &str) -> Vec<String> {
+    text.lines()
+        .filter_map(|line| line.strip_prefix("- cmd: "))
+        .map(unescape_fish)
+        .collect()
+}
+
+fn unescape_fish(cmd: &str) -> String {
+    let mut out = String::with_capacity(cmd.len());
+    let mut chars = cmd.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+
+    out
+}
+
+fn entry_label(entry: &str) -> String {
+    let mut lines = entry.lines();
+    let first = lines.next().unwrap_or_default();
+    let more = lines.count();
+
+    if more == 0 {
+        first.to_string()
+    } else {
+        format!("{} ↵ (+{} lines)", first, more)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Snippet;
+
+    struct MockSelectionUI {
+        choice: Option<usize>,
+    }
+
+    impl SelectionUI for MockSelectionUI {
+        fn with_snippet_list(&self, _: Vec<Snippet>) -> Option<Snippet> {
+            None
+        }
+
+        fn with_backup_list(&self, _: &[String]) -> Option<usize> {
+            None
+        }
+
+        fn with_choice_list(&self, _: &str, _: &[String]) -> Option<usize> {
+            self.choice
+        }
+    }
+
+    struct MockDetails;
+
+    impl SaveInput for MockDetails {
+        fn get_description(&self) -> String {
+            "from details".into()
+        }
+
+        fn get_kind(&self) -> SnippetKind {
+            SnippetKind::Shell
+        }
+
+        fn get_content(&self) -> String {
+            unreachable!("content comes from history")
+        }
+
+        fn get_tags(&self) -> Vec<String> {
+            vec!["hist".into()]
+        }
+    }
+
+    #[test]
+    fn test_parse_plain_bash_history() {
+        let raw = b"ls -la\ngit status\n";
+        assert_eq!(
+            parse_history(raw, HistoryFormat::Bash),
+            vec!["ls -la", "git status"]
+        );
+    }
+
+    #[test]
+    fn test_parse_bash_history_with_timestamps() {
+        let raw = b"#1700000000\nls -la\n#1700000005\nfor f in *; do\n  echo $f\ndone\n";
+        assert_eq!(
+            parse_history(raw, HistoryFormat::Bash),
+            vec!["ls -la", "for f in *; do\n  echo $f\ndone"]
+        );
+    }
+
+    #[test]
+    fn test_parse_zsh_extended_and_multiline() {
+        let raw = b": 1700000000:0;git status\n: 1700000010:3;docker run \\\n  --rm alpine\nplain entry\n";
+        assert_eq!(
+            parse_history(raw, HistoryFormat::Zsh),
+            vec!["git status", "docker run \n  --rm alpine", "plain entry"]
+        );
+    }
+
+    #[test]
+    fn test_parse_zsh_keeps_lookalike_prefix() {
+        let raw = b": not-a-timestamp;echo hi\n";
+        assert_eq!(
+            parse_history(raw, HistoryFormat::Zsh),
+            vec![": not-a-timestamp;echo hi"]
+        );
+    }
+
+    #[test]
+    fn test_parse_zsh_unmetafies_non_ascii() {
+        let mut raw = b": 1700000000:0;echo ".to_vec();
+        for b in "é".bytes() {
+            if b >= ZSH_META {
+                raw.push(ZSH_META);
+                raw.push(b ^ 0x20);
+            } else {
+                raw.push(b);
+            }
+        }
+        raw.push(b'\n');
+
+        assert_eq!(parse_history(&raw, HistoryFormat::Zsh), vec!["echo é"]);
+    }
+
+    #[test]
+    fn test_parse_fish_history() {
+        let raw = b"- cmd: ls\n  when: 1700000000\n- cmd: printf 'a\\\\nb'\\nand more\n  when: 1700000001\n  paths:\n    - foo\n";
+        assert_eq!(
+            parse_history(raw, HistoryFormat::Fish),
+            vec!["ls", "printf 'a\\nb'\nand more"]
+        );
+    }
+
+    #[test]
+    fn test_recent_entries_are_newest_first_and_unique() {
+        let entries = vec![
+            "ls".to_string(),
+            "git status".to_string(),
+            "ls".to_string(),
+            "markit save foo --from-history".to_string(),
+            "  ".to_string(),
+        ];
+
+        assert_eq!(
+            recent_entries(entries.clone(), 10),
+            vec!["ls", "git status"]
+        );
+        assert_eq!(recent_entries(entries, 1), vec!["ls"]);
+    }
+
+    #[test]
+    fn test_locate_prefers_current_shell() {
+        let home = tempfile::tempdir().unwrap();
+        fs::write(home.path().join(".zsh_history"), "ls\n").unwrap();
+        fs::write(home.path().join(".bash_history"), "ls\n").unwrap();
+
+        let (path, format) = locate_history(home.path(), Some("/bin/bash"), None).unwrap();
+        assert_eq!(format, HistoryFormat::Bash);
+        assert_eq!(path, home.path().join(".bash_history"));
+
+        let (_, format) = locate_history(home.path(), None, None).unwrap();
+        assert_eq!(format, HistoryFormat::Zsh);
+    }
+
+    #[test]
+    fn test_pick_prefills_content_and_delegates_details() {
+        let entries = vec!["git status".to_string(), "ls -la".to_string()];
+        let selection_ui = MockSelectionUI { choice: Some(1) };
+
+        let input = HistorySaveInput::pick(&entries, &selection_ui, &MockDetails).unwrap();
+
+        assert_eq!(input.get_content(), "ls -la\n");
+        assert_eq!(input.get_description(), "from details");
+        assert_eq!(input.get_kind(), SnippetKind::Shell);
+        assert_eq!(input.get_tags(), vec!["hist"]);
+    }
+
+    #[test]
+    fn test_pick_cancelled() {
+        let entries = vec!["git status".to_string()];
+        let selection_ui = MockSelectionUI { choice: None };
+
+        assert!(HistorySaveInput::pick(&entries, &selection_ui, &MockDetails).is_none());
+    }
+
+    #[test]
+    fn test_entry_label_collapses_multiline() {
+        assert_eq!(entry_label("ls"), "ls");
+        assert_eq!(entry_label("a\nb\nc"), "a ↵ (+2 lines)");
+    }
+}
@@ -1,8 +1,12 @@
+use crate::models::SnippetKind;
+
+pub mod args_save;
 pub mod cli_save;
+pub mod history_save;
 
 pub trait SaveInput {
     fn get_description(&self) -> String;
-    fn get_executable(&self) -> bool;
+    fn get_kind(&self) -> SnippetKind;
     fn get_content(&self) -> String;
     fn get_tags(&self) -> Vec<String>;
 }
@@ -1,82 +1,599 @@
+mod agent;
 mod cli;
 mod clipboard_provider;
 mod command_runner;
 mod commands;
+mod config;
 mod file;
 mod input;
 mod models;
+mod paths;
 mod search;
+mod secret_scan;
 mod storage;
+mod template;
 mod ui;
 
+use std::{
+    env,
+    io::{self, IsTerminal},
+};
+
 use clap::Parser;
-use cli::{Cli, Commands};
+use cli::{BackupsAction, Cli, Commands, SecretAction, StoreAction, TrashAction};
 
 use crate::{
+    agent::{AgentClient, PASSPHRASE_ENV, PassphraseCache, SECRETS_PASSPHRASE_ENV},
     clipboard_provider::SmartClipboard,
-    command_runner::ShellCommandRunner,
+    command_runner::RunnerRegistry,
     commands::{
-        copy, delete, edit, export, import, list, restore, run, save,
-        search as search_cmd, show,
+        backups, copy, delete, diff, edit, export, history, import, list, merge, migrate,
+        move_snippet, restore, revert, run, save, search as search_cmd, secret, show, store, sync,
+        trash, trust, undo,
     },
+    config::Config,
     file::{editor::Editor, reader::Reader, writer::Writer},
-    input::cli_save::CliSaveInput,
-    storage::file_storage::FileStorage,
-    ui::{cli_confirm::DialoguerConfirm, cli_selection::CliSelection, cli_table::CliTable},
+    input::{cli_save::CliSaveInput, history_save},
+    models::SnippetKind,
+    paths::Paths,
+    storage::{
+        ProjectTrust, Storage, StoreCatalog, StoreEncryption,
+        backend::open_backend,
+        crypto::StoreKey,
+        file_storage::FileStorage,
+        git::GitRepo,
+        include::IncludeStore,
+        layered::LayeredStorage,
+        lock::LockGuard,
+        project::{self, PROJECT_FILE, ProjectFile, TrustList},
+        stores::StoreRegistry,
+        vault::SecretVault,
+    },
+    ui::{
+        SecretPrompt, cli_confirm::DialoguerConfirm, cli_input::DialoguerInput,
+        cli_selection::CliSelection, cli_table::CliTable,
+    },
 };
 
+fn lock_store(storage: &FileStorage) -> Option<LockGuard<'_>> {
+    match storage.lock() {
+        Ok(lock) => Some(lock),
+        Err(e) => {
+            eprintln!("⛔ Failed to lock the snippet store: {}", e);
+            None
+        }
+    }
+}
+
+fn lock_stores<'a>(storages: &[&'a FileStorage]) -> Option<Vec<LockGuard<'a>>> {
+    let mut ordered = storages.to_vec();
+    ordered.sort_by(|a, b| a.data_dir().cmp(b.data_dir()));
+    ordered.into_iter().map(lock_store).collect()
+}
+
+fn with_git(storage: FileStorage, paths: &Paths, config: &Config) -> FileStorage {
+    if !config.git.enabled {
+        return storage;
+    }
+
+    match GitRepo::open_or_init(&paths.data, &config.git.branch) {
+        Ok(git) => storage.with_git(git.with_remote(config.git.remote.clone())),
+        Err(e) => {
+            eprintln!("⚠️ Git versioning is unavailable: {}", e);
+            storage
+        }
+    }
+}
+
+fn open_store(stores: &StoreRegistry, name: &str, paths: &Paths, config: &Config) -> FileStorage {
+    with_git(
+        stores
+            .open(name)
+            .with_backend(config.storage.backend.unwrap_or_default())
+            .with_retention(config.backups.clone()),
+        paths,
+        config,
+    )
+}
+
+fn unlock_store(
+    storage: FileStorage,
+    name: &str,
+    cache: &dyn PassphraseCache,
+    prompt: &dyn SecretPrompt,
+) -> Option<FileStorage> {
+    if !storage.is_encrypted() {
+        return Some(storage);
+    }
+
+    let passphrase = env::var(PASSPHRASE_ENV)
+        .ok()
+        .filter(|p| !p.is_empty())
+        .or_else(|| cache.passphrase(name))
+        .or_else(|| prompt.secret(&format!("Passphrase for store '{}'", name), false));
+    let Some(passphrase) = passphrase else {
+        eprintln!(
+            "⛔ Store '{}' is encrypted. Run `markit store unlock` or set {}.",
+            name, PASSPHRASE_ENV
+        );
+        return None;
+    };
+
+    match StoreKey::unlock(storage.data_dir(), &passphrase) {
+        Ok(key) => Some(storage.with_key(key)),
+        Err(e) => {
+            eprintln!("⛔ Failed to unlock store '{}': {}", name, e);
+            None
+        }
+    }
+}
+
+fn keep_trusted(trust: &dyn ProjectTrust, file: Option<&ProjectFile>, trusted: bool) {
+    if let Some(file) = file
+        && trusted
+        && file.path().exists()
+        && let Err(e) = trust.trust(file.path())
+    {
+        eprintln!(
+            "⚠️ Failed to keep '{}' trusted: {}",
+            file.path().display(),
+            e
+        );
+    }
+}
+
+fn open_vault(paths: &Paths) -> SecretVault {
+    SecretVault::new(&paths.data).with_passphrase(|| {
+        env::var(SECRETS_PASSPHRASE_ENV)
+            .ok()
+            .filter(|p| !p.is_empty())
+            .or_else(|| DialoguerInput.secret("Passphrase for your secrets", false))
+    })
+}
+
 fn main() {
     let args = Cli::parse();
-    let storage = FileStorage::new();
+    if let Commands::Agent { socket } = &args.command {
+        if let Err(e) = agent::serve(socket) {
+            eprintln!("⛔ Passphrase agent stopped: {}", e);
+        }
+        return;
+    }
+    let paths = match Paths::resolve(args.home.clone()) {
+        Ok(paths) => paths,
+        Err(e) => {
+            eprintln!("⛔ {}", e);
+            return;
+        }
+    };
+    match paths.migrate_legacy() {
+        Ok(Some(legacy)) => println!(
+            "📦 Moved your snippets from '{}' to '{}'.",
+            legacy.display(),
+            paths.data.display()
+        ),
+        Ok(None) => {}
+        Err(e) => eprintln!("⚠️ Failed to migrate the legacy data directory: {}", e),
+    }
+
+    let config = Config::load_from(&paths.config_file());
+    let stores = StoreRegistry::new(&paths);
+    let active_store = stores.resolve(args.store.clone());
+
+    if let Commands::Store { action } = &args.command {
+        match action {
+            StoreAction::List => store::list_command(&stores, &active_store),
+            StoreAction::Create { name } => store::create_command(&stores, name.clone()),
+            StoreAction::Remove { name, force } => {
+                let confirm_prompt = DialoguerConfirm;
+                store::remove_command(&stores, &confirm_prompt, name.clone(), *force);
+            }
+            StoreAction::Default { name } => store::default_command(&stores, name.clone()),
+            StoreAction::Encrypt { name } | StoreAction::Decrypt { name } => {
+                let name = name.clone().unwrap_or_else(|| active_store.clone());
+                if !stores.exists(&name) {
+                    eprintln!("⛔ Store '{}' does not exist.", name);
+                    return;
+                }
+                let storage = open_store(&stores, &name, &paths, &config);
+
+                if matches!(action, StoreAction::Encrypt { .. }) {
+                    store::encrypt_command(&storage, &DialoguerInput, &name);
+                    if storage.git().is_some() && storage.is_encrypted() {
+                        eprintln!("⚠️ Earlier git commits still contain the unencrypted snippets.");
+                    }
+                    return;
+                }
+
+                let agent = AgentClient::new(&paths.state);
+                if let Some(storage) = unlock_store(storage, &name, &agent, &DialoguerInput) {
+                    store::decrypt_command(&storage, &name);
+                }
+            }
+            StoreAction::Unlock { name, ttl } => {
+                let name = name.clone().unwrap_or_else(|| active_store.clone());
+                if !stores.exists(&name) {
+                    eprintln!("⛔ Store '{}' does not exist.", name);
+                    return;
+                }
+                let storage = stores.open(&name);
+                let agent = AgentClient::new(&paths.state);
+                store::unlock_command(&storage, &DialoguerInput, &agent, &name, *ttl);
+            }
+            StoreAction::Lock => store::lock_command(&AgentClient::new(&paths.state)),
+        }
+        return;
+    }
+
+    let cwd = env::current_dir().unwrap_or_default();
+    let trust = TrustList::new(&paths.state);
+    if let Commands::Allow { path } | Commands::Deny { path } = &args.command {
+        let path = path.clone().or_else(|| project::discover(&cwd));
+        match args.command {
+            Commands::Allow { .. } => trust::allow_command(&trust, path.as_deref()),
+            _ => trust::deny_command(&trust, path.as_deref()),
+        }
+        return;
+    }
+
+    if let Commands::Secret { action } = &args.command {
+        let vault = open_vault(&paths);
+        match action {
+            SecretAction::Set { name, value } => {
+                secret::set_command(&vault, &DialoguerInput, name.clone(), value.clone())
+            }
+            SecretAction::Get { name } => secret::get_command(&vault, name.clone()),
+            SecretAction::List => secret::list_command(&vault),
+            SecretAction::Rm { name } => secret::remove_command(&vault, name.clone()),
+        }
+        return;
+    }
+
+    if !stores.exists(&active_store) {
+        eprintln!(
+            "⛔ Store '{}' does not exist. Create it with `markit store create {}` or use '{}'.",
+            active_store,
+            active_store,
+            stores.default_store()
+        );
+        return;
+    }
+
+    let agent = AgentClient::new(&paths.state);
+    let storage = open_store(&stores, &active_store, &paths, &config);
+    let Some(storage) = unlock_store(storage, &active_store, &agent, &DialoguerInput) else {
+        return;
+    };
+    if let Some(configured) = config.storage.backend
+        && configured != storage.backend_kind()
+        && !matches!(args.command, Commands::Migrate { .. })
+    {
+        eprintln!(
+            "⚠️ Store '{}' uses the '{}' backend but config.yml selects '{}'. Run `markit migrate --to {}` to convert it.",
+            active_store,
+            storage.backend_kind(),
+            configured,
+            configured
+        );
+    }
+    storage.set_operation(args.command.operation());
+
+    let project_file = project::discover(&cwd)
+        .filter(|path| match trust.is_trusted(path) {
+            Ok(true) => true,
+            Ok(false) => {
+                eprintln!(
+                    "⚠️ Ignoring untrusted '{}'. Review it, then run `markit allow` to use its snippets.",
+                    path.display()
+                );
+                false
+            }
+            Err(e) => {
+                eprintln!("⚠️ Ignoring '{}': {}", path.display(), e);
+                false
+            }
+        })
+        .map(|path| ProjectFile::new(path, &paths.state));
+    let mut snippets = LayeredStorage::new();
+    if let Some(project_file) = &project_file {
+        snippets = snippets.with_layer("project", project_file);
+    }
+    let mut snippets = snippets.with_required_layer(active_store.as_str(), &storage);
+
+    let includes: Vec<IncludeStore> = config
+        .include
+        .iter()
+        .map(|include| IncludeStore::new(include.label(), include.resolve_path(&paths.config)))
+        .collect();
+    let mut shared = LayeredStorage::new();
+    for include in &includes {
+        snippets = snippets.with_layer(include.label(), include);
+        shared = shared.with_layer(include.label(), include);
+    }
 
     match args.command {
-        Commands::Save { name } => {
-            let input = CliSaveInput;
-            save::save_command(&storage, &input, name);
+        Commands::Save {
+            name,
+            description,
+            tag,
+            kind,
+            exec,
+            no_exec,
+            content,
+            from_file,
+            from_history,
+            local,
+        } => {
+            let local_file = local
+                .then(|| project::discover(&cwd).unwrap_or_else(|| cwd.join(PROJECT_FILE)))
+                .map(|path| ProjectFile::new(path, &paths.state));
+            let was_trusted = local_file.as_ref().is_some_and(|file| {
+                !file.path().exists() || trust.is_trusted(file.path()).unwrap_or(false)
+            });
+            let target: &dyn Storage = match &local_file {
+                Some(local_file) => {
+                    println!("📁 Saving to '{}'.", local_file.path().display());
+                    local_file
+                }
+                None => &storage,
+            };
+
+            let history = match from_history
+                .then(history_save::load_recent_history)
+                .transpose()
+            {
+                Ok(history) => history,
+                Err(e) => {
+                    eprintln!("⛔ {}", e);
+                    return;
+                }
+            };
+            let args = save::SaveArgs {
+                description,
+                tags: tag,
+                kind: kind
+                    .or(exec.then_some(SnippetKind::Shell))
+                    .or(no_exec.then_some(SnippetKind::Plain)),
+                content,
+                from_file,
+                piped: !io::stdin().is_terminal(),
+                history,
+            };
+            save::save_from_args(
+                target,
+                &CliSaveInput,
+                &CliSelection::new(),
+                &mut io::stdin(),
+                name,
+                args,
+            );
+            keep_trusted(&trust, local_file.as_ref(), was_trusted);
         }
-        Commands::Run { name } => {
+        Commands::Run { name, set, db } => {
             let selection_ui = CliSelection::new();
-            let runner = ShellCommandRunner;
-            run::run_command(&storage, &selection_ui, &runner, name);
+            let prompt = DialoguerInput;
+            let runner = RunnerRegistry::new(&config.sql);
+            run::run_command(
+                &snippets,
+                &snippets,
+                &selection_ui,
+                &prompt,
+                &runner,
+                &open_vault(&paths),
+                name,
+                set.into_iter().collect(),
+                db,
+            );
         }
         Commands::List { tag, search } => {
-            let mut cli_table = CliTable::new();
-            list::list_command(&storage, &mut cli_table, tag, search);
+            let mut cli_table = CliTable::new().with_sources(&snippets);
+            list::list_command(&snippets, &mut cli_table, tag, search);
         }
         Commands::Search { query } => {
-            let mut cli_table = CliTable::new();
-            search_cmd::search_command(&storage, &mut cli_table, query);
+            let mut cli_table = CliTable::new().with_sources(&snippets);
+            search_cmd::search_command(&snippets, &mut cli_table, query);
         }
         Commands::Show { name } => {
             let selection_ui = CliSelection::new();
-            show::show_command(&storage, &selection_ui, name);
+            show::show_command(&snippets, &selection_ui, name);
         }
-        Commands::Copy { name } => {
+        Commands::Copy { name, set } => {
             let selection_ui = CliSelection::new();
+            let prompt = DialoguerInput;
+            let runner = RunnerRegistry::new(&config.sql);
             let mut clipboard = SmartClipboard::new();
-            copy::copy_command(&storage, &selection_ui, &mut clipboard, name);
+            copy::copy_command(
+                &snippets,
+                &selection_ui,
+                &prompt,
+                &runner,
+                &mut clipboard,
+                name,
+                set.into_iter().collect(),
+            );
         }
-        Commands::Delete { name, force } => {
+        Commands::Delete { name, force, purge } => {
             let selection_ui = CliSelection::new();
+            let Some(_lock) = lock_store(&storage) else {
+                return;
+            };
             let confirm_prompt = DialoguerConfirm;
-            delete::delete_command(&storage, &selection_ui, &confirm_prompt, name, force);
+            delete::delete_command(
+                &storage,
+                &storage,
+                &selection_ui,
+                &confirm_prompt,
+                name,
+                force,
+                purge,
+            );
         }
+        Commands::Move { name, to } => {
+            if to == active_store {
+                eprintln!("⛔ Snippet is already in store '{}'.", to);
+                return;
+            }
+            if !stores.exists(&to) {
+                eprintln!("⛔ Store '{}' does not exist.", to);
+                return;
+            }
+
+            let target = open_store(&stores, &to, &paths, &config);
+            let Some(target) = unlock_store(target, &to, &agent, &DialoguerInput) else {
+                return;
+            };
+            target.set_operation(format!("move from {}", active_store));
+            storage.set_operation(format!("move to {}", to));
+
+            let selection_ui = CliSelection::new();
+            let Some(_locks) = lock_stores(&[&storage, &target]) else {
+                return;
+            };
+            move_snippet::move_command(&storage, &target, &selection_ui, name, &to);
+        }
+        Commands::Store { .. }
+        | Commands::Secret { .. }
+        | Commands::Agent { .. }
+        | Commands::Allow { .. }
+        | Commands::Deny { .. } => {
+            unreachable!(
+                "store, secret, trust and agent commands are handled before opening a store"
+            )
+        }
+        Commands::Migrate { to } => {
+            if storage.is_encrypted() {
+                eprintln!(
+                    "⛔ Store '{}' is encrypted. Run `markit store decrypt` before migrating it.",
+                    active_store
+                );
+                return;
+            }
+            let Some(_lock) = lock_store(&storage) else {
+                return;
+            };
+            let source = open_backend(storage.backend_kind(), storage.data_dir());
+            let target = open_backend(to, storage.data_dir());
+            migrate::migrate_command(source.as_ref(), target.as_ref());
+        }
+        Commands::Sync { prefer } => {
+            let Some(git) = storage.git() else {
+                eprintln!("⛔ Git versioning is off. Set `git.enabled: true` in config.yml first.");
+                return;
+            };
+            let others: Vec<FileStorage> = stores
+                .list_stores()
+                .unwrap_or_default()
+                .iter()
+                .filter(|name| **name != active_store)
+                .map(|name| stores.open(name))
+                .collect();
+            let mut all: Vec<&FileStorage> = others.iter().collect();
+            all.push(&storage);
+            let Some(_locks) = lock_stores(&all) else {
+                return;
+            };
+            sync::sync_command(git, prefer);
+        }
+        Commands::Undo => undo::undo_command(&storage),
+        Commands::Redo => undo::redo_command(&storage),
+        Commands::Undelete { name } => {
+            let selection_ui = CliSelection::new();
+            let Some(_lock) = lock_store(&storage) else {
+                return;
+            };
+            trash::undelete_command(&storage, &storage, &selection_ui, name);
+        }
+        Commands::Trash { action } => match action {
+            TrashAction::List => trash::list_command(&storage),
+            TrashAction::Empty { older_than, force } => {
+                let Some(_lock) = lock_store(&storage) else {
+                    return;
+                };
+                let confirm_prompt = DialoguerConfirm;
+                trash::empty_command(&storage, &confirm_prompt, older_than, force);
+            }
+        },
         Commands::Edit { name } => {
             let selection_ui = CliSelection::new();
+            let Some(_lock) = lock_store(&storage) else {
+                return;
+            };
+            let confirm_prompt = DialoguerConfirm;
             let editor = Editor;
-            edit::edit_command(&storage, &selection_ui, &editor, name);
+            edit::edit_command(
+                &storage,
+                &shared,
+                &selection_ui,
+                &confirm_prompt,
+                &editor,
+                name,
+            );
         }
         Commands::Export { path } => {
             let writer = Writer;
             export::export_command(&storage, &writer, &path);
         }
         Commands::Import { path } => {
+            let Some(_lock) = lock_store(&storage) else {
+                return;
+            };
             let reader = Reader;
-            import::import_command(&storage, &reader, &path);
+            let selection_ui = CliSelection::new();
+            import::import_command(&storage, &reader, &selection_ui, &path);
+        }
+        Commands::Merge { path, base } => {
+            let Some(_lock) = lock_store(&storage) else {
+                return;
+            };
+            let selection_ui = CliSelection::new();
+            merge::merge_command(
+                &storage,
+                &Reader,
+                &selection_ui,
+                &Editor,
+                &path,
+                base.as_deref(),
+            );
+        }
+        Commands::Restore { select } => {
+            let selection_ui = CliSelection::new();
+            let Some(_lock) = lock_store(&storage) else {
+                return;
+            };
+            if select {
+                restore::restore_snippets_command(&storage, &storage, &selection_ui, &selection_ui);
+            } else {
+                let confirm_prompt = DialoguerConfirm;
+                restore::restore_command(&storage, &storage, &selection_ui, &confirm_prompt);
+            }
+        }
+        Commands::Backups { action } => match action {
+            BackupsAction::List => backups::list_command(&storage, &storage),
+            BackupsAction::Show { backup } => {
+                let mut cli_table = CliTable::new();
+                backups::show_command(&storage, &storage, &mut cli_table, backup);
+            }
+            BackupsAction::Prune { dry_run } => {
+                let Some(_lock) = lock_store(&storage) else {
+                    return;
+                };
+                backups::prune_command(&storage, dry_run);
+            }
+        },
+        Commands::History { name } => {
+            let selection_ui = CliSelection::new();
+            history::history_command(&storage, &storage, &selection_ui, name);
+        }
+        Commands::Diff { name, rev } => {
+            let selection_ui = CliSelection::new();
+            diff::diff_command(&storage, &storage, &selection_ui, name, rev);
         }
-        Commands::Restore => {
+        Commands::Revert { name, rev } => {
             let selection_ui = CliSelection::new();
-            restore::restore_command(&storage, &selection_ui);
+            let Some(_lock) = lock_store(&storage) else {
+                return;
+            };
+            revert::revert_command(&storage, &storage, &selection_ui, name, rev);
         }
     }
 }
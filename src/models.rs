@@ -1,19 +1,175 @@
+use std::{collections::HashSet, fmt, str::FromStr};
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(from = "SnippetRecord")]
 pub struct Snippet {
+    pub id: String,
     pub name: String,
     pub description: String,
     pub content: String,
-    pub executable: bool,
+    pub kind: SnippetKind,
     pub tags: Vec<String>,
-    #[serde(default = "default_now")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<Variable>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
     pub created_at: DateTime<Utc>,
-    #[serde(default = "default_now")]
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Deserialize)]
+struct SnippetRecord {
+    #[serde(default)]
+    id: String,
+    name: String,
+    description: String,
+    content: String,
+    #[serde(default)]
+    kind: Option<SnippetKind>,
+    #[serde(default)]
+    executable: Option<bool>,
+    tags: Vec<String>,
+    #[serde(default)]
+    variables: Vec<Variable>,
+    #[serde(default)]
+    database: Option<String>,
+    #[serde(default = "default_now")]
+    created_at: DateTime<Utc>,
+    #[serde(default = "default_now")]
+    updated_at: DateTime<Utc>,
+}
+
+impl From<SnippetRecord> for Snippet {
+    fn from(record: SnippetRecord) -> Self {
+        let kind = record.kind.unwrap_or(match record.executable {
+            Some(true) => SnippetKind::Shell,
+            _ => SnippetKind::Plain,
+        });
+
+        Snippet {
+            id: record.id,
+            name: record.name,
+            description: record.description,
+            content: record.content,
+            kind,
+            tags: record.tags,
+            variables: record.variables,
+            database: record.database,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        }
+    }
+}
+
+impl Snippet {
+    pub fn generate_id() -> String {
+        uuid::Uuid::new_v4().simple().to_string()
+    }
+
+    pub fn derived_id(scope: &str, name: &str) -> String {
+        Sha256::digest(format!("{}\0{}", scope, name.to_lowercase()))
+            .iter()
+            .take(16)
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    pub fn is_valid_id(id: &str) -> bool {
+        !id.is_empty() && id.len() <= 64 && id.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+    }
+
+    pub fn short_id(&self) -> &str {
+        let end = self.id.len().min(8);
+        &self.id[..end]
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SnippetKind {
+    Shell,
+    Bash,
+    Sh,
+    Zsh,
+    Python,
+    Node,
+    Sql,
+    #[default]
+    Plain,
+}
+
+impl SnippetKind {
+    pub const ALL: [SnippetKind; 8] = [
+        SnippetKind::Shell,
+        SnippetKind::Bash,
+        SnippetKind::Sh,
+        SnippetKind::Zsh,
+        SnippetKind::Python,
+        SnippetKind::Node,
+        SnippetKind::Sql,
+        SnippetKind::Plain,
+    ];
+
+    pub fn is_executable(&self) -> bool {
+        *self != SnippetKind::Plain
+    }
+
+    pub fn is_shell(&self) -> bool {
+        matches!(
+            self,
+            SnippetKind::Shell | SnippetKind::Bash | SnippetKind::Sh | SnippetKind::Zsh
+        )
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            SnippetKind::Shell => "shell",
+            SnippetKind::Bash => "bash",
+            SnippetKind::Sh => "sh",
+            SnippetKind::Zsh => "zsh",
+            SnippetKind::Python => "python",
+            SnippetKind::Node => "node",
+            SnippetKind::Sql => "sql",
+            SnippetKind::Plain => "plain",
+        }
+    }
+}
+
+impl fmt::Display for SnippetKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for SnippetKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_lowercase();
+        SnippetKind::ALL
+            .into_iter()
+            .find(|k| k.as_str() == normalized)
+            .ok_or_else(|| {
+                let names: Vec<&str> = SnippetKind::ALL.iter().map(|k| k.as_str()).collect();
+                format!(
+                    "unknown kind '{}' (expected one of: {})",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Variable {
+    pub name: String,
+    pub command: String,
+}
+
 fn default_now() -> DateTime<Utc> {
     Utc::now()
 }
@@ -23,11 +179,326 @@ pub struct SnippetStore {
     pub snippets: Vec<Snippet>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+impl SnippetStore {
+    pub fn backfill_ids(&mut self) -> bool {
+        self.backfill_ids_with(|_| Snippet::generate_id())
+    }
+
+    pub fn backfill_ids_with(&mut self, derive: impl Fn(&Snippet) -> String) -> bool {
+        let mut seen = HashSet::new();
+        let mut changed = false;
+
+        for snippet in &mut self.snippets {
+            if !Snippet::is_valid_id(&snippet.id) || !seen.insert(snippet.id.clone()) {
+                let derived = derive(snippet);
+                snippet.id = if seen.contains(&derived) {
+                    Snippet::generate_id()
+                } else {
+                    derived
+                };
+                seen.insert(snippet.id.clone());
+                changed = true;
+            }
+        }
+
+        changed
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct PartialSnippet {
     pub name: String,
     pub description: String,
     pub content: String,
-    pub executable: bool,
+    #[serde(default)]
+    pub kind: SnippetKind,
     pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<Variable>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+}
+
+impl From<&Snippet> for PartialSnippet {
+    fn from(snippet: &Snippet) -> Self {
+        PartialSnippet {
+            name: snippet.name.clone(),
+            description: snippet.description.clone(),
+            content: snippet.content.clone(),
+            kind: snippet.kind,
+            tags: snippet.tags.clone(),
+            variables: snippet.variables.clone(),
+            database: snippet.database.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TrashEntry {
+    pub deleted_at: DateTime<Utc>,
+    pub snippet: Snippet,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct StoreDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+}
+
+impl StoreDiff {
+    pub fn between(old: &SnippetStore, new: &SnippetStore) -> Self {
+        let same = |a: &Snippet, b: &Snippet| {
+            if a.id.is_empty() || b.id.is_empty() {
+                a.name.eq_ignore_ascii_case(&b.name)
+            } else {
+                a.id == b.id
+            }
+        };
+
+        let mut diff = StoreDiff::default();
+
+        for snippet in &new.snippets {
+            match old.snippets.iter().find(|o| same(o, snippet)) {
+                None => diff.added.push(snippet.name.clone()),
+                Some(o) if PartialSnippet::from(o) != PartialSnippet::from(snippet) => {
+                    diff.modified.push(snippet.name.clone())
+                }
+                Some(_) => {}
+            }
+        }
+
+        for snippet in &old.snippets {
+            if !new.snippets.iter().any(|n| same(snippet, n)) {
+                diff.removed.push(snippet.name.clone());
+            }
+        }
+
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "+{} ~{} -{}",
+            self.added.len(),
+            self.modified.len(),
+            self.removed.len()
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Revision {
+    pub rev: u32,
+    pub recorded_at: DateTime<Utc>,
+    pub snippet: PartialSnippet,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_executable_maps_to_kind() {
+        let yaml = r#"
+snippets:
+  - name: legacy-exec
+    description: old
+    content: ls
+    executable: true
+    tags: []
+  - name: legacy-text
+    description: old
+    content: SELECT 1;
+    executable: false
+    tags: []
+"#;
+
+        let store: SnippetStore = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(store.snippets[0].kind, SnippetKind::Shell);
+        assert_eq!(store.snippets[1].kind, SnippetKind::Plain);
+    }
+
+    #[test]
+    fn test_kind_takes_precedence_over_executable() {
+        let yaml = r#"
+name: script
+description: py
+content: print(1)
+kind: python
+executable: false
+tags: []
+"#;
+
+        let snippet: Snippet = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(snippet.kind, SnippetKind::Python);
+    }
+
+    #[test]
+    fn test_kind_round_trips_without_executable() {
+        let yaml = r#"
+name: q
+description: query
+content: SELECT 1;
+kind: sql
+tags: []
+"#;
+
+        let snippet: Snippet = serde_yaml::from_str(yaml).unwrap();
+        let written = serde_yaml::to_string(&snippet).unwrap();
+
+        assert!(written.contains("kind: sql"));
+        assert!(!written.contains("executable"));
+    }
+
+    #[test]
+    fn test_kind_from_str() {
+        assert_eq!("Python".parse::<SnippetKind>(), Ok(SnippetKind::Python));
+        assert_eq!(" zsh ".parse::<SnippetKind>(), Ok(SnippetKind::Zsh));
+        assert!("ruby".parse::<SnippetKind>().is_err());
+    }
+
+    #[test]
+    fn test_backfill_ids_fills_missing_and_duplicate_ids() {
+        let yaml = r#"
+snippets:
+  - name: a
+    description: ""
+    content: ls
+    kind: shell
+    tags: []
+  - id: 0f1e2d3c
+    name: b
+    description: ""
+    content: ls
+    kind: shell
+    tags: []
+  - id: 0f1e2d3c
+    name: c
+    description: ""
+    content: ls
+    kind: shell
+    tags: []
+"#;
+
+        let mut store: SnippetStore = serde_yaml::from_str(yaml).unwrap();
+        assert!(store.backfill_ids());
+        assert!(!store.backfill_ids());
+
+        assert_eq!(store.snippets[0].id.len(), 32);
+        assert_eq!(store.snippets[1].id, "0f1e2d3c");
+        assert_ne!(store.snippets[2].id, "0f1e2d3c");
+        assert_ne!(store.snippets[0].id, store.snippets[2].id);
+    }
+
+    #[test]
+    fn test_backfill_ids_replaces_unsafe_ids() {
+        let yaml = r#"
+snippets:
+  - id: ../../x
+    name: a
+    description: ""
+    content: ls
+    tags: []
+  - id: 0123456789ABCDEF-0123
+    name: b
+    description: ""
+    content: ls
+    tags: []
+"#;
+
+        let mut store: SnippetStore = serde_yaml::from_str(yaml).unwrap();
+        assert!(store.backfill_ids());
+
+        assert!(Snippet::is_valid_id(&store.snippets[0].id));
+        assert_eq!(store.snippets[1].id, "0123456789ABCDEF-0123");
+    }
+
+    #[test]
+    fn test_backfill_ids_with_derives_stable_ids() {
+        let yaml = "snippets:\n- {name: a, description: '', content: ls, tags: []}\n- {name: A, description: '', content: ls, tags: []}\n";
+        let derive = |s: &Snippet| Snippet::derived_id("/repo/.markit.yml", &s.name);
+
+        let mut first: SnippetStore = serde_yaml::from_str(yaml).unwrap();
+        let mut second: SnippetStore = serde_yaml::from_str(yaml).unwrap();
+        first.backfill_ids_with(derive);
+        second.backfill_ids_with(derive);
+
+        assert_eq!(first.snippets[0].id, second.snippets[0].id);
+        assert!(Snippet::is_valid_id(&first.snippets[0].id));
+        assert_ne!(first.snippets[0].id, first.snippets[1].id);
+    }
+
+    #[test]
+    fn test_short_id() {
+        let mut snippet: Snippet = serde_yaml::from_str(
+            "name: a\ndescription: ''\ncontent: ls\ntags: []\nid: 0123456789abcdef",
+        )
+        .unwrap();
+        assert_eq!(snippet.short_id(), "01234567");
+
+        snippet.id = "abc".to_string();
+        assert_eq!(snippet.short_id(), "abc");
+    }
+
+    fn snippet(id: &str, name: &str, content: &str) -> Snippet {
+        Snippet {
+            id: id.into(),
+            name: name.into(),
+            description: String::new(),
+            content: content.into(),
+            kind: SnippetKind::Plain,
+            tags: vec![],
+            variables: vec![],
+            database: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_store_diff_by_id() {
+        let old = SnippetStore {
+            snippets: vec![
+                snippet("1", "keep", "ls"),
+                snippet("2", "change", "ls"),
+                snippet("3", "gone", "ls"),
+            ],
+        };
+        let new = SnippetStore {
+            snippets: vec![
+                snippet("1", "keep", "ls"),
+                snippet("2", "renamed", "ls"),
+                snippet("4", "new", "ls"),
+            ],
+        };
+
+        let diff = StoreDiff::between(&old, &new);
+
+        assert_eq!(diff.added, vec!["new"]);
+        assert_eq!(diff.modified, vec!["renamed"]);
+        assert_eq!(diff.removed, vec!["gone"]);
+        assert_eq!(diff.summary(), "+1 ~1 -1");
+    }
+
+    #[test]
+    fn test_store_diff_falls_back_to_name_without_ids() {
+        let old = SnippetStore {
+            snippets: vec![snippet("", "a", "ls")],
+        };
+        let new = SnippetStore {
+            snippets: vec![snippet("abc", "A", "ls -la")],
+        };
+
+        let diff = StoreDiff::between(&old, &new);
+
+        assert_eq!(diff.modified, vec!["A"]);
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert!(StoreDiff::between(&old, &old).is_empty());
+    }
 }
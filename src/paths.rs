@@ -0,0 +1,281 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+pub const HOME_ENV: &str = "MARKIT_HOME";
+
+const APP_DIR: &str = "markit";
+const LEGACY_DIR: &str = ".markit";
+const CONFIG_FILE: &str = "config.yml";
+const LOCK_FILE: &str = ".lock";
+const STORES_DIR: &str = "stores";
+const STATE_ENTRIES: [&str; 3] = ["backups", "history", "journal.yml"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Paths {
+    pub data: PathBuf,
+    pub config: PathBuf,
+    pub state: PathBuf,
+    legacy: Option<PathBuf>,
+}
+
+impl Paths {
+    pub fn resolve(home_flag: Option<PathBuf>) -> Result<Self, String> {
+        Self::resolve_with(
+            home_flag,
+            |key| {
+                std::env::var_os(key)
+                    .filter(|v| !v.is_empty())
+                    .map(PathBuf::from)
+            },
+            dirs::home_dir(),
+        )
+    }
+
+    pub fn single(root: PathBuf) -> Self {
+        Self {
+            data: root.clone(),
+            config: root.clone(),
+            state: root,
+            legacy: None,
+        }
+    }
+
+    fn resolve_with(
+        home_flag: Option<PathBuf>,
+        env: impl Fn(&str) -> Option<PathBuf>,
+        home: Option<PathBuf>,
+    ) -> Result<Self, String> {
+        if let Some(root) = home_flag.or_else(|| env(HOME_ENV)) {
+            return Ok(Self::single(root));
+        }
+
+        let xdg = |var: &str, fallback: &str| {
+            env(var)
+                .filter(|p| p.is_absolute())
+                .or_else(|| home.as_ref().map(|h| h.join(fallback)))
+                .map(|p| p.join(APP_DIR))
+                .ok_or_else(|| {
+                    format!(
+                        "Could not determine your home directory. Set {} or pass --home.",
+                        HOME_ENV
+                    )
+                })
+        };
+
+        Ok(Self {
+            data: xdg("XDG_DATA_HOME", ".local/share")?,
+            config: xdg("XDG_CONFIG_HOME", ".config")?,
+            state: xdg("XDG_STATE_HOME", ".local/state")?,
+            legacy: home.map(|h| h.join(LEGACY_DIR)),
+        })
+    }
+
+    pub fn config_file(&self) -> PathBuf {
+        self.config.join(CONFIG_FILE)
+    }
+
+    pub fn migrate_legacy(&self) -> io::Result<Option<&Path>> {
+        let Some(legacy) = self.legacy.as_deref() else {
+            return Ok(None);
+        };
+        if !legacy.is_dir() || self.data.exists() {
+            return Ok(None);
+        }
+
+        let config = legacy.join(CONFIG_FILE);
+        if config.is_file() {
+            move_entry(&config, &self.config_file())?;
+        }
+
+        let stores = legacy.join(STORES_DIR);
+        if stores.is_dir() {
+            for store in fs::read_dir(&stores)? {
+                let store = store?;
+                if store.file_type()?.is_dir() {
+                    let name = store.file_name();
+                    move_store(
+                        &store.path(),
+                        &self.data.join(STORES_DIR).join(&name),
+                        &self.state.join(STORES_DIR).join(&name),
+                    )?;
+                    remove_if_empty(&store.path())?;
+                }
+            }
+            remove_if_empty(&stores)?;
+        }
+
+        move_store(legacy, &self.data, &self.state)?;
+        remove_if_empty(legacy)?;
+        Ok(Some(legacy))
+    }
+}
+
+fn move_store(from: &Path, data: &Path, state: &Path) -> io::Result<()> {
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let name = entry.file_name();
+
+        let target = match name.to_str() {
+            Some(LOCK_FILE) => {
+                fs::remove_file(entry.path())?;
+                continue;
+            }
+            Some(n) if STATE_ENTRIES.contains(&n) => state.join(&name),
+            _ => data.join(&name),
+        };
+
+        move_entry(&entry.path(), &target)?;
+    }
+
+    Ok(())
+}
+
+fn move_entry(from: &Path, to: &Path) -> io::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    copy_recursive(from, to)?;
+    if from.is_dir() {
+        fs::remove_dir_all(from)
+    } else {
+        fs::remove_file(from)
+    }
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> io::Result<()> {
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(from, to).map(|_| ())
+    }
+}
+
+fn remove_if_empty(dir: &Path) -> io::Result<()> {
+    if fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_none()) {
+        fs::remove_dir(dir)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn resolve(
+        flag: Option<&str>,
+        vars: &[(&str, &str)],
+        home: Option<&str>,
+    ) -> Result<Paths, String> {
+        let vars: HashMap<String, PathBuf> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), PathBuf::from(v)))
+            .collect();
+        Paths::resolve_with(
+            flag.map(PathBuf::from),
+            |key| vars.get(key).cloned(),
+            home.map(PathBuf::from),
+        )
+    }
+
+    #[test]
+    fn test_xdg_defaults_under_home() {
+        let paths = resolve(None, &[], Some("/home/u")).unwrap();
+
+        assert_eq!(paths.data, PathBuf::from("/home/u/.local/share/markit"));
+        assert_eq!(paths.config, PathBuf::from("/home/u/.config/markit"));
+        assert_eq!(paths.state, PathBuf::from("/home/u/.local/state/markit"));
+        assert_eq!(
+            paths.config_file(),
+            PathBuf::from("/home/u/.config/markit/config.yml")
+        );
+    }
+
+    #[test]
+    fn test_xdg_variables_override_defaults() {
+        let paths = resolve(
+            None,
+            &[
+                ("XDG_DATA_HOME", "/data"),
+                ("XDG_CONFIG_HOME", "relative/is/ignored"),
+                ("XDG_STATE_HOME", "/state"),
+            ],
+            Some("/home/u"),
+        )
+        .unwrap();
+
+        assert_eq!(paths.data, PathBuf::from("/data/markit"));
+        assert_eq!(paths.config, PathBuf::from("/home/u/.config/markit"));
+        assert_eq!(paths.state, PathBuf::from("/state/markit"));
+    }
+
+    #[test]
+    fn test_home_flag_and_env_use_a_single_directory() {
+        let env = [(HOME_ENV, "/env"), ("XDG_DATA_HOME", "/data")];
+
+        assert_eq!(
+            resolve(None, &env, None).unwrap(),
+            Paths::single(PathBuf::from("/env"))
+        );
+        assert_eq!(
+            resolve(Some("/flag"), &env, None).unwrap(),
+            Paths::single(PathBuf::from("/flag"))
+        );
+    }
+
+    #[test]
+    fn test_missing_home_is_an_error() {
+        assert!(resolve(None, &[], None).is_err());
+        assert!(resolve(None, &[("XDG_DATA_HOME", "/data")], None).is_err());
+    }
+
+    #[test]
+    fn test_migrates_legacy_directory_once() {
+        let home = tempfile::tempdir().unwrap();
+        let legacy = home.path().join(".markit");
+        fs::create_dir_all(legacy.join("backups")).unwrap();
+        fs::create_dir_all(legacy.join("stores/work/history")).unwrap();
+        fs::write(legacy.join("bookmarks.yml"), "snippets: []\n").unwrap();
+        fs::write(legacy.join("config.yml"), "sql: {}\n").unwrap();
+        fs::write(legacy.join("journal.yml"), "undo: []\n").unwrap();
+        fs::write(legacy.join(".lock"), "").unwrap();
+        fs::write(legacy.join("backups/b.yml"), "snippets: []\n").unwrap();
+        fs::write(legacy.join("stores/work/bookmarks.yml"), "snippets: []\n").unwrap();
+        fs::write(legacy.join("stores/work/history/x.yml"), "[]\n").unwrap();
+
+        let paths = resolve(None, &[], home.path().to_str()).unwrap();
+        assert_eq!(paths.migrate_legacy().unwrap(), Some(legacy.as_path()));
+
+        assert!(paths.data.join("bookmarks.yml").is_file());
+        assert!(paths.config.join("config.yml").is_file());
+        assert!(paths.state.join("journal.yml").is_file());
+        assert!(paths.state.join("backups/b.yml").is_file());
+        assert!(paths.data.join("stores/work/bookmarks.yml").is_file());
+        assert!(paths.state.join("stores/work/history/x.yml").is_file());
+        assert!(!paths.data.join(".lock").exists());
+        assert!(!legacy.exists());
+
+        fs::create_dir_all(&legacy).unwrap();
+        fs::write(legacy.join("bookmarks.yml"), "snippets: []\n").unwrap();
+        assert_eq!(paths.migrate_legacy().unwrap(), None);
+        assert!(legacy.join("bookmarks.yml").is_file());
+    }
+
+    #[test]
+    fn test_no_migration_with_explicit_home() {
+        let home = tempfile::tempdir().unwrap();
+        let paths = Paths::single(home.path().join("custom"));
+        assert_eq!(paths.migrate_legacy().unwrap(), None);
+    }
+}
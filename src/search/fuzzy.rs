@@ -87,15 +87,19 @@ impl Searcher for FuzzySearcher {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::SnippetKind;
     use chrono::Utc;
 
     fn create_test_snippet(name: &str, description: &str, content: &str, tags: Vec<&str>) -> Snippet {
         Snippet {
+            id: Snippet::generate_id(),
             name: name.to_string(),
             description: description.to_string(),
             content: content.to_string(),
-            executable: true,
+            kind: SnippetKind::Shell,
             tags: tags.iter().map(|s| s.to_string()).collect(),
+            variables: vec![],
+            database: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
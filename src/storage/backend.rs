@@ -0,0 +1,452 @@
+use std::{
+    collections::HashSet,
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+
+use crate::{
+    models::{Snippet, SnippetStore},
+    storage::{
+        StorageError,
+        atomic::write_atomic,
+        crypto::{StoreKey, read_file, write_file},
+        filter::{Filter, apply_filter},
+        sqlite::{SqliteBackend, database_path},
+    },
+};
+
+const YAML_FILE: &str = "bookmarks.yml";
+const SNIPPETS_DIR: &str = "snippets";
+const SNIPPET_EXTENSION: &str = "md";
+const FRONT_MATTER_DELIMITER: &str = "---\n";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendKind {
+    #[default]
+    Yaml,
+    Dir,
+    Sqlite,
+}
+
+impl BackendKind {
+    pub const ALL: [BackendKind; 3] = [BackendKind::Yaml, BackendKind::Dir, BackendKind::Sqlite];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            BackendKind::Yaml => "yaml",
+            BackendKind::Dir => "dir",
+            BackendKind::Sqlite => "sqlite",
+        }
+    }
+}
+
+impl fmt::Display for BackendKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for BackendKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_lowercase();
+        BackendKind::ALL
+            .into_iter()
+            .find(|k| k.as_str() == normalized)
+            .ok_or_else(|| {
+                let names: Vec<&str> = BackendKind::ALL.iter().map(|k| k.as_str()).collect();
+                format!(
+                    "unknown backend '{}' (expected one of: {})",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+pub trait SnippetBackend {
+    fn kind(&self) -> BackendKind;
+    fn exists(&self) -> bool;
+    fn read(&self) -> Result<SnippetStore, StorageError>;
+    fn write(&self, store: &SnippetStore) -> Result<(), StorageError>;
+    fn clear(&self) -> Result<(), StorageError>;
+
+    fn is_transactional(&self) -> bool {
+        false
+    }
+
+    fn find_by_name(&self, name: &str) -> Result<Option<Snippet>, StorageError> {
+        Ok(self
+            .read()?
+            .snippets
+            .into_iter()
+            .find(|s| s.name.eq_ignore_ascii_case(name)))
+    }
+
+    fn find_by_tag(&self, tag: &str) -> Result<Vec<Snippet>, StorageError> {
+        Ok(apply_filter(&self.read()?, Filter::Tag(tag.to_string())))
+    }
+}
+
+pub fn open_backend(kind: BackendKind, data_dir: &Path) -> Box<dyn SnippetBackend> {
+    match kind {
+        BackendKind::Yaml => Box::new(YamlBackend::new(yaml_path(data_dir))),
+        BackendKind::Dir => Box::new(DirBackend::new(data_dir.join(SNIPPETS_DIR))),
+        BackendKind::Sqlite => Box::new(SqliteBackend::new(database_path(data_dir))),
+    }
+}
+
+pub fn open_sealed_backend(
+    kind: BackendKind,
+    data_dir: &Path,
+    key: Option<&StoreKey>,
+) -> Box<dyn SnippetBackend> {
+    match (kind, key) {
+        (BackendKind::Yaml, Some(key)) => {
+            Box::new(YamlBackend::new(yaml_path(data_dir)).with_key(key.clone()))
+        }
+        _ => open_backend(kind, data_dir),
+    }
+}
+
+pub fn yaml_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(YAML_FILE)
+}
+
+pub fn detect_backend(data_dir: &Path) -> Option<BackendKind> {
+    BackendKind::ALL
+        .into_iter()
+        .find(|kind| open_backend(*kind, data_dir).exists())
+}
+
+pub fn store_dir_of(file: &Path) -> Option<&Path> {
+    let parent = file.parent()?;
+    let name = file.file_name()?;
+    if name == YAML_FILE || file == database_path(parent) {
+        Some(parent)
+    } else if parent.file_name()? == SNIPPETS_DIR
+        && file.extension().is_some_and(|e| e == SNIPPET_EXTENSION)
+    {
+        parent.parent()
+    } else {
+        None
+    }
+}
+
+pub struct YamlBackend {
+    path: PathBuf,
+    key: Option<StoreKey>,
+}
+
+impl YamlBackend {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, key: None }
+    }
+
+    pub fn with_key(mut self, key: StoreKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+impl SnippetBackend for YamlBackend {
+    fn kind(&self) -> BackendKind {
+        BackendKind::Yaml
+    }
+
+    fn exists(&self) -> bool {
+        self.path.is_file()
+    }
+
+    fn read(&self) -> Result<SnippetStore, StorageError> {
+        if !self.exists() {
+            return Ok(SnippetStore::default());
+        }
+
+        let data = read_file(&self.path, self.key.as_ref())?;
+        Ok(serde_yaml::from_slice(&data)?)
+    }
+
+    fn write(&self, store: &SnippetStore) -> Result<(), StorageError> {
+        let yaml = serde_yaml::to_string(store)?;
+        write_file(&self.path, yaml.as_bytes(), self.key.as_ref())
+    }
+
+    fn clear(&self) -> Result<(), StorageError> {
+        if self.exists() {
+            fs::remove_file(&self.path)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct DirBackend {
+    dir: PathBuf,
+}
+
+impl DirBackend {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn snippet_files(&self) -> Result<Vec<PathBuf>, StorageError> {
+        let mut files: Vec<PathBuf> = fs::read_dir(&self.dir)?
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                (path.extension()? == SNIPPET_EXTENSION).then_some(path)
+            })
+            .collect();
+        files.sort();
+        Ok(files)
+    }
+}
+
+impl SnippetBackend for DirBackend {
+    fn kind(&self) -> BackendKind {
+        BackendKind::Dir
+    }
+
+    fn exists(&self) -> bool {
+        self.dir.is_dir()
+    }
+
+    fn read(&self) -> Result<SnippetStore, StorageError> {
+        if !self.exists() {
+            return Ok(SnippetStore::default());
+        }
+
+        let mut snippets = Vec::new();
+        for path in self.snippet_files()? {
+            let raw = fs::read_to_string(&path)?;
+            let snippet = parse_snippet_file(&raw).map_err(|e| {
+                StorageError::Conflict(format!("Invalid snippet file '{}': {}", path.display(), e))
+            })?;
+            snippets.push(snippet);
+        }
+
+        snippets.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        Ok(SnippetStore { snippets })
+    }
+
+    fn write(&self, store: &SnippetStore) -> Result<(), StorageError> {
+        fs::create_dir_all(&self.dir)?;
+
+        let mut written = HashSet::new();
+        for snippet in &store.snippets {
+            let mut file_name = format!("{}.{}", slug(&snippet.name), SNIPPET_EXTENSION);
+            if written.contains(&file_name.to_lowercase()) {
+                file_name = format!(
+                    "{}-{}.{}",
+                    slug(&snippet.name),
+                    snippet.short_id(),
+                    SNIPPET_EXTENSION
+                );
+            }
+
+            let path = self.dir.join(&file_name);
+            let contents = render_snippet_file(snippet)?;
+            if fs::read(&path).ok().as_deref() != Some(contents.as_bytes()) {
+                write_atomic(&path, contents.as_bytes())?;
+            }
+            written.insert(file_name.to_lowercase());
+        }
+
+        for path in self.snippet_files()? {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            if !written.contains(&name) {
+                fs::remove_file(path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<(), StorageError> {
+        if self.exists() {
+            fs::remove_dir_all(&self.dir)?;
+        }
+        Ok(())
+    }
+}
+
+fn slug(name: &str) -> String {
+    let slug: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let slug = slug.trim_matches(['-', '.']);
+
+    if slug.is_empty() {
+        "snippet".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+fn render_snippet_file(snippet: &Snippet) -> Result<String, StorageError> {
+    let mut meta = match serde_yaml::to_value(snippet)? {
+        Value::Mapping(meta) => meta,
+        _ => Mapping::new(),
+    };
+    meta.shift_remove("content");
+
+    Ok(format!(
+        "{}{}{}{}",
+        FRONT_MATTER_DELIMITER,
+        serde_yaml::to_string(&meta)?,
+        FRONT_MATTER_DELIMITER,
+        snippet.content
+    ))
+}
+
+fn parse_snippet_file(raw: &str) -> Result<Snippet, String> {
+    let rest = raw
+        .strip_prefix(FRONT_MATTER_DELIMITER)
+        .ok_or("missing front matter")?;
+    let (meta, content) = rest
+        .split_once(&format!("\n{}", FRONT_MATTER_DELIMITER))
+        .ok_or("unterminated front matter")?;
+
+    let mut meta: Mapping = serde_yaml::from_str(meta).map_err(|e| e.to_string())?;
+    meta.insert("content".into(), content.into());
+    serde_yaml::from_value(Value::Mapping(meta)).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{PartialSnippet, SnippetKind, Variable};
+    use chrono::{Duration, Utc};
+
+    fn sample_snippet(name: &str, content: &str, age_minutes: i64) -> Snippet {
+        let created_at = Utc::now() - Duration::minutes(age_minutes);
+        Snippet {
+            id: Snippet::generate_id(),
+            name: name.into(),
+            description: "multi\nline".into(),
+            content: content.into(),
+            kind: SnippetKind::Bash,
+            tags: vec!["ops".into()],
+            variables: vec![Variable {
+                name: "ns".into(),
+                command: "kubectl get ns".into(),
+            }],
+            database: None,
+            created_at,
+            updated_at: created_at,
+        }
+    }
+
+    fn assert_same(a: &SnippetStore, b: &SnippetStore) {
+        assert_eq!(a.snippets.len(), b.snippets.len());
+        for (x, y) in a.snippets.iter().zip(&b.snippets) {
+            assert_eq!(x.id, y.id);
+            assert_eq!(PartialSnippet::from(x), PartialSnippet::from(y));
+            assert_eq!(x.created_at, y.created_at);
+            assert_eq!(x.updated_at, y.updated_at);
+        }
+    }
+
+    #[test]
+    fn test_dir_backend_round_trips_losslessly() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = DirBackend::new(dir.path().join(SNIPPETS_DIR));
+        let store = SnippetStore {
+            snippets: vec![
+                sample_snippet("deploy", "kubectl apply -f .\n---\nfoo", 3),
+                sample_snippet("no newline", "echo hi", 2),
+                sample_snippet("trailing", "echo hi\n\n", 1),
+            ],
+        };
+
+        backend.write(&store).unwrap();
+        assert_same(&store, &backend.read().unwrap());
+        assert!(dir.path().join("snippets/no-newline.md").is_file());
+    }
+
+    #[test]
+    fn test_dir_backend_removes_and_disambiguates_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = DirBackend::new(dir.path().join(SNIPPETS_DIR));
+        let a = sample_snippet("a/b", "one", 2);
+        let b = sample_snippet("a b", "two", 1);
+
+        backend
+            .write(&SnippetStore {
+                snippets: vec![a.clone(), b.clone()],
+            })
+            .unwrap();
+        assert!(dir.path().join("snippets/a-b.md").is_file());
+        assert!(
+            dir.path()
+                .join(format!("snippets/a-b-{}.md", b.short_id()))
+                .is_file()
+        );
+
+        backend
+            .write(&SnippetStore {
+                snippets: vec![b.clone()],
+            })
+            .unwrap();
+        let files = backend.snippet_files().unwrap();
+        assert_eq!(files, vec![dir.path().join("snippets/a-b.md")]);
+        assert_eq!(backend.read().unwrap().snippets[0].id, b.id);
+    }
+
+    #[test]
+    fn test_parse_rejects_missing_front_matter() {
+        assert!(parse_snippet_file("echo hi").is_err());
+        assert!(parse_snippet_file("---\nname: x\n").is_err());
+    }
+
+    #[test]
+    fn test_detect_backend() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(detect_backend(dir.path()), None);
+
+        fs::create_dir(dir.path().join(SNIPPETS_DIR)).unwrap();
+        assert_eq!(detect_backend(dir.path()), Some(BackendKind::Dir));
+
+        fs::write(dir.path().join(YAML_FILE), "snippets: []\n").unwrap();
+        assert_eq!(detect_backend(dir.path()), Some(BackendKind::Yaml));
+    }
+
+    #[test]
+    fn test_store_dir_of() {
+        assert_eq!(
+            store_dir_of(Path::new("bookmarks.yml")),
+            Some(Path::new(""))
+        );
+        assert_eq!(
+            store_dir_of(Path::new("stores/work/snippets/deploy.md")),
+            Some(Path::new("stores/work"))
+        );
+        assert_eq!(
+            store_dir_of(Path::new("stores/work/snippets.db")),
+            Some(Path::new("stores/work"))
+        );
+        assert_eq!(store_dir_of(Path::new("trash.yml")), None);
+    }
+
+    #[test]
+    fn test_backend_kind_parsing() {
+        assert_eq!("DIR".parse::<BackendKind>(), Ok(BackendKind::Dir));
+        assert!("xml".parse::<BackendKind>().is_err());
+    }
+}
@@ -0,0 +1,211 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use argon2::Argon2;
+use chacha20poly1305::{
+    XChaCha20Poly1305, XNonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng, rand_core::RngCore},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::storage::{StorageError, atomic::write_atomic};
+
+pub const KEY_FILE: &str = "encryption.yml";
+const MAGIC: &[u8] = b"markit-encrypted-v1\n";
+const NONCE_LEN: usize = 24;
+const SALT_LEN: usize = 16;
+const VERIFIER: &[u8] = b"markit";
+
+#[derive(Serialize, Deserialize)]
+struct KeyFile {
+    kdf: String,
+    salt: String,
+    verifier: String,
+}
+
+#[derive(Clone)]
+pub struct StoreKey {
+    cipher: XChaCha20Poly1305,
+}
+
+impl StoreKey {
+    fn derive(passphrase: &str, salt: &[u8]) -> Result<Self, StorageError> {
+        let mut key = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .map_err(|e| StorageError::Conflict(format!("Key derivation failed: {}", e)))?;
+        Ok(Self {
+            cipher: XChaCha20Poly1305::new(&key.into()),
+        })
+    }
+
+    pub fn create(data_dir: &Path, passphrase: &str) -> Result<Self, StorageError> {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+
+        let key = Self::derive(passphrase, &salt)?;
+        let key_file = KeyFile {
+            kdf: "argon2id".to_string(),
+            salt: to_hex(&salt),
+            verifier: to_hex(&key.seal(VERIFIER)),
+        };
+        write_atomic(
+            &key_path(data_dir),
+            serde_yaml::to_string(&key_file)?.as_bytes(),
+        )?;
+        Ok(key)
+    }
+
+    pub fn unlock(data_dir: &Path, passphrase: &str) -> Result<Self, StorageError> {
+        let key_file: KeyFile = serde_yaml::from_slice(&fs::read(key_path(data_dir))?)?;
+        let invalid = || StorageError::Conflict(format!("Invalid {}.", KEY_FILE));
+
+        let salt = from_hex(&key_file.salt).ok_or_else(invalid)?;
+        let verifier = from_hex(&key_file.verifier).ok_or_else(invalid)?;
+
+        let key = Self::derive(passphrase, &salt)?;
+        match key.open(&verifier) {
+            Ok(plain) if plain == VERIFIER => Ok(key),
+            _ => Err(StorageError::Conflict("Wrong passphrase.".to_string())),
+        }
+    }
+
+    pub fn seal(&self, plain: &[u8]) -> Vec<u8> {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plain)
+            .expect("encrypting an in-memory buffer cannot fail");
+
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    pub fn open(&self, data: &[u8]) -> Result<Vec<u8>, StorageError> {
+        let Some(sealed) = data.strip_prefix(MAGIC) else {
+            return Err(StorageError::Conflict(
+                "File is not encrypted although the store is; refusing to trust it.".to_string(),
+            ));
+        };
+        if sealed.len() < NONCE_LEN {
+            return Err(StorageError::Conflict(
+                "Encrypted file is truncated.".to_string(),
+            ));
+        }
+
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        self.cipher
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                StorageError::Conflict(
+                    "Could not decrypt the file; it was modified or uses another key.".to_string(),
+                )
+            })
+    }
+}
+
+pub fn key_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(KEY_FILE)
+}
+
+pub fn is_encrypted(data_dir: &Path) -> bool {
+    key_path(data_dir).is_file()
+}
+
+pub fn is_sealed(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+pub fn read_file(path: &Path, key: Option<&StoreKey>) -> Result<Vec<u8>, StorageError> {
+    let data = fs::read(path)?;
+    match key {
+        Some(_) if !is_sealed(&data) => Err(StorageError::Conflict(format!(
+            "'{}' is not encrypted although the store is; refusing to trust it.",
+            path.display()
+        ))),
+        Some(key) => key.open(&data),
+        None if is_sealed(&data) => Err(StorageError::Conflict(format!(
+            "'{}' is encrypted; unlock the store first.",
+            path.display()
+        ))),
+        None => Ok(data),
+    }
+}
+
+pub fn write_file(path: &Path, data: &[u8], key: Option<&StoreKey>) -> Result<(), StorageError> {
+    match key {
+        Some(key) => write_atomic(path, &key.seal(data))?,
+        None => write_atomic(path, data)?,
+    }
+    Ok(())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = StoreKey::create(dir.path(), "hunter2").unwrap();
+
+        let sealed = key.seal(b"snippets: []\n");
+        assert!(is_sealed(&sealed));
+        assert_ne!(sealed, key.seal(b"snippets: []\n"));
+        assert_eq!(key.open(&sealed).unwrap(), b"snippets: []\n");
+        assert!(key.open(b"plain").is_err());
+
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(key.open(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_unlock_checks_passphrase() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = StoreKey::create(dir.path(), "hunter2").unwrap();
+        assert!(is_encrypted(dir.path()));
+
+        let sealed = key.seal(b"secret");
+        let unlocked = StoreKey::unlock(dir.path(), "hunter2").unwrap();
+        assert_eq!(unlocked.open(&sealed).unwrap(), b"secret");
+
+        assert!(StoreKey::unlock(dir.path(), "wrong").is_err());
+    }
+
+    #[test]
+    fn test_read_file_requires_key_for_sealed_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = StoreKey::create(dir.path(), "hunter2").unwrap();
+        let path = dir.path().join("bookmarks.yml");
+
+        write_file(&path, b"snippets: []\n", Some(&key)).unwrap();
+        assert!(read_file(&path, None).is_err());
+        assert_eq!(read_file(&path, Some(&key)).unwrap(), b"snippets: []\n");
+
+        write_file(&path, b"snippets: []\n", None).unwrap();
+        assert!(read_file(&path, Some(&key)).is_err());
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        assert_eq!(from_hex(&to_hex(&[0, 15, 255])), Some(vec![0, 15, 255]));
+        assert_eq!(from_hex("abc"), None);
+        assert_eq!(from_hex("zz"), None);
+    }
+}
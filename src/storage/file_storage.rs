@@ -1,69 +1,408 @@
 use std::{
-    fs::{self, File},
-    io::Write,
+    cell::RefCell,
+    fs,
     path::{Path, PathBuf},
 };
 
 use chrono::Utc;
 
 use crate::{
-    models::{Snippet, SnippetStore},
-    storage::{Storage, StorageError},
+    config::BackupConfig,
+    models::{PartialSnippet, Revision, Snippet, SnippetStore, TrashEntry},
+    storage::{
+        BackupArchive, BackupRetention, Journal, SnippetHistory, Storage, StorageError,
+        StoreEncryption, Trash,
+        backend::{
+            BackendKind, SnippetBackend, detect_backend, open_backend, open_sealed_backend,
+            yaml_path,
+        },
+        crypto::{self, StoreKey, read_file, write_file},
+        ensure_unique_name,
+        git::{GitRepo, commit_message},
+        journal::{JournalEntry, JournalFile, apply_changes, changes_between, store_hash},
+        lock::{LockGuard, StoreLock},
+        retention::{BACKUP_TIMESTAMP_FORMAT, BackupFile, backup_timestamp, plan_prune},
+    },
 };
 
 pub struct FileStorage {
-    base_path: PathBuf,
+    data_dir: PathBuf,
+    state_dir: PathBuf,
+    backend: Box<dyn SnippetBackend>,
+    lock: StoreLock,
+    retention: BackupConfig,
+    operation: RefCell<Option<String>>,
+    git: Option<GitRepo>,
+    key: Option<StoreKey>,
 }
 
 impl FileStorage {
-    pub fn new() -> Self {
-        let base_path = dirs::home_dir()
-            .unwrap_or_else(|| {
-                eprintln!(
-                    "⚠️ Could not determine home directory, defaulting to current directory."
-                );
-                PathBuf::from(".")
-            })
-            .join(".markit");
+    pub fn with_dirs(data_dir: PathBuf, state_dir: PathBuf) -> Self {
+        for dir in [&data_dir, &state_dir] {
+            if let Err(e) = fs::create_dir_all(dir) {
+                eprintln!("⛔ Failed to create directory '{}': {}", dir.display(), e);
+            }
+        }
 
-        if let Err(e) = fs::create_dir_all(&base_path) {
-            eprintln!("⛔ Failed to create base directory: {}", e);
+        Self {
+            lock: StoreLock::new(data_dir.join(".lock")),
+            backend: open_backend(detect_backend(&data_dir).unwrap_or_default(), &data_dir),
+            data_dir,
+            state_dir,
+            retention: BackupConfig::default(),
+            operation: RefCell::new(None),
+            git: None,
+            key: None,
         }
+    }
+
+    pub fn set_operation(&self, operation: impl Into<String>) {
+        self.operation.replace(Some(operation.into()));
+    }
+
+    pub fn with_backend(mut self, preferred: BackendKind) -> Self {
+        let kind = detect_backend(&self.data_dir).unwrap_or(preferred);
+        self.backend = open_backend(kind, &self.data_dir);
+        self
+    }
+
+    pub fn backend_kind(&self) -> BackendKind {
+        self.backend.kind()
+    }
+
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    pub fn with_retention(mut self, retention: BackupConfig) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    pub fn with_git(mut self, git: GitRepo) -> Self {
+        self.git = Some(git);
+        self
+    }
 
-        Self { base_path }
+    pub fn with_key(mut self, key: StoreKey) -> Self {
+        self.backend = open_sealed_backend(self.backend.kind(), &self.data_dir, Some(&key));
+        self.git = self
+            .git
+            .map(|git| git.with_key(&self.data_dir, key.clone()));
+        self.key = Some(key);
+        self
     }
 
-    fn storage_path(&self) -> PathBuf {
-        self.base_path.join("bookmarks.yml")
+    pub fn git(&self) -> Option<&GitRepo> {
+        self.git.as_ref()
+    }
+
+    pub fn lock(&self) -> Result<LockGuard<'_>, StorageError> {
+        Ok(self.lock.acquire()?)
     }
 
     fn backup_dir(&self) -> PathBuf {
-        self.base_path.join("backups")
+        self.state_dir.join("backups")
+    }
+
+    fn journal_path(&self) -> PathBuf {
+        self.state_dir.join("journal.yml")
+    }
+
+    fn trash_path(&self) -> PathBuf {
+        self.data_dir.join("trash.yml")
+    }
+
+    fn history_dir(&self) -> PathBuf {
+        self.state_dir.join("history")
+    }
+
+    fn history_path(&self, id: &str) -> Result<PathBuf, StorageError> {
+        if id.is_empty() || id.contains(['/', '\\']) || id.contains("..") {
+            return Err(StorageError::Conflict(format!(
+                "Invalid snippet id '{}'.",
+                id
+            )));
+        }
+        Ok(self.history_dir().join(format!("{}.yml", id)))
     }
 
     fn load_store(&self) -> Result<SnippetStore, StorageError> {
-        let path = self.storage_path();
+        let mut store = self.backend.read()?;
+        if store.backfill_ids() {
+            let _lock = self.lock()?;
+            store = self.backend.read()?;
+            if store.backfill_ids() {
+                self.write_store(&store)?;
+            }
+        }
+        Ok(store)
+    }
+
+    fn write_store(&self, store: &SnippetStore) -> Result<(), StorageError> {
+        self.backend.write(store)
+    }
+
+    fn load_history(&self, id: &str) -> Result<Vec<Revision>, StorageError> {
+        let path = self.history_path(id)?;
         if !path.exists() {
-            return Ok(SnippetStore::default());
+            return Ok(vec![]);
         }
 
-        let file = File::open(&path)?;
-        let store = serde_yaml::from_reader(file)?;
-        Ok(store)
+        Ok(serde_yaml::from_slice(&self.read_sealed(&path)?)?)
+    }
+
+    fn write_history(&self, id: &str, revisions: &[Revision]) -> Result<(), StorageError> {
+        fs::create_dir_all(self.history_dir())?;
+        let yaml = serde_yaml::to_string(revisions)?;
+        self.write_sealed(&self.history_path(id)?, yaml.as_bytes())
+    }
+
+    fn load_journal(&self) -> Result<JournalFile, StorageError> {
+        let path = self.journal_path();
+        if !path.exists() {
+            return Ok(JournalFile::default());
+        }
+
+        Ok(serde_yaml::from_slice(&self.read_sealed(&path)?)?)
+    }
+
+    fn write_journal(&self, journal: &JournalFile) -> Result<(), StorageError> {
+        let yaml = serde_yaml::to_string(journal)?;
+        self.write_sealed(&self.journal_path(), yaml.as_bytes())
+    }
+
+    fn read_sealed(&self, path: &Path) -> Result<Vec<u8>, StorageError> {
+        read_file(path, self.key.as_ref())
+    }
+
+    fn write_sealed(&self, path: &Path, data: &[u8]) -> Result<(), StorageError> {
+        write_file(path, data, self.key.as_ref())
+    }
+
+    fn sealed_files(&self) -> Vec<PathBuf> {
+        let mut files = vec![
+            yaml_path(&self.data_dir),
+            self.trash_path(),
+            self.journal_path(),
+        ];
+        for dir in [self.backup_dir(), self.history_dir()] {
+            if let Ok(entries) = fs::read_dir(dir) {
+                files.extend(entries.filter_map(|e| Some(e.ok()?.path())));
+            }
+        }
+        files.retain(|path| path.extension().is_some_and(|e| e == "yml") && path.is_file());
+        files
+    }
+
+    fn commit_files(&self, message: &str) {
+        if let Some(git) = &self.git
+            && let Err(e) = git.commit_all(message)
+        {
+            eprintln!("⚠️ Failed to commit snippets to git: {}", e);
+        }
+    }
+
+    fn reseal_files(&self, key: Option<&StoreKey>) -> Result<(), StorageError> {
+        for path in self.sealed_files() {
+            let data = self.read_sealed(&path)?;
+            write_file(&path, &data, key)?;
+        }
+        Ok(())
+    }
+
+    fn operation(&self) -> String {
+        self.operation
+            .borrow()
+            .clone()
+            .unwrap_or_else(|| "change".to_string())
+    }
+
+    fn commit(&self, operation: &str, before: &SnippetStore, after: &SnippetStore) {
+        if self.git.is_none() {
+            return;
+        }
+
+        let changes = changes_between(before, after);
+        if !changes.is_empty() {
+            self.commit_files(&commit_message(operation, &changes));
+        }
+    }
+
+    fn record_journal(
+        &self,
+        before: &SnippetStore,
+        after: &SnippetStore,
+    ) -> Result<(), StorageError> {
+        let changes = changes_between(before, after);
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        let mut journal = self.load_journal()?;
+        journal.record(JournalEntry {
+            operation: self.operation(),
+            recorded_at: Utc::now(),
+            changes,
+            before_hash: store_hash(before),
+            after_hash: store_hash(after),
+        });
+        self.write_journal(&journal)
+    }
+
+    fn replay(&self, undo: bool) -> Result<Option<JournalEntry>, StorageError> {
+        let _lock = self.lock()?;
+        let mut journal = self.load_journal()?;
+
+        let entry = if undo {
+            journal.undo.pop()
+        } else {
+            journal.redo.pop()
+        };
+        let Some(entry) = entry else {
+            return Ok(None);
+        };
+
+        let current = self.load_store()?;
+        let expected = if undo {
+            &entry.after_hash
+        } else {
+            &entry.before_hash
+        };
+        if &store_hash(&current) != expected {
+            return Err(StorageError::Conflict(format!(
+                "Snippets changed outside of markit since '{}', refusing to {}.",
+                entry.operation,
+                if undo { "undo" } else { "redo" }
+            )));
+        }
+
+        let mut store = current.clone();
+        apply_changes(&mut store, &entry.changes, undo);
+
+        self.backup_before_write(&current)?;
+        self.write_store(&store)?;
+        self.reconcile_trash(&store)?;
+        self.record_revisions(&current, &store)?;
+        self.commit(
+            &format!("{} {}", if undo { "undo" } else { "redo" }, entry.operation),
+            &current,
+            &store,
+        );
+
+        if undo {
+            journal.redo.push(entry.clone());
+        } else {
+            journal.undo.push(entry.clone());
+        }
+        self.write_journal(&journal)?;
+
+        Ok(Some(entry))
+    }
+
+    fn reconcile_trash(&self, store: &SnippetStore) -> Result<(), StorageError> {
+        let entries = self.load_trash()?;
+        let remaining: Vec<TrashEntry> = entries
+            .iter()
+            .filter(|e| !store.snippets.iter().any(|s| s.id == e.snippet.id))
+            .cloned()
+            .collect();
+
+        if remaining.len() != entries.len() {
+            self.save_trash(&remaining)?;
+        }
+        Ok(())
+    }
+
+    fn record_revisions(
+        &self,
+        before: &SnippetStore,
+        after: &SnippetStore,
+    ) -> Result<(), StorageError> {
+        for snippet in &after.snippets {
+            let current = PartialSnippet::from(snippet);
+            let previous = before.snippets.iter().find(|s| s.id == snippet.id);
+
+            if previous.is_some_and(|p| PartialSnippet::from(p) == current) {
+                continue;
+            }
+
+            let mut revisions = self.load_history(&snippet.id)?;
+
+            if revisions.is_empty()
+                && let Some(previous) = previous
+            {
+                revisions.push(Revision {
+                    rev: 1,
+                    recorded_at: previous.updated_at,
+                    snippet: PartialSnippet::from(previous),
+                });
+            }
+
+            if revisions.last().is_some_and(|r| r.snippet == current) {
+                continue;
+            }
+
+            revisions.push(Revision {
+                rev: revisions.last().map_or(1, |r| r.rev + 1),
+                recorded_at: snippet.updated_at,
+                snippet: current,
+            });
+            self.write_history(&snippet.id, &revisions)?;
+        }
+
+        Ok(())
+    }
+
+    fn backup_before_write(&self, store: &SnippetStore) -> Result<(), StorageError> {
+        if self.backend.is_transactional() || self.git.is_some() {
+            return Ok(());
+        }
+        self.backup_current_store(store)
     }
 
     fn backup_current_store(&self, store: &SnippetStore) -> Result<(), StorageError> {
         let backup_dir = self.backup_dir();
         fs::create_dir_all(&backup_dir).map_err(StorageError::Io)?;
 
-        let timestamp = Utc::now().format("%Y-%m-%dT%H-%M-%SZ").to_string();
-        let backup_file = backup_dir.join(format!("{}.yml", timestamp));
-        let mut file = File::create(backup_file).map_err(StorageError::Io)?;
         let yaml = serde_yaml::to_string(store).map_err(StorageError::Serde)?;
-        file.write_all(yaml.as_bytes()).map_err(StorageError::Io)?;
+        if let Some(newest) = self.get_backups()?.first()
+            && self.read_sealed(newest).is_ok_and(|b| b == yaml.as_bytes())
+        {
+            return Ok(());
+        }
+
+        let timestamp = Utc::now().format(BACKUP_TIMESTAMP_FORMAT).to_string();
+        let backup_file = backup_dir.join(format!("{}.yml", timestamp));
+        self.write_sealed(&backup_file, yaml.as_bytes())?;
+
+        if let Err(e) = self.prune_backups(false) {
+            eprintln!("⚠️ Failed to prune old backups: {}", e);
+        }
 
         Ok(())
     }
+
+    fn backup_files(&self) -> Result<Vec<BackupFile>, StorageError> {
+        let backups = match self.get_backups() {
+            Ok(b) => b,
+            Err(StorageError::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e),
+        };
+
+        Ok(backups
+            .into_iter()
+            .filter_map(|path| {
+                let created_at = backup_timestamp(&path)?;
+                let size = fs::metadata(&path).ok()?.len();
+                Some(BackupFile {
+                    path,
+                    created_at,
+                    size,
+                })
+            })
+            .collect())
+    }
 }
 
 impl Storage for FileStorage {
@@ -71,25 +410,39 @@ impl Storage for FileStorage {
         self.load_store()
     }
 
+    fn find_by_name(&self, name: &str) -> Result<Option<Snippet>, StorageError> {
+        self.backend.find_by_name(name)
+    }
+
+    fn find_by_tag(&self, tag: &str) -> Result<Vec<Snippet>, StorageError> {
+        self.backend.find_by_tag(tag)
+    }
+
     fn save(&self, snippet: Snippet) -> Result<(), StorageError> {
+        let _lock = self.lock()?;
         let mut store = self.load_store()?;
-        self.backup_current_store(&store)?;
+        ensure_unique_name(&store, &snippet.name)?;
+        self.backup_before_write(&store)?;
 
+        let before = store.clone();
         store.snippets.push(snippet);
-
-        let file = File::create(self.storage_path()).map_err(StorageError::Io)?;
-        serde_yaml::to_writer(file, &store).map_err(StorageError::Serde)?;
+        self.write_store(&store)?;
+        self.record_revisions(&before, &store)?;
+        self.record_journal(&before, &store)?;
+        self.commit(&self.operation(), &before, &store);
 
         println!("✅ Snippet saved.");
         Ok(())
     }
 
     fn save_all(&self, store: &SnippetStore) -> Result<(), StorageError> {
-        self.backup_current_store(&self.load_store()?)?;
-
-        let file = File::create(self.storage_path()).map_err(StorageError::Io)?;
-        serde_yaml::to_writer(file, store).map_err(StorageError::Serde)?;
-
+        let _lock = self.lock()?;
+        let before = self.load_store()?;
+        self.backup_before_write(&before)?;
+        self.write_store(store)?;
+        self.record_revisions(&before, store)?;
+        self.record_journal(&before, store)?;
+        self.commit(&self.operation(), &before, store);
         Ok(())
     }
 
@@ -113,10 +466,494 @@ impl Storage for FileStorage {
     }
 
     fn restore_backup(&self, path: &Path) -> Result<(), StorageError> {
-        fs::copy(path, self.storage_path())
-            .map_err(StorageError::Io)
-            .map(|_| {
-                println!("✅ Backup restored from '{}'", path.display());
+        let _lock = self.lock()?;
+        let contents = self.read_sealed(path)?;
+        let mut restored: SnippetStore = serde_yaml::from_slice(&contents)?;
+
+        let current = self.load_store()?;
+        restored.backfill_ids();
+        self.backup_current_store(&current)?;
+        self.write_store(&restored)?;
+        self.record_journal(&current, &restored)?;
+        self.commit(&self.operation(), &current, &restored);
+
+        println!("✅ Backup restored from '{}'", path.display());
+        Ok(())
+    }
+}
+
+impl BackupArchive for FileStorage {
+    fn load_backup(&self, path: &Path) -> Result<SnippetStore, StorageError> {
+        Ok(serde_yaml::from_slice(&self.read_sealed(path)?)?)
+    }
+}
+
+impl BackupRetention for FileStorage {
+    fn prune_backups(&self, dry_run: bool) -> Result<Vec<BackupFile>, StorageError> {
+        let pruned = plan_prune(&self.backup_files()?, &self.retention, Utc::now());
+
+        if !dry_run {
+            for backup in &pruned {
+                fs::remove_file(&backup.path)?;
+            }
+        }
+
+        Ok(pruned)
+    }
+}
+
+impl Trash for FileStorage {
+    fn load_trash(&self) -> Result<Vec<TrashEntry>, StorageError> {
+        let path = self.trash_path();
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+
+        Ok(serde_yaml::from_slice(&self.read_sealed(&path)?)?)
+    }
+
+    fn save_trash(&self, entries: &[TrashEntry]) -> Result<(), StorageError> {
+        let _lock = self.lock()?;
+        let yaml = serde_yaml::to_string(entries)?;
+        self.write_sealed(&self.trash_path(), yaml.as_bytes())
+    }
+}
+
+impl StoreEncryption for FileStorage {
+    fn is_encrypted(&self) -> bool {
+        crypto::is_encrypted(&self.data_dir)
+    }
+
+    fn check_passphrase(&self, passphrase: &str) -> Result<(), StorageError> {
+        StoreKey::unlock(&self.data_dir, passphrase).map(|_| ())
+    }
+
+    fn encrypt(&self, passphrase: &str) -> Result<(), StorageError> {
+        let _lock = self.lock()?;
+        if self.is_encrypted() {
+            return Err(StorageError::Conflict(
+                "Store is already encrypted.".to_string(),
+            ));
+        }
+        if self.backend.kind() != BackendKind::Yaml {
+            return Err(StorageError::Conflict(format!(
+                "Only the yaml backend can be encrypted; run `markit migrate --to yaml` first (store uses '{}').",
+                self.backend.kind()
+            )));
+        }
+
+        fs::create_dir_all(&self.data_dir)?;
+        let key = StoreKey::create(&self.data_dir, passphrase)?;
+        self.reseal_files(Some(&key))?;
+        self.commit_files("encrypt store");
+        Ok(())
+    }
+
+    fn decrypt(&self) -> Result<(), StorageError> {
+        let _lock = self.lock()?;
+        if self.key.is_none() {
+            return Err(StorageError::Conflict(
+                "Unlock the store before decrypting it.".to_string(),
+            ));
+        }
+
+        self.reseal_files(None)?;
+        fs::remove_file(crypto::key_path(&self.data_dir))?;
+        self.commit_files("decrypt store");
+        Ok(())
+    }
+}
+
+impl Journal for FileStorage {
+    fn undo(&self) -> Result<Option<JournalEntry>, StorageError> {
+        self.replay(true)
+    }
+
+    fn redo(&self) -> Result<Option<JournalEntry>, StorageError> {
+        self.replay(false)
+    }
+}
+
+impl SnippetHistory for FileStorage {
+    fn get_history(&self, id: &str) -> Result<Vec<Revision>, StorageError> {
+        self.load_history(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SnippetKind;
+
+    fn storage() -> (tempfile::TempDir, FileStorage) {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::with_dirs(dir.path().join("data"), dir.path().join("state"));
+        (dir, storage)
+    }
+
+    fn sample_snippet(name: &str) -> Snippet {
+        Snippet {
+            id: Snippet::generate_id(),
+            name: name.into(),
+            description: "desc".into(),
+            content: "echo one".into(),
+            kind: SnippetKind::Shell,
+            tags: vec![],
+            variables: vec![],
+            database: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_save_rejects_duplicate_names_under_the_lock() {
+        let (_dir, storage) = storage();
+        storage.save(sample_snippet("deploy")).unwrap();
+
+        assert!(storage.save(sample_snippet("Deploy")).is_err());
+        assert_eq!(storage.load().unwrap().snippets.len(), 1);
+    }
+
+    #[test]
+    fn test_history_rejects_path_ids() {
+        let (dir, storage) = storage();
+        let mut snippet = sample_snippet("a");
+        snippet.id = "../../escaped".into();
+
+        assert!(storage.save(snippet).is_err());
+        assert!(storage.get_history("../x").is_err());
+        assert!(!dir.path().join("escaped.yml").exists());
+    }
+
+    #[test]
+    fn test_save_records_initial_revision() {
+        let (_dir, storage) = storage();
+        let snippet = sample_snippet("a");
+
+        storage.save(snippet.clone()).unwrap();
+
+        let history = storage.get_history(&snippet.id).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].rev, 1);
+        assert_eq!(history[0].snippet.content, "echo one");
+    }
+
+    #[test]
+    fn test_save_all_records_only_changed_snippets() {
+        let (_dir, storage) = storage();
+        let a = sample_snippet("a");
+        let b = sample_snippet("b");
+        storage.save(a.clone()).unwrap();
+        storage.save(b.clone()).unwrap();
+
+        let mut store = storage.load().unwrap();
+        store.snippets[0].content = "echo two".into();
+        storage.save_all(&store).unwrap();
+        storage.save_all(&store).unwrap();
+
+        let history_a = storage.get_history(&a.id).unwrap();
+        assert_eq!(history_a.len(), 2);
+        assert_eq!(history_a[1].rev, 2);
+        assert_eq!(history_a[1].snippet.content, "echo two");
+        assert_eq!(storage.get_history(&b.id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_first_change_without_history_records_previous_state() {
+        let (_dir, storage) = storage();
+        let snippet = sample_snippet("a");
+        storage
+            .write_store(&SnippetStore {
+                snippets: vec![snippet.clone()],
             })
+            .unwrap();
+
+        let mut store = storage.load().unwrap();
+        store.snippets[0].description = "changed".into();
+        storage.save_all(&store).unwrap();
+
+        let history = storage.get_history(&snippet.id).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].snippet.description, "desc");
+        assert_eq!(history[1].snippet.description, "changed");
+    }
+
+    #[test]
+    fn test_concurrent_saves_are_not_lost() {
+        let (dir, _storage) = storage();
+
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let path = dir.path().to_path_buf();
+                std::thread::spawn(move || {
+                    let storage = FileStorage::with_dirs(path.join("data"), path.join("state"));
+                    for i in 0..5 {
+                        storage
+                            .save(sample_snippet(&format!("s-{}-{}", t, i)))
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let store = FileStorage::with_dirs(dir.path().join("data"), dir.path().join("state"))
+            .load()
+            .unwrap();
+        assert_eq!(store.snippets.len(), 20);
+    }
+
+    #[test]
+    fn test_identical_backup_is_skipped() {
+        let (dir, storage) = storage();
+        let store = SnippetStore {
+            snippets: vec![sample_snippet("a")],
+        };
+
+        storage.backup_current_store(&store).unwrap();
+        let first = storage.get_backups().unwrap();
+        fs::rename(
+            &first[0],
+            dir.path().join("state/backups/2000-01-01T00-00-00Z.yml"),
+        )
+        .unwrap();
+
+        storage.backup_current_store(&store).unwrap();
+        assert_eq!(storage.get_backups().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_prune_backups_applies_retention() {
+        let (dir, storage) = storage();
+        let storage = storage.with_retention(BackupConfig {
+            keep_last: 2,
+            keep_daily_days: 0,
+            max_total_size: None,
+        });
+        let backup_dir = dir.path().join("state/backups");
+        fs::create_dir_all(&backup_dir).unwrap();
+        for day in 1..=4 {
+            fs::write(
+                backup_dir.join(format!("2024-01-0{}T00-00-00Z.yml", day)),
+                "snippets: []\n",
+            )
+            .unwrap();
+        }
+
+        let planned = storage.prune_backups(true).unwrap();
+        assert_eq!(planned.len(), 2);
+        assert_eq!(storage.get_backups().unwrap().len(), 4);
+
+        let pruned = storage.prune_backups(false).unwrap();
+        assert_eq!(pruned, planned);
+        let remaining = storage.get_backups().unwrap();
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining[1].ends_with("2024-01-03T00-00-00Z.yml"));
+    }
+
+    #[test]
+    fn test_restore_takes_safety_backup_of_current_store() {
+        let (dir, storage) = storage();
+        let backup_dir = dir.path().join("state/backups");
+        fs::create_dir_all(&backup_dir).unwrap();
+        let old = backup_dir.join("2000-01-01T00-00-00Z.yml");
+        fs::write(&old, "snippets: []\n").unwrap();
+
+        storage
+            .write_store(&SnippetStore {
+                snippets: vec![sample_snippet("current")],
+            })
+            .unwrap();
+        storage.restore_backup(&old).unwrap();
+
+        assert!(storage.load().unwrap().snippets.is_empty());
+        let backups = storage.get_backups().unwrap();
+        assert_eq!(backups.len(), 2);
+        let safety = storage.load_backup(&backups[0]).unwrap();
+        assert_eq!(safety.snippets[0].name, "current");
+    }
+
+    #[test]
+    fn test_trash_round_trip() {
+        let (_dir, storage) = storage();
+        assert!(storage.load_trash().unwrap().is_empty());
+
+        let entry = TrashEntry {
+            deleted_at: Utc::now(),
+            snippet: sample_snippet("gone"),
+        };
+        storage.save_trash(std::slice::from_ref(&entry)).unwrap();
+
+        let trash = storage.load_trash().unwrap();
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].snippet.id, entry.snippet.id);
+    }
+
+    #[test]
+    fn test_undo_and_redo_last_operation() {
+        let (_dir, storage) = storage();
+        let snippet = sample_snippet("a");
+        storage.set_operation("save");
+        storage.save(snippet.clone()).unwrap();
+
+        storage.set_operation("delete");
+        storage.save_all(&SnippetStore::default()).unwrap();
+
+        let undone = storage.undo().unwrap().unwrap();
+        assert_eq!(undone.operation, "delete");
+        assert_eq!(storage.load().unwrap().snippets[0].id, snippet.id);
+
+        let redone = storage.redo().unwrap().unwrap();
+        assert_eq!(redone.operation, "delete");
+        assert!(storage.load().unwrap().snippets.is_empty());
+
+        assert_eq!(storage.undo().unwrap().unwrap().operation, "delete");
+        assert_eq!(storage.undo().unwrap().unwrap().operation, "save");
+        assert!(storage.undo().unwrap().is_none());
+        assert!(storage.load().unwrap().snippets.is_empty());
+    }
+
+    #[test]
+    fn test_undo_delete_takes_snippet_out_of_the_trash() {
+        let (_dir, storage) = storage();
+        let (kept, deleted) = (sample_snippet("kept"), sample_snippet("deleted"));
+        storage.save(deleted.clone()).unwrap();
+
+        let trashed = |snippet: &Snippet| TrashEntry {
+            deleted_at: Utc::now(),
+            snippet: snippet.clone(),
+        };
+        storage
+            .save_trash(&[trashed(&kept), trashed(&deleted)])
+            .unwrap();
+        storage.set_operation("delete");
+        storage.save_all(&SnippetStore::default()).unwrap();
+
+        storage.undo().unwrap();
+        let trash = storage.load_trash().unwrap();
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].snippet.id, kept.id);
+    }
+
+    #[test]
+    fn test_undo_refuses_after_external_change() {
+        let (_dir, storage) = storage();
+        storage.save(sample_snippet("a")).unwrap();
+
+        let mut store = storage.load().unwrap();
+        store.snippets[0].content = "edited by hand".into();
+        storage.write_store(&store).unwrap();
+
+        assert!(matches!(storage.undo(), Err(StorageError::Conflict(_))));
+        assert_eq!(storage.load().unwrap().snippets.len(), 1);
+    }
+
+    #[test]
+    fn test_load_persists_backfilled_ids() {
+        let (dir, storage) = storage();
+        fs::write(
+            dir.path().join("data/bookmarks.yml"),
+            "snippets:\n- name: a\n  description: ''\n  content: ls\n  executable: true\n  tags: []\n",
+        )
+        .unwrap();
+
+        let first = storage.load().unwrap();
+        let second = storage.load().unwrap();
+
+        assert!(!first.snippets[0].id.is_empty());
+        assert_eq!(first.snippets[0].id, second.snippets[0].id);
+    }
+
+    #[test]
+    fn test_sqlite_backend_skips_copy_backups() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::with_dirs(dir.path().join("data"), dir.path().join("state"))
+            .with_backend(BackendKind::Sqlite);
+
+        storage.save(sample_snippet("a")).unwrap();
+        storage.save(sample_snippet("b")).unwrap();
+
+        assert!(dir.path().join("data/snippets.db").is_file());
+        assert!(
+            storage
+                .get_backups()
+                .is_err_and(|e| matches!(e, StorageError::Io(_)))
+        );
+        assert_eq!(storage.load().unwrap().snippets.len(), 2);
+        assert_eq!(storage.find_by_name("A").unwrap().unwrap().name, "a");
+
+        storage.undo().unwrap();
+        assert_eq!(storage.load().unwrap().snippets.len(), 1);
+    }
+
+    #[test]
+    fn test_encrypt_seals_store_and_state_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let (data, state) = (dir.path().join("data"), dir.path().join("state"));
+        let storage = FileStorage::with_dirs(data.clone(), state.clone());
+        let snippet = sample_snippet("a");
+        storage.save(snippet.clone()).unwrap();
+        storage.save(sample_snippet("b")).unwrap();
+
+        storage.encrypt("hunter2").unwrap();
+        assert!(storage.is_encrypted());
+        let sealed = storage.sealed_files();
+        assert!(sealed.len() >= 4);
+        for path in &sealed {
+            let raw = fs::read(path).unwrap();
+            assert!(crypto::is_sealed(&raw), "{} is not sealed", path.display());
+        }
+
+        let locked = FileStorage::with_dirs(data.clone(), state.clone());
+        assert!(locked.load().is_err());
+        assert!(locked.check_passphrase("wrong").is_err());
+
+        let key = StoreKey::unlock(&data, "hunter2").unwrap();
+        let unlocked = FileStorage::with_dirs(data.clone(), state.clone()).with_key(key);
+        assert_eq!(unlocked.load().unwrap().snippets.len(), 2);
+        assert_eq!(unlocked.get_history(&snippet.id).unwrap().len(), 1);
+        let backup = unlocked.get_backups().unwrap()[0].clone();
+        assert_eq!(unlocked.load_backup(&backup).unwrap().snippets.len(), 1);
+
+        unlocked.save(sample_snippet("c")).unwrap();
+        unlocked.undo().unwrap();
+        assert_eq!(unlocked.load().unwrap().snippets.len(), 2);
+        assert!(crypto::is_sealed(&fs::read(yaml_path(&data)).unwrap()));
+
+        unlocked.decrypt().unwrap();
+        assert!(!unlocked.is_encrypted());
+        let plain = FileStorage::with_dirs(data, state);
+        assert_eq!(plain.load().unwrap().snippets.len(), 2);
+        assert!(plain.load_backup(&backup).is_ok());
+    }
+
+    #[test]
+    fn test_encrypted_store_rejects_swapped_plaintext() {
+        let dir = tempfile::tempdir().unwrap();
+        let (data, state) = (dir.path().join("data"), dir.path().join("state"));
+        let storage = FileStorage::with_dirs(data.clone(), state.clone());
+        storage.save(sample_snippet("a")).unwrap();
+        storage.encrypt("hunter2").unwrap();
+
+        fs::write(yaml_path(&data), "snippets: []\n").unwrap();
+
+        let key = StoreKey::unlock(&data, "hunter2").unwrap();
+        let unlocked = FileStorage::with_dirs(data, state).with_key(key);
+        assert!(unlocked.load().is_err());
+    }
+
+    #[test]
+    fn test_encrypt_requires_yaml_backend() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::with_dirs(dir.path().join("data"), dir.path().join("state"))
+            .with_backend(BackendKind::Dir);
+        storage.save(sample_snippet("a")).unwrap();
+
+        assert!(storage.encrypt("hunter2").is_err());
+        assert!(!storage.is_encrypted());
     }
 }
@@ -2,6 +2,7 @@ use crate::models::{Snippet, SnippetStore};
 
 pub enum Filter {
     All,
+    Id(String),
     Name(String),
     Tag(String),
     FuzzySearch(String),
@@ -10,6 +11,7 @@ pub enum Filter {
 pub fn apply_filter(store: &SnippetStore, filter: Filter) -> Vec<Snippet> {
     match filter {
         Filter::All => store.snippets.clone(),
+        Filter::Id(id) => get_by_id(store, &id),
         Filter::Name(name) => get_by_name(store, &name),
         Filter::Tag(tag) => get_by_tag(store, &tag),
         Filter::FuzzySearch(query) => {
@@ -25,6 +27,24 @@ pub fn apply_filter(store: &SnippetStore, filter: Filter) -> Vec<Snippet> {
     }
 }
 
+fn get_by_id(store: &SnippetStore, id: &str) -> Vec<Snippet> {
+    let id = id.to_lowercase();
+    if let Some(exact) = store.snippets.iter().find(|s| s.id == id) {
+        return vec![exact.clone()];
+    }
+
+    if id.len() < 4 {
+        return vec![];
+    }
+
+    store
+        .snippets
+        .iter()
+        .filter(|s| s.id.starts_with(&id))
+        .cloned()
+        .collect()
+}
+
 fn get_by_name(store: &SnippetStore, name: &str) -> Vec<Snippet> {
     store
         .snippets
@@ -0,0 +1,768 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use git2::{
+    Commit, Cred, CredentialType, Delta, ErrorCode, FetchOptions, Index, IndexAddOption,
+    IndexEntry, IndexTime, ObjectType, Oid, PushOptions, RemoteCallbacks, Repository,
+    RepositoryInitOptions, Signature, Tree, build::CheckoutBuilder,
+};
+
+use crate::{
+    models::SnippetStore,
+    storage::{
+        SnippetSync, StorageError,
+        atomic::write_atomic,
+        backend::{BackendKind, detect_backend, open_sealed_backend, store_dir_of},
+        crypto::StoreKey,
+        journal::SnippetChange,
+        lock::StoreLock,
+        merge::{Side, merge_stores},
+    },
+};
+
+const REMOTE_NAME: &str = "origin";
+const GITIGNORE: &str = ".lock
+agent.sock
+vault/
+/agent/
+/backups/
+/history/
+/journal*
+/trash*
+/stores/*/backups/
+/stores/*/history/
+/stores/*/journal*
+/stores/*/trash*
+/trusted_projects.yml
+/project.lock
+";
+const STORES_DIR: &str = "stores";
+const REPO_LOCK: &str = "markit.lock";
+
+impl From<git2::Error> for StorageError {
+    fn from(e: git2::Error) -> Self {
+        StorageError::Conflict(format!("Git error: {}", e.message()))
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct SyncReport {
+    pub pulled: bool,
+    pub pushed: bool,
+    pub conflicts: Vec<String>,
+}
+
+pub struct GitRepo {
+    repo: Repository,
+    branch: String,
+    remote: Option<String>,
+    keys: HashMap<PathBuf, StoreKey>,
+    lock: StoreLock,
+}
+
+impl GitRepo {
+    pub fn open_or_init(dir: &Path, branch: &str) -> Result<Self, StorageError> {
+        let repo = match Repository::open(dir) {
+            Ok(repo) => repo,
+            Err(_) => {
+                fs::create_dir_all(dir)?;
+                let mut options = RepositoryInitOptions::new();
+                options.initial_head(branch);
+                Repository::init_opts(dir, &options)?
+            }
+        };
+
+        let ignore = dir.join(".gitignore");
+        let mut patterns = fs::read_to_string(&ignore).unwrap_or_default();
+        let missing: Vec<&str> = GITIGNORE
+            .lines()
+            .filter(|line| !patterns.lines().any(|p| p.trim() == *line))
+            .collect();
+        if !missing.is_empty() {
+            if !patterns.is_empty() && !patterns.ends_with('\n') {
+                patterns.push('\n');
+            }
+            for line in missing {
+                patterns.push_str(line);
+                patterns.push('\n');
+            }
+            write_atomic(&ignore, patterns.as_bytes())?;
+        }
+
+        Ok(Self {
+            lock: StoreLock::new(repo.path().join(REPO_LOCK)),
+            repo,
+            branch: branch.to_string(),
+            remote: None,
+            keys: HashMap::new(),
+        })
+    }
+
+    pub fn with_remote(mut self, remote: Option<String>) -> Self {
+        self.remote = remote;
+        self
+    }
+
+    pub fn with_key(mut self, store_dir: &Path, key: StoreKey) -> Self {
+        if let Some(dir) = self
+            .repo
+            .workdir()
+            .and_then(|root| store_dir.strip_prefix(root).ok())
+        {
+            self.keys.insert(dir.to_path_buf(), key);
+        }
+        self
+    }
+
+    pub fn commit_all(&self, message: &str) -> Result<Option<Oid>, StorageError> {
+        let _lock = self.lock.acquire()?;
+        let mut index = self.repo.index()?;
+        index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
+        index.update_all(["*"], None)?;
+        let repo = &self.repo;
+        index.remove_all(
+            ["*"],
+            Some(
+                &mut |path: &Path, _: &[u8]| match repo.is_path_ignored(path) {
+                    Ok(true) => 0,
+                    _ => 1,
+                },
+            ),
+        )?;
+        index.write()?;
+
+        let tree = self.repo.find_tree(index.write_tree()?)?;
+        let parent = self.head_commit()?;
+        if parent.as_ref().is_some_and(|p| p.tree_id() == tree.id()) {
+            return Ok(None);
+        }
+
+        let signature = self.signature()?;
+        let parents: Vec<&Commit> = parent.iter().collect();
+        Ok(Some(self.repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )?))
+    }
+
+    fn head_commit(&self) -> Result<Option<Commit<'_>>, StorageError> {
+        match self.repo.head() {
+            Ok(head) => Ok(Some(head.peel_to_commit()?)),
+            Err(e) if matches!(e.code(), ErrorCode::UnbornBranch | ErrorCode::NotFound) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn signature(&self) -> Result<Signature<'static>, StorageError> {
+        Ok(self
+            .repo
+            .signature()
+            .or_else(|_| Signature::now("markit", "markit@localhost"))?)
+    }
+
+    fn callbacks(&self) -> RemoteCallbacks<'_> {
+        let config = self.repo.config().ok();
+        let mut callbacks = RemoteCallbacks::new();
+        let mut tried = CredentialType::empty();
+        callbacks.credentials(move |url, username, allowed| {
+            let untried = allowed - tried;
+            if untried.contains(CredentialType::USERNAME) {
+                tried |= CredentialType::USERNAME;
+                return Cred::username("git");
+            }
+            if untried.contains(CredentialType::SSH_KEY) {
+                tried |= CredentialType::SSH_KEY;
+                return Cred::ssh_key_from_agent(username.unwrap_or("git"));
+            }
+            if untried.contains(CredentialType::USER_PASS_PLAINTEXT)
+                && let Some(config) = &config
+            {
+                tried |= CredentialType::USER_PASS_PLAINTEXT;
+                return Cred::credential_helper(config, url, username);
+            }
+            if untried.contains(CredentialType::DEFAULT) {
+                tried |= CredentialType::DEFAULT;
+                return Cred::default();
+            }
+            Err(git2::Error::from_str(
+                "authentication failed; check the keys in your ssh-agent or your git credentials",
+            ))
+        });
+        callbacks
+    }
+
+    fn tracking_ref(&self) -> String {
+        format!("refs/remotes/{}/{}", REMOTE_NAME, self.branch)
+    }
+
+    fn fetch(&self, url: &str) -> Result<Option<Commit<'_>>, StorageError> {
+        let mut remote = match self.repo.find_remote(REMOTE_NAME) {
+            Ok(remote) if remote.url() == Some(url) => remote,
+            Ok(_) => {
+                self.repo.remote_set_url(REMOTE_NAME, url)?;
+                self.repo.find_remote(REMOTE_NAME)?
+            }
+            Err(_) => self.repo.remote(REMOTE_NAME, url)?,
+        };
+
+        let refspec = format!("+refs/heads/{}:{}", self.branch, self.tracking_ref());
+        let mut options = FetchOptions::new();
+        options.remote_callbacks(self.callbacks());
+        remote.fetch(&[refspec], Some(&mut options), None)?;
+
+        match self.repo.find_reference(&self.tracking_ref()) {
+            Ok(reference) => Ok(Some(reference.peel_to_commit()?)),
+            Err(e) if e.code() == ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn push(&self) -> Result<(), StorageError> {
+        let head = self.repo.head()?;
+        let local_ref = head.name().unwrap_or("HEAD");
+        let refspec = format!("{}:refs/heads/{}", local_ref, self.branch);
+
+        let mut rejected = None;
+        {
+            let mut callbacks = self.callbacks();
+            callbacks.push_update_reference(|reference, status| {
+                if let Some(status) = status {
+                    rejected = Some(format!("Remote rejected '{}': {}", reference, status));
+                }
+                Ok(())
+            });
+            let mut options = PushOptions::new();
+            options.remote_callbacks(callbacks);
+            self.repo
+                .find_remote(REMOTE_NAME)?
+                .push(&[refspec], Some(&mut options))?;
+        }
+
+        match rejected {
+            Some(message) => Err(StorageError::Conflict(message)),
+            None => Ok(()),
+        }
+    }
+
+    fn checkout_head(&self) -> Result<(), StorageError> {
+        Ok(self
+            .repo
+            .checkout_head(Some(CheckoutBuilder::new().force()))?)
+    }
+
+    fn merge(
+        &self,
+        local: &Commit,
+        remote: &Commit,
+        prefer: Option<Side>,
+    ) -> Result<Vec<String>, StorageError> {
+        let base = match self.repo.merge_base(local.id(), remote.id()) {
+            Ok(id) => Some(self.repo.find_commit(id)?.tree()?),
+            Err(e) if e.code() == ErrorCode::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        let (local_tree, remote_tree) = (local.tree()?, remote.tree()?);
+        let mut index = self.repo.merge_commits(local, remote, None)?;
+
+        let local_stores = self.changed_stores(base.as_ref(), &local_tree)?;
+        let remote_stores = self.changed_stores(base.as_ref(), &remote_tree)?;
+
+        let mut conflicts = Vec::new();
+        for dir in local_stores.intersection(&remote_stores) {
+            let key = self.keys.get(dir);
+            let base_store = read_store(&self.repo, base.as_ref(), dir, key)?;
+            let local_store = read_store(&self.repo, Some(&local_tree), dir, key)?;
+            let remote_store = read_store(&self.repo, Some(&remote_tree), dir, key)?;
+
+            let merge = merge_stores(&base_store, &local_store, &remote_store);
+            let merged = match prefer {
+                Some(side) => merge.resolve(side),
+                None if merge.conflicts.is_empty() => merge.store,
+                None => {
+                    conflicts.extend(merge.conflicts.iter().map(|c| match store_label(dir) {
+                        Some(store) => format!("{}: {}", store, c.describe()),
+                        None => c.describe(),
+                    }));
+                    continue;
+                }
+            };
+
+            let kind = [&local_tree, &remote_tree]
+                .into_iter()
+                .find_map(|tree| store_kind(&self.repo, tree, dir))
+                .unwrap_or_default();
+            self.stage_store(&mut index, dir, kind, &merged)?;
+        }
+
+        if !conflicts.is_empty() {
+            return Ok(conflicts);
+        }
+
+        self.resolve_other_files(&mut index, prefer.unwrap_or(Side::Local))?;
+
+        let tree = self.repo.find_tree(index.write_tree_to(&self.repo)?)?;
+        let signature = self.signature()?;
+        self.repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "sync: merge remote snippets",
+            &tree,
+            &[local, remote],
+        )?;
+        self.checkout_head()?;
+
+        Ok(vec![])
+    }
+
+    fn changed_stores(
+        &self,
+        base: Option<&Tree>,
+        tree: &Tree,
+    ) -> Result<BTreeSet<PathBuf>, StorageError> {
+        let diff = self.repo.diff_tree_to_tree(base, Some(tree), None)?;
+        Ok(diff
+            .deltas()
+            .filter(|delta| delta.status() != Delta::Unmodified)
+            .flat_map(|delta| [delta.old_file().path(), delta.new_file().path()])
+            .flatten()
+            .filter_map(|path| store_dir_of(path).map(Path::to_path_buf))
+            .collect())
+    }
+
+    fn stage_store(
+        &self,
+        index: &mut Index,
+        dir: &Path,
+        kind: BackendKind,
+        store: &SnippetStore,
+    ) -> Result<(), StorageError> {
+        let stale: Vec<PathBuf> = index
+            .iter()
+            .map(|entry| PathBuf::from(String::from_utf8_lossy(&entry.path).into_owned()))
+            .filter(|path| store_dir_of(path) == Some(dir))
+            .collect();
+        for path in stale {
+            index.remove_path(&path)?;
+        }
+
+        let staging = tempfile::tempdir()?;
+        open_sealed_backend(kind, staging.path(), self.keys.get(dir)).write(store)?;
+        for file in files_in(staging.path())? {
+            let relative = file.strip_prefix(staging.path()).unwrap_or(&file);
+            let blob = self.repo.blob(&fs::read(&file)?)?;
+            index.add(&index_entry(&dir.join(relative), blob))?;
+        }
+
+        Ok(())
+    }
+
+    fn resolve_other_files(&self, index: &mut Index, side: Side) -> Result<(), StorageError> {
+        let mut resolved = Vec::new();
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            let path = [&conflict.our, &conflict.their, &conflict.ancestor]
+                .into_iter()
+                .flatten()
+                .map(|entry| entry.path.clone())
+                .next()
+                .unwrap_or_default();
+            let chosen = match side {
+                Side::Local => conflict.our,
+                Side::Remote => conflict.their,
+            };
+            resolved.push((path, chosen));
+        }
+
+        for (path, chosen) in resolved {
+            let path = PathBuf::from(String::from_utf8_lossy(&path).into_owned());
+            index.remove_path(&path)?;
+            if let Some(entry) = chosen {
+                index.add(&index_entry(&path, entry.id))?;
+            }
+            eprintln!(
+                "⚠️ Kept the {} version of '{}'.",
+                if side == Side::Local {
+                    "local"
+                } else {
+                    "remote"
+                },
+                path.display()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl SnippetSync for GitRepo {
+    fn sync(&self, prefer: Option<Side>) -> Result<SyncReport, StorageError> {
+        let Some(url) = self.remote.as_deref() else {
+            return Err(StorageError::Conflict(
+                "No git remote configured; set git.remote in config.yml.".to_string(),
+            ));
+        };
+
+        let _lock = self.lock.acquire()?;
+        self.commit_all("sync: commit local changes")?;
+        let Some(local) = self.head_commit()? else {
+            return Ok(SyncReport::default());
+        };
+
+        let mut report = SyncReport::default();
+        let remote = self.fetch(url)?;
+
+        if let Some(remote) = &remote
+            && remote.id() != local.id()
+        {
+            let base = self.repo.merge_base(local.id(), remote.id()).ok();
+            if base == Some(local.id()) {
+                self.repo
+                    .head()?
+                    .set_target(remote.id(), "sync: fast-forward")?;
+                self.checkout_head()?;
+                report.pulled = true;
+            } else if base != Some(remote.id()) {
+                report.conflicts = self.merge(&local, remote, prefer)?;
+                if !report.conflicts.is_empty() {
+                    return Ok(report);
+                }
+                report.pulled = true;
+            }
+        }
+
+        let head = self.head_commit()?.map(|c| c.id());
+        if head != remote.map(|c| c.id()) {
+            self.push()?;
+            report.pushed = true;
+        }
+
+        Ok(report)
+    }
+}
+
+pub fn commit_message(operation: &str, changes: &[SnippetChange]) -> String {
+    let names: Vec<&str> = changes.iter().map(|c| c.name()).collect();
+    let subject = match names.len() {
+        0..=3 => names.join(", "),
+        n => format!("{} and {} more", names[..3].join(", "), n - 3),
+    };
+
+    let body: Vec<String> = changes
+        .iter()
+        .map(|change| {
+            let action = match (&change.before, &change.after) {
+                (None, Some(_)) => "added",
+                (Some(_), None) => "removed",
+                _ => "updated",
+            };
+            format!("- {} {}", action, change.name())
+        })
+        .collect();
+
+    format!("{}: {}\n\n{}\n", operation, subject, body.join("\n"))
+}
+
+fn store_label(dir: &Path) -> Option<String> {
+    dir.strip_prefix(STORES_DIR)
+        .ok()
+        .map(|name| name.display().to_string())
+}
+
+fn index_entry(path: &Path, id: Oid) -> IndexEntry {
+    let path = path.to_string_lossy().into_owned().into_bytes();
+    IndexEntry {
+        ctime: IndexTime::new(0, 0),
+        mtime: IndexTime::new(0, 0),
+        dev: 0,
+        ino: 0,
+        mode: 0o100644,
+        uid: 0,
+        gid: 0,
+        file_size: 0,
+        id,
+        flags: path.len().min(0xfff) as u16,
+        flags_extended: 0,
+        path,
+    }
+}
+
+fn files_in(dir: &Path) -> Result<Vec<PathBuf>, StorageError> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(files_in(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+fn materialize(repo: &Repository, tree: &Tree, dest: &Path) -> Result<(), StorageError> {
+    fs::create_dir_all(dest)?;
+    for entry in tree.iter() {
+        let Some(name) = entry.name() else {
+            continue;
+        };
+        match entry.kind() {
+            Some(ObjectType::Tree) if name != STORES_DIR => {
+                materialize(repo, &repo.find_tree(entry.id())?, &dest.join(name))?
+            }
+            Some(ObjectType::Blob) => {
+                fs::write(dest.join(name), repo.find_blob(entry.id())?.content())?
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn store_tree<'r>(
+    repo: &'r Repository,
+    tree: &Tree<'r>,
+    dir: &Path,
+) -> Result<Option<Tree<'r>>, StorageError> {
+    if dir.as_os_str().is_empty() {
+        return Ok(Some(tree.clone()));
+    }
+    match tree.get_path(dir) {
+        Ok(entry) => Ok(Some(repo.find_tree(entry.id())?)),
+        Err(e) if e.code() == ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn store_kind(repo: &Repository, tree: &Tree, dir: &Path) -> Option<BackendKind> {
+    let tree = store_tree(repo, tree, dir).ok()??;
+    let staging = tempfile::tempdir().ok()?;
+    materialize(repo, &tree, staging.path()).ok()?;
+    detect_backend(staging.path())
+}
+
+fn read_store(
+    repo: &Repository,
+    tree: Option<&Tree>,
+    dir: &Path,
+    key: Option<&StoreKey>,
+) -> Result<SnippetStore, StorageError> {
+    let Some(tree) = tree else {
+        return Ok(SnippetStore::default());
+    };
+    let Some(tree) = store_tree(repo, tree, dir)? else {
+        return Ok(SnippetStore::default());
+    };
+
+    let staging = tempfile::tempdir()?;
+    materialize(repo, &tree, staging.path())?;
+    match detect_backend(staging.path()) {
+        Some(kind) => open_sealed_backend(kind, staging.path(), key).read(),
+        None => Ok(SnippetStore::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{Snippet, SnippetKind, TrashEntry},
+        storage::{Storage, Trash, file_storage::FileStorage},
+    };
+    use chrono::Utc;
+
+    fn sample_snippet(name: &str) -> Snippet {
+        Snippet {
+            id: Snippet::generate_id(),
+            name: name.into(),
+            description: String::new(),
+            content: "ls".into(),
+            kind: SnippetKind::Shell,
+            tags: vec![],
+            variables: vec![],
+            database: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn machine(root: &Path, name: &str, remote: &Path) -> FileStorage {
+        let data = root.join(name);
+        let git = GitRepo::open_or_init(&data, "main")
+            .unwrap()
+            .with_remote(Some(remote.display().to_string()));
+        let storage = FileStorage::with_dirs(data, root.join(format!("{}-state", name)));
+        storage.set_operation("save");
+        storage.with_git(git)
+    }
+
+    fn head_message(storage: &FileStorage) -> String {
+        let repo = &storage.git().unwrap().repo;
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        head.message().unwrap().to_string()
+    }
+
+    fn edit(storage: &FileStorage, name: &str, content: &str) {
+        let mut store = storage.load().unwrap();
+        let snippet = store.snippets.iter_mut().find(|s| s.name == name).unwrap();
+        snippet.content = content.into();
+        storage.save_all(&store).unwrap();
+    }
+
+    fn remote(root: &Path) -> PathBuf {
+        let path = root.join("remote.git");
+        Repository::init_bare(&path).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_saves_are_committed_with_descriptive_messages() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = machine(dir.path(), "laptop", &remote(dir.path()));
+
+        storage.save(sample_snippet("deploy")).unwrap();
+        assert_eq!(head_message(&storage), "save: deploy\n\n- added deploy\n");
+        assert!(storage.get_backups().is_err());
+
+        let git = storage.git().unwrap();
+        assert_eq!(git.commit_all("noop").unwrap(), None);
+        assert!(dir.path().join("laptop/.gitignore").is_file());
+    }
+
+    #[test]
+    fn test_local_state_is_never_committed() {
+        let dir = tempfile::tempdir().unwrap();
+        let home = dir.path().join("home");
+        fs::create_dir_all(home.join("history")).unwrap();
+        fs::write(home.join("history/old.yml"), "tracked before\n").unwrap();
+        let git = GitRepo::open_or_init(&home, "main").unwrap();
+        fs::write(home.join(".gitignore"), "custom/").unwrap();
+        git.commit_all("old layout").unwrap();
+        let old = git.repo.head().unwrap().peel_to_tree().unwrap();
+        assert!(old.get_path(Path::new("history/old.yml")).is_ok());
+
+        let storage = FileStorage::with_dirs(home.clone(), home.clone())
+            .with_git(GitRepo::open_or_init(&home, "main").unwrap());
+        storage.set_operation("save");
+        storage.save(sample_snippet("deploy")).unwrap();
+        edit(&storage, "deploy", "make deploy-all");
+        let mut store = storage.load().unwrap();
+        let deleted = store.snippets.remove(0);
+        storage.save_all(&store).unwrap();
+        storage
+            .save_trash(&[TrashEntry {
+                deleted_at: Utc::now(),
+                snippet: deleted,
+            }])
+            .unwrap();
+        storage.git().unwrap().commit_all("trash").unwrap();
+
+        let repo = &storage.git().unwrap().repo;
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        let mut committed = Vec::new();
+        tree.walk(git2::TreeWalkMode::PreOrder, |root, entry| {
+            committed.push(format!("{}{}", root, entry.name().unwrap_or_default()));
+            git2::TreeWalkResult::Ok
+        })
+        .unwrap();
+
+        assert!(home.join("journal.yml").is_file());
+        assert!(home.join("trash.yml").is_file());
+        for path in &committed {
+            assert!(
+                !["backups", "history", "journal", "trash"]
+                    .iter()
+                    .any(|state| path.starts_with(state)),
+                "{} was committed",
+                path
+            );
+        }
+        let ignore = fs::read_to_string(home.join(".gitignore")).unwrap();
+        assert!(ignore.starts_with("custom/\n.lock\n"));
+    }
+
+    #[test]
+    fn test_sync_pushes_and_pulls() {
+        let dir = tempfile::tempdir().unwrap();
+        let remote = remote(dir.path());
+        let laptop = machine(dir.path(), "laptop", &remote);
+        let desktop = machine(dir.path(), "desktop", &remote);
+
+        laptop.save(sample_snippet("deploy")).unwrap();
+        let report = laptop.git().unwrap().sync(None).unwrap();
+        assert!(report.pushed && !report.pulled);
+
+        assert!(desktop.git().unwrap().sync(None).unwrap().pulled);
+        assert_eq!(desktop.load().unwrap().snippets[0].name, "deploy");
+
+        desktop.save(sample_snippet("seed")).unwrap();
+        assert!(desktop.git().unwrap().sync(None).unwrap().pushed);
+
+        let report = laptop.git().unwrap().sync(None).unwrap();
+        assert!(report.pulled && !report.pushed);
+        assert_eq!(laptop.load().unwrap().snippets.len(), 2);
+
+        assert_eq!(
+            desktop.git().unwrap().sync(None).unwrap(),
+            SyncReport::default()
+        );
+    }
+
+    #[test]
+    fn test_sync_merges_snippets_changed_on_both_sides() {
+        let dir = tempfile::tempdir().unwrap();
+        let remote = remote(dir.path());
+        let laptop = machine(dir.path(), "laptop", &remote);
+        let desktop = machine(dir.path(), "desktop", &remote);
+
+        laptop.save(sample_snippet("deploy")).unwrap();
+        laptop.save(sample_snippet("seed")).unwrap();
+        laptop.git().unwrap().sync(None).unwrap();
+        desktop.git().unwrap().sync(None).unwrap();
+
+        edit(&laptop, "deploy", "make deploy");
+        edit(&desktop, "seed", "make seed");
+        laptop.git().unwrap().sync(None).unwrap();
+
+        let report = desktop.git().unwrap().sync(None).unwrap();
+        assert!(report.pulled && report.pushed && report.conflicts.is_empty());
+
+        let store = desktop.load().unwrap();
+        let contents: Vec<&str> = store.snippets.iter().map(|s| s.content.as_str()).collect();
+        assert_eq!(contents, vec!["make deploy", "make seed"]);
+    }
+
+    #[test]
+    fn test_sync_reports_conflicts_per_snippet() {
+        let dir = tempfile::tempdir().unwrap();
+        let remote = remote(dir.path());
+        let laptop = machine(dir.path(), "laptop", &remote);
+        let desktop = machine(dir.path(), "desktop", &remote);
+
+        laptop.save(sample_snippet("deploy")).unwrap();
+        laptop.git().unwrap().sync(None).unwrap();
+        desktop.git().unwrap().sync(None).unwrap();
+
+        edit(&laptop, "deploy", "make deploy");
+        edit(&desktop, "deploy", "./deploy.sh");
+        laptop.git().unwrap().sync(None).unwrap();
+
+        let report = desktop.git().unwrap().sync(None).unwrap();
+        assert_eq!(
+            report.conflicts,
+            vec!["deploy (changed locally, changed remotely)"]
+        );
+        assert_eq!(desktop.load().unwrap().snippets[0].content, "./deploy.sh");
+
+        let report = desktop.git().unwrap().sync(Some(Side::Remote)).unwrap();
+        assert!(report.conflicts.is_empty());
+        assert_eq!(desktop.load().unwrap().snippets[0].content, "make deploy");
+    }
+}
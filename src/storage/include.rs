@@ -0,0 +1,143 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    models::{Snippet, SnippetStore},
+    storage::{
+        Storage, StorageError,
+        backend::{SnippetBackend, YamlBackend, detect_backend, open_backend},
+    },
+};
+
+pub struct IncludeStore {
+    label: String,
+    path: PathBuf,
+}
+
+impl IncludeStore {
+    pub fn new(label: String, path: PathBuf) -> Self {
+        Self { label, path }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn backend(&self) -> Box<dyn SnippetBackend> {
+        if self.path.is_dir() {
+            open_backend(detect_backend(&self.path).unwrap_or_default(), &self.path)
+        } else {
+            Box::new(YamlBackend::new(self.path.clone()))
+        }
+    }
+
+    fn read_only(&self) -> StorageError {
+        StorageError::Conflict(format!("'{}' is a read-only shared store.", self.label))
+    }
+}
+
+impl Storage for IncludeStore {
+    fn load(&self) -> Result<SnippetStore, StorageError> {
+        let backend = self.backend();
+        if !backend.exists() {
+            return Err(StorageError::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no snippets found at '{}'", self.path.display()),
+            )));
+        }
+
+        let mut store = backend.read()?;
+        let scope = self.path.to_string_lossy();
+        store.backfill_ids_with(|snippet| Snippet::derived_id(&scope, &snippet.name));
+        Ok(store)
+    }
+
+    fn save(&self, _: Snippet) -> Result<(), StorageError> {
+        Err(self.read_only())
+    }
+
+    fn save_all(&self, _: &SnippetStore) -> Result<(), StorageError> {
+        Err(self.read_only())
+    }
+
+    fn get_backups(&self) -> Result<Vec<PathBuf>, StorageError> {
+        Ok(vec![])
+    }
+
+    fn restore_backup(&self, _: &Path) -> Result<(), StorageError> {
+        Err(self.read_only())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::backend::BackendKind;
+    use std::fs;
+
+    const SNIPPETS: &str = "snippets:\n- name: deploy\n  description: ''\n  content: make deploy\n  kind: shell\n  tags: []\n";
+
+    #[test]
+    fn test_loads_store_directory_or_file() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("bookmarks.yml"), SNIPPETS).unwrap();
+        fs::write(dir.path().join("ops.yml"), SNIPPETS).unwrap();
+
+        let from_dir = IncludeStore::new("team".into(), dir.path().to_path_buf());
+        let from_file = IncludeStore::new("ops".into(), dir.path().join("ops.yml"));
+
+        assert_eq!(from_dir.load().unwrap().snippets[0].name, "deploy");
+        assert_eq!(from_file.load().unwrap().snippets[0].name, "deploy");
+        assert!(!from_dir.load().unwrap().snippets[0].id.is_empty());
+    }
+
+    #[test]
+    fn test_ids_are_stable_across_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("bookmarks.yml"), SNIPPETS).unwrap();
+        let store = IncludeStore::new("team".into(), dir.path().to_path_buf());
+
+        assert_eq!(
+            store.load().unwrap().snippets[0].id,
+            store.load().unwrap().snippets[0].id
+        );
+    }
+
+    #[test]
+    fn test_loads_dir_backend_store() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("ops.yml"), SNIPPETS).unwrap();
+        let store = IncludeStore::new("ops".into(), dir.path().join("ops.yml"))
+            .load()
+            .unwrap();
+        open_backend(BackendKind::Dir, &dir.path().join("team"))
+            .write(&store)
+            .unwrap();
+
+        let team = IncludeStore::new("team".into(), dir.path().join("team"));
+        assert_eq!(team.load().unwrap().snippets[0].name, "deploy");
+    }
+
+    #[test]
+    fn test_is_read_only() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("bookmarks.yml"), SNIPPETS).unwrap();
+        let store = IncludeStore::new("team".into(), dir.path().to_path_buf());
+
+        let loaded = store.load().unwrap();
+        assert!(store.save_all(&loaded).is_err());
+        assert!(store.save(loaded.snippets[0].clone()).is_err());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("bookmarks.yml")).unwrap(),
+            SNIPPETS
+        );
+    }
+
+    #[test]
+    fn test_missing_include_is_an_error() {
+        let store = IncludeStore::new("team".into(), PathBuf::from("/nonexistent/team"));
+        assert!(store.load().is_err());
+    }
+}
@@ -0,0 +1,202 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::models::{PartialSnippet, Snippet, SnippetStore};
+
+pub const MAX_JOURNAL_ENTRIES: usize = 50;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SnippetChange {
+    pub id: String,
+    pub before: Option<Snippet>,
+    pub after: Option<Snippet>,
+}
+
+impl SnippetChange {
+    pub fn name(&self) -> &str {
+        self.after
+            .as_ref()
+            .or(self.before.as_ref())
+            .map(|s| s.name.as_str())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JournalEntry {
+    pub operation: String,
+    pub recorded_at: DateTime<Utc>,
+    pub changes: Vec<SnippetChange>,
+    pub before_hash: String,
+    pub after_hash: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct JournalFile {
+    #[serde(default)]
+    pub undo: Vec<JournalEntry>,
+    #[serde(default)]
+    pub redo: Vec<JournalEntry>,
+}
+
+impl JournalFile {
+    pub fn record(&mut self, entry: JournalEntry) {
+        self.undo.push(entry);
+        self.redo.clear();
+
+        let excess = self.undo.len().saturating_sub(MAX_JOURNAL_ENTRIES);
+        self.undo.drain(..excess);
+    }
+}
+
+pub fn changes_between(before: &SnippetStore, after: &SnippetStore) -> Vec<SnippetChange> {
+    let mut changes = Vec::new();
+
+    for snippet in &after.snippets {
+        let previous = before.snippets.iter().find(|s| s.id == snippet.id);
+        if previous.is_some_and(|p| PartialSnippet::from(p) == PartialSnippet::from(snippet)) {
+            continue;
+        }
+
+        changes.push(SnippetChange {
+            id: snippet.id.clone(),
+            before: previous.cloned(),
+            after: Some(snippet.clone()),
+        });
+    }
+
+    for snippet in &before.snippets {
+        if !after.snippets.iter().any(|s| s.id == snippet.id) {
+            changes.push(SnippetChange {
+                id: snippet.id.clone(),
+                before: Some(snippet.clone()),
+                after: None,
+            });
+        }
+    }
+
+    changes
+}
+
+pub fn apply_changes(store: &mut SnippetStore, changes: &[SnippetChange], undo: bool) {
+    for change in changes {
+        let target = if undo { &change.before } else { &change.after };
+        let position = store.snippets.iter().position(|s| s.id == change.id);
+
+        match (position, target) {
+            (Some(i), Some(snippet)) => store.snippets[i] = snippet.clone(),
+            (Some(i), None) => {
+                store.snippets.remove(i);
+            }
+            (None, Some(snippet)) => store.snippets.push(snippet.clone()),
+            (None, None) => {}
+        }
+    }
+}
+
+pub fn store_hash(store: &SnippetStore) -> String {
+    let mut snippets: Vec<(&str, PartialSnippet)> = store
+        .snippets
+        .iter()
+        .map(|s| (s.id.as_str(), PartialSnippet::from(s)))
+        .collect();
+    snippets.sort_by(|a, b| a.0.cmp(b.0));
+
+    let yaml = serde_yaml::to_string(&snippets).unwrap_or_default();
+    Sha256::digest(yaml.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SnippetKind;
+
+    fn snippet(name: &str, content: &str) -> Snippet {
+        Snippet {
+            id: Snippet::generate_id(),
+            name: name.into(),
+            description: String::new(),
+            content: content.into(),
+            kind: SnippetKind::Shell,
+            tags: vec![],
+            variables: vec![],
+            database: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_changes_round_trip_through_undo_and_redo() {
+        let kept = snippet("kept", "ls");
+        let edited = snippet("edited", "pwd");
+        let removed = snippet("removed", "id");
+
+        let before = SnippetStore {
+            snippets: vec![kept.clone(), edited.clone(), removed.clone()],
+        };
+        let mut changed = edited.clone();
+        changed.content = "pwd -P".into();
+        let after = SnippetStore {
+            snippets: vec![kept, changed, snippet("added", "whoami")],
+        };
+
+        let changes = changes_between(&before, &after);
+        assert_eq!(changes.len(), 3);
+
+        let mut store = after.clone();
+        apply_changes(&mut store, &changes, true);
+        assert_eq!(store_hash(&store), store_hash(&before));
+
+        apply_changes(&mut store, &changes, false);
+        assert_eq!(store_hash(&store), store_hash(&after));
+    }
+
+    #[test]
+    fn test_store_hash_ignores_order_and_timestamps() {
+        let a = snippet("a", "ls");
+        let b = snippet("b", "pwd");
+        let one = SnippetStore {
+            snippets: vec![a.clone(), b.clone()],
+        };
+        let mut touched = b.clone();
+        touched.updated_at = Utc::now() + chrono::Duration::hours(1);
+        let two = SnippetStore {
+            snippets: vec![touched, a.clone()],
+        };
+
+        assert_eq!(store_hash(&one), store_hash(&two));
+
+        let mut changed = a;
+        changed.content = "ls -la".into();
+        let three = SnippetStore {
+            snippets: vec![changed, b],
+        };
+        assert_ne!(store_hash(&one), store_hash(&three));
+    }
+
+    #[test]
+    fn test_record_clears_redo_and_caps_history() {
+        let entry = |op: &str| JournalEntry {
+            operation: op.into(),
+            recorded_at: Utc::now(),
+            changes: vec![],
+            before_hash: String::new(),
+            after_hash: String::new(),
+        };
+
+        let mut journal = JournalFile::default();
+        journal.redo.push(entry("undone"));
+        for i in 0..MAX_JOURNAL_ENTRIES + 5 {
+            journal.record(entry(&format!("op {}", i)));
+        }
+
+        assert!(journal.redo.is_empty());
+        assert_eq!(journal.undo.len(), MAX_JOURNAL_ENTRIES);
+        assert_eq!(journal.undo[0].operation, "op 5");
+    }
+}
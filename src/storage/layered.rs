@@ -0,0 +1,277 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    models::{Snippet, SnippetStore},
+    storage::{
+        SnippetSources, Storage, StorageError,
+        filter::{Filter, apply_filter},
+    },
+};
+
+pub struct LayeredStorage<'a> {
+    layers: Vec<(String, &'a dyn Storage, bool)>,
+    sources: RefCell<HashMap<String, String>>,
+}
+
+impl<'a> LayeredStorage<'a> {
+    pub fn new() -> Self {
+        Self {
+            layers: vec![],
+            sources: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_layer(mut self, label: impl Into<String>, storage: &'a dyn Storage) -> Self {
+        self.layers.push((label.into(), storage, false));
+        self
+    }
+
+    pub fn with_required_layer(
+        mut self,
+        label: impl Into<String>,
+        storage: &'a dyn Storage,
+    ) -> Self {
+        self.layers.push((label.into(), storage, true));
+        self
+    }
+
+    fn read_only() -> StorageError {
+        StorageError::Conflict(
+            "Combined snippets are read-only; write to a single store instead.".to_string(),
+        )
+    }
+}
+
+impl Storage for LayeredStorage<'_> {
+    fn load(&self) -> Result<SnippetStore, StorageError> {
+        let mut merged = SnippetStore::default();
+        let mut sources = HashMap::new();
+        let mut last_error = None;
+        let mut loaded_any = false;
+
+        for (label, storage, required) in &self.layers {
+            let store = match storage.load() {
+                Ok(store) => store,
+                Err(e) if *required => return Err(e),
+                Err(e) => {
+                    eprintln!("⚠️ Skipping {} snippets: {}", label, e);
+                    last_error = Some(e);
+                    continue;
+                }
+            };
+            loaded_any = true;
+
+            for snippet in store.snippets {
+                if merged
+                    .snippets
+                    .iter()
+                    .any(|s: &Snippet| s.name.eq_ignore_ascii_case(&snippet.name))
+                {
+                    continue;
+                }
+                sources.insert(snippet.id.clone(), label.clone());
+                merged.snippets.push(snippet);
+            }
+        }
+
+        if !loaded_any && let Some(e) = last_error {
+            return Err(e);
+        }
+
+        self.sources.replace(sources);
+        Ok(merged)
+    }
+
+    fn find_by_name(&self, name: &str) -> Result<Option<Snippet>, StorageError> {
+        for (label, storage, _) in &self.layers {
+            match storage.find_by_name(name) {
+                Ok(Some(snippet)) => return Ok(Some(snippet)),
+                Ok(None) => {}
+                Err(e) => {
+                    return Err(StorageError::Conflict(format!(
+                        "Failed to read {} snippets: {}",
+                        label, e
+                    )));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn find_by_tag(&self, tag: &str) -> Result<Vec<Snippet>, StorageError> {
+        match self.layers.as_slice() {
+            [(_, storage, _)] => storage.find_by_tag(tag),
+            _ => Ok(apply_filter(&self.load()?, Filter::Tag(tag.to_string()))),
+        }
+    }
+
+    fn save(&self, _: Snippet) -> Result<(), StorageError> {
+        Err(Self::read_only())
+    }
+
+    fn save_all(&self, _: &SnippetStore) -> Result<(), StorageError> {
+        Err(Self::read_only())
+    }
+
+    fn get_backups(&self) -> Result<Vec<PathBuf>, StorageError> {
+        Ok(vec![])
+    }
+
+    fn restore_backup(&self, _: &Path) -> Result<(), StorageError> {
+        Err(Self::read_only())
+    }
+}
+
+impl SnippetSources for LayeredStorage<'_> {
+    fn source_of(&self, id: &str) -> Option<String> {
+        if self.layers.len() < 2 {
+            return None;
+        }
+        self.sources.borrow().get(id).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SnippetKind;
+    use chrono::Utc;
+
+    struct MockStorage {
+        store: Result<SnippetStore, String>,
+    }
+
+    impl MockStorage {
+        fn with(names: &[&str]) -> Self {
+            Self {
+                store: Ok(SnippetStore {
+                    snippets: names.iter().map(|n| sample_snippet(n)).collect(),
+                }),
+            }
+        }
+    }
+
+    impl Storage for MockStorage {
+        fn load(&self) -> Result<SnippetStore, StorageError> {
+            self.store
+                .clone()
+                .map_err(|e| StorageError::Conflict(e.clone()))
+        }
+
+        fn save(&self, _: Snippet) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn save_all(&self, _: &SnippetStore) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn get_backups(&self) -> Result<Vec<PathBuf>, StorageError> {
+            Ok(vec![])
+        }
+
+        fn restore_backup(&self, _: &Path) -> Result<(), StorageError> {
+            Ok(())
+        }
+    }
+
+    fn sample_snippet(name: &str) -> Snippet {
+        Snippet {
+            id: Snippet::generate_id(),
+            name: name.into(),
+            description: String::new(),
+            content: "ls".into(),
+            kind: SnippetKind::Shell,
+            tags: vec![],
+            variables: vec![],
+            database: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_project_snippets_shadow_global_ones() {
+        let project = MockStorage::with(&["deploy", "seed"]);
+        let global = MockStorage::with(&["Deploy", "docker-clean"]);
+        let layered = LayeredStorage::new()
+            .with_layer("project", &project)
+            .with_layer("default", &global);
+
+        let store = layered.load().unwrap();
+        let names: Vec<&str> = store.snippets.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["deploy", "seed", "docker-clean"]);
+
+        assert_eq!(
+            layered.source_of(&store.snippets[0].id).as_deref(),
+            Some("project")
+        );
+        assert_eq!(
+            layered.source_of(&store.snippets[2].id).as_deref(),
+            Some("default")
+        );
+    }
+
+    #[test]
+    fn test_single_layer_has_no_sources() {
+        let global = MockStorage::with(&["ls"]);
+        let layered = LayeredStorage::new().with_layer("default", &global);
+
+        let store = layered.load().unwrap();
+        assert_eq!(layered.source_of(&store.snippets[0].id), None);
+        assert!(layered.save(sample_snippet("x")).is_err());
+    }
+
+    #[test]
+    fn test_broken_layers_are_skipped() {
+        let project = MockStorage {
+            store: Err("bad yaml".into()),
+        };
+        let global = MockStorage::with(&["ls"]);
+        let layered = LayeredStorage::new()
+            .with_layer("project", &project)
+            .with_layer("default", &global);
+
+        assert_eq!(layered.load().unwrap().snippets.len(), 1);
+
+        let layered = LayeredStorage::new()
+            .with_layer("default", &global)
+            .with_layer("team", &project);
+        assert_eq!(layered.load().unwrap().snippets.len(), 1);
+
+        let layered = LayeredStorage::new().with_layer("project", &project);
+        assert!(layered.load().is_err());
+    }
+
+    #[test]
+    fn test_find_by_name_reports_broken_layers() {
+        let global = MockStorage::with(&["ls"]);
+        let broken = MockStorage {
+            store: Err("bad yaml".into()),
+        };
+        let layered = LayeredStorage::new()
+            .with_layer("default", &global)
+            .with_layer("team", &broken);
+
+        assert!(layered.find_by_name("ls").unwrap().is_some());
+        let err = layered.find_by_name("deploy").unwrap_err();
+        assert!(err.to_string().contains("team"));
+    }
+
+    #[test]
+    fn test_broken_required_layer_is_an_error() {
+        let broken = MockStorage {
+            store: Err("bad yaml".into()),
+        };
+        let project = MockStorage::with(&["deploy"]);
+        let layered = LayeredStorage::new()
+            .with_layer("project", &project)
+            .with_required_layer("default", &broken);
+
+        assert!(layered.load().is_err());
+    }
+}
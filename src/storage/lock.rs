@@ -0,0 +1,125 @@
+use std::{
+    cell::{Cell, RefCell},
+    fs::{File, OpenOptions},
+    io,
+    path::PathBuf,
+};
+
+pub struct StoreLock {
+    path: PathBuf,
+    file: RefCell<Option<File>>,
+    depth: Cell<usize>,
+}
+
+pub struct LockGuard<'a> {
+    lock: &'a StoreLock,
+}
+
+impl StoreLock {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            file: RefCell::new(None),
+            depth: Cell::new(0),
+        }
+    }
+
+    pub fn acquire(&self) -> io::Result<LockGuard<'_>> {
+        if self.depth.get() == 0 {
+            let file = OpenOptions::new()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(&self.path)?;
+
+            match file.try_lock() {
+                Ok(()) => {}
+                Err(std::fs::TryLockError::WouldBlock) => {
+                    eprintln!("⏳ Waiting for another markit process to finish...");
+                    file.lock()?;
+                }
+                Err(std::fs::TryLockError::Error(e)) => return Err(e),
+            }
+
+            self.file.replace(Some(file));
+        }
+
+        self.depth.set(self.depth.get() + 1);
+        Ok(LockGuard { lock: self })
+    }
+}
+
+impl Drop for LockGuard<'_> {
+    fn drop(&mut self) {
+        let depth = self.lock.depth.get() - 1;
+        self.lock.depth.set(depth);
+
+        if depth == 0
+            && let Some(file) = self.lock.file.take()
+        {
+            let _ = file.unlock();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicBool, Ordering},
+        },
+        thread,
+        time::Duration,
+    };
+
+    fn contender(path: PathBuf) -> (Arc<AtomicBool>, thread::JoinHandle<()>) {
+        let acquired = Arc::new(AtomicBool::new(false));
+        let flag = acquired.clone();
+
+        let handle = thread::spawn(move || {
+            let lock = StoreLock::new(path);
+            let _guard = lock.acquire().unwrap();
+            flag.store(true, Ordering::SeqCst);
+        });
+
+        (acquired, handle)
+    }
+
+    #[test]
+    fn test_lock_blocks_other_holders_until_released() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".lock");
+        let lock = StoreLock::new(path.clone());
+
+        let guard = lock.acquire().unwrap();
+        let (acquired, handle) = contender(path);
+
+        thread::sleep(Duration::from_millis(100));
+        assert!(!acquired.load(Ordering::SeqCst));
+
+        drop(guard);
+        handle.join().unwrap();
+        assert!(acquired.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_lock_is_reentrant() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".lock");
+        let lock = StoreLock::new(path.clone());
+
+        let outer = lock.acquire().unwrap();
+        let inner = lock.acquire().unwrap();
+        drop(inner);
+
+        let (acquired, handle) = contender(path);
+        thread::sleep(Duration::from_millis(100));
+        assert!(!acquired.load(Ordering::SeqCst));
+
+        drop(outer);
+        handle.join().unwrap();
+        assert!(acquired.load(Ordering::SeqCst));
+    }
+}
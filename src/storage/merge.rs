@@ -0,0 +1,387 @@
+use std::{cmp::Ordering, collections::HashSet, str::FromStr};
+
+use similar::{ChangeTag, TextDiff};
+
+use crate::models::{PartialSnippet, Snippet, SnippetStore};
+
+pub const CONFLICT_START: &str = "<<<<<<<";
+pub const CONFLICT_SEPARATOR: &str = "=======";
+pub const CONFLICT_END: &str = ">>>>>>>";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Local,
+    Remote,
+}
+
+impl FromStr for Side {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "local" => Ok(Side::Local),
+            "remote" => Ok(Side::Remote),
+            _ => Err(format!("unknown side '{}' (expected local or remote)", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SnippetConflict {
+    pub name: String,
+    pub local: Option<Snippet>,
+    pub remote: Option<Snippet>,
+}
+
+impl SnippetConflict {
+    pub fn describe(&self) -> String {
+        let side = |s: &Option<Snippet>| if s.is_some() { "changed" } else { "deleted" };
+        format!(
+            "{} ({} locally, {} remotely)",
+            self.name,
+            side(&self.local),
+            side(&self.remote)
+        )
+    }
+
+    fn pick(&self, side: Side) -> Option<&Snippet> {
+        match side {
+            Side::Local => self.local.as_ref(),
+            Side::Remote => self.remote.as_ref(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct StoreMerge {
+    pub store: SnippetStore,
+    pub conflicts: Vec<SnippetConflict>,
+}
+
+impl StoreMerge {
+    pub fn resolve(mut self, prefer: Side) -> SnippetStore {
+        for conflict in &self.conflicts {
+            if let Some(chosen) = conflict.pick(prefer) {
+                self.store.snippets.push(chosen.clone());
+            }
+        }
+        self.store
+    }
+}
+
+fn same(a: Option<&Snippet>, b: Option<&Snippet>) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => PartialSnippet::from(a) == PartialSnippet::from(b),
+        _ => false,
+    }
+}
+
+fn find<'a>(store: &'a SnippetStore, id: &str) -> Option<&'a Snippet> {
+    store.snippets.iter().find(|s| s.id == id)
+}
+
+pub fn merge_stores(
+    base: &SnippetStore,
+    local: &SnippetStore,
+    remote: &SnippetStore,
+) -> StoreMerge {
+    let mut ids: Vec<&str> = Vec::new();
+    let mut seen = HashSet::new();
+    for snippet in local.snippets.iter().chain(&remote.snippets) {
+        if seen.insert(snippet.id.as_str()) {
+            ids.push(&snippet.id);
+        }
+    }
+
+    let mut merge = StoreMerge::default();
+    for id in ids {
+        let (b, l, r) = (find(base, id), find(local, id), find(remote, id));
+
+        let merged = if same(l, r) || same(b, r) {
+            l
+        } else if same(b, l) {
+            r
+        } else {
+            merge.conflicts.push(SnippetConflict {
+                name: l.or(r).map(|s| s.name.clone()).unwrap_or_default(),
+                local: l.cloned(),
+                remote: r.cloned(),
+            });
+            continue;
+        };
+
+        if let Some(snippet) = merged {
+            merge.store.snippets.push(snippet.clone());
+        }
+    }
+
+    let mut dropped: Vec<String> = Vec::new();
+    let mut clashes: Vec<SnippetConflict> = Vec::new();
+    for remote_only in merge
+        .store
+        .snippets
+        .iter()
+        .filter(|s| find(local, &s.id).is_none())
+    {
+        let Some(local_twin) = merge.store.snippets.iter().find(|s| {
+            find(local, &s.id).is_some() && s.name.eq_ignore_ascii_case(&remote_only.name)
+        }) else {
+            continue;
+        };
+
+        if same(Some(local_twin), Some(remote_only)) {
+            dropped.push(remote_only.id.clone());
+            continue;
+        }
+        match local_twin.updated_at.cmp(&remote_only.updated_at) {
+            Ordering::Greater => dropped.push(remote_only.id.clone()),
+            Ordering::Less => dropped.push(local_twin.id.clone()),
+            Ordering::Equal => {
+                dropped.extend([local_twin.id.clone(), remote_only.id.clone()]);
+                clashes.push(SnippetConflict {
+                    name: local_twin.name.clone(),
+                    local: Some(local_twin.clone()),
+                    remote: Some(remote_only.clone()),
+                });
+            }
+        }
+    }
+
+    merge.store.snippets.retain(|s| !dropped.contains(&s.id));
+    merge.conflicts.extend(clashes);
+
+    merge
+}
+
+pub fn merge_two_way(local: &SnippetStore, remote: &SnippetStore) -> StoreMerge {
+    let base = SnippetStore {
+        snippets: local
+            .snippets
+            .iter()
+            .filter_map(|l| {
+                let r = find(remote, &l.id)?;
+                if same(Some(l), Some(r)) {
+                    return Some(l.clone());
+                }
+                match l.updated_at.cmp(&r.updated_at) {
+                    Ordering::Less => Some(l.clone()),
+                    Ordering::Greater => Some(r.clone()),
+                    Ordering::Equal => None,
+                }
+            })
+            .collect(),
+    };
+
+    merge_stores(&base, local, remote)
+}
+
+pub fn conflict_markers(
+    local: &str,
+    remote: &str,
+    local_label: &str,
+    remote_label: &str,
+) -> String {
+    let diff = TextDiff::from_lines(local, remote);
+    let mut out = String::new();
+    let (mut ours, mut theirs) = (String::new(), String::new());
+
+    for change in diff.iter_all_changes() {
+        let line = match change.missing_newline() {
+            true => format!("{}\n", change.value()),
+            false => change.value().to_string(),
+        };
+        match change.tag() {
+            ChangeTag::Equal => {
+                flush_conflict(&mut out, &mut ours, &mut theirs, local_label, remote_label);
+                out.push_str(&line);
+            }
+            ChangeTag::Delete => ours.push_str(&line),
+            ChangeTag::Insert => theirs.push_str(&line),
+        }
+    }
+    flush_conflict(&mut out, &mut ours, &mut theirs, local_label, remote_label);
+
+    out
+}
+
+fn flush_conflict(
+    out: &mut String,
+    ours: &mut String,
+    theirs: &mut String,
+    local_label: &str,
+    remote_label: &str,
+) {
+    if ours.is_empty() && theirs.is_empty() {
+        return;
+    }
+    out.push_str(&format!("{} {}\n", CONFLICT_START, local_label));
+    out.push_str(ours);
+    out.push_str(&format!("{}\n", CONFLICT_SEPARATOR));
+    out.push_str(theirs);
+    out.push_str(&format!("{} {}\n", CONFLICT_END, remote_label));
+    ours.clear();
+    theirs.clear();
+}
+
+pub fn has_conflict_markers(text: &str) -> bool {
+    text.lines().any(|line| {
+        [CONFLICT_START, CONFLICT_SEPARATOR, CONFLICT_END]
+            .iter()
+            .any(|marker| line.starts_with(marker))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SnippetKind;
+    use chrono::Utc;
+
+    fn snippet(name: &str, content: &str) -> Snippet {
+        Snippet {
+            id: Snippet::generate_id(),
+            name: name.into(),
+            description: String::new(),
+            content: content.into(),
+            kind: SnippetKind::Shell,
+            tags: vec![],
+            variables: vec![],
+            database: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn store(snippets: &[&Snippet]) -> SnippetStore {
+        SnippetStore {
+            snippets: snippets.iter().map(|s| (*s).clone()).collect(),
+        }
+    }
+
+    fn names(store: &SnippetStore) -> Vec<&str> {
+        store.snippets.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_non_conflicting_changes_are_combined() {
+        let a = snippet("a", "ls");
+        let b = snippet("b", "pwd");
+        let mut a_edited = a.clone();
+        a_edited.content = "ls -la".into();
+        let c = snippet("c", "whoami");
+
+        let base = store(&[&a, &b]);
+        let local = store(&[&a_edited, &b]);
+        let remote = store(&[&a, &c]);
+
+        let merge = merge_stores(&base, &local, &remote);
+        assert!(merge.conflicts.is_empty());
+        assert_eq!(names(&merge.store), vec!["a", "c"]);
+        assert_eq!(merge.store.snippets[0].content, "ls -la");
+    }
+
+    #[test]
+    fn test_conflicting_edits_are_reported_and_resolved() {
+        let a = snippet("a", "ls");
+        let mut local_a = a.clone();
+        local_a.content = "ls -l".into();
+        let mut remote_a = a.clone();
+        remote_a.content = "ls -a".into();
+
+        let merge = merge_stores(&store(&[&a]), &store(&[&local_a]), &store(&[&remote_a]));
+        assert_eq!(merge.conflicts.len(), 1);
+        assert_eq!(
+            merge.conflicts[0].describe(),
+            "a (changed locally, changed remotely)"
+        );
+
+        let resolved = merge.resolve(Side::Remote);
+        assert_eq!(resolved.snippets.len(), 1);
+        assert_eq!(resolved.snippets[0].content, "ls -a");
+    }
+
+    #[test]
+    fn test_delete_against_edit_conflicts() {
+        let a = snippet("a", "ls");
+        let mut remote_a = a.clone();
+        remote_a.content = "ls -a".into();
+
+        let merge = merge_stores(&store(&[&a]), &store(&[]), &store(&[&remote_a]));
+        assert_eq!(
+            merge.conflicts[0].describe(),
+            "a (deleted locally, changed remotely)"
+        );
+        assert!(merge.resolve(Side::Local).snippets.is_empty());
+    }
+
+    #[test]
+    fn test_same_name_added_on_both_sides_conflicts() {
+        let local = snippet("deploy", "make deploy");
+        let mut remote = snippet("Deploy", "./deploy.sh");
+        remote.updated_at = local.updated_at;
+
+        let merge = merge_stores(&store(&[]), &store(&[&local]), &store(&[&remote]));
+        assert_eq!(merge.conflicts.len(), 1);
+        assert!(merge.store.snippets.is_empty());
+
+        let resolved = merge.resolve(Side::Local);
+        assert_eq!(resolved.snippets.len(), 1);
+        assert_eq!(resolved.snippets[0].content, "make deploy");
+    }
+
+    #[test]
+    fn test_same_name_pairs_are_resolved_when_possible() {
+        let local = snippet("deploy", "make deploy");
+        let mut twin = snippet("Deploy", "make deploy");
+        twin.name = "deploy".into();
+
+        let merge = merge_two_way(&store(&[&local]), &store(&[&twin]));
+        assert!(merge.conflicts.is_empty());
+        assert_eq!(merge.store.snippets.len(), 1);
+        assert_eq!(merge.store.snippets[0].id, local.id);
+
+        let mut newer = snippet("Deploy", "./deploy.sh");
+        newer.updated_at = local.updated_at + chrono::Duration::minutes(5);
+
+        let merge = merge_two_way(&store(&[&local]), &store(&[&newer]));
+        assert!(merge.conflicts.is_empty());
+        assert_eq!(merge.store.snippets.len(), 1);
+        assert_eq!(merge.store.snippets[0].content, "./deploy.sh");
+    }
+
+    #[test]
+    fn test_two_way_merge_prefers_newer_updates() {
+        let a = snippet("a", "ls");
+        let mut newer_a = a.clone();
+        newer_a.content = "ls -la".into();
+        newer_a.updated_at = a.updated_at + chrono::Duration::minutes(5);
+        let b = snippet("b", "pwd");
+
+        let merge = merge_two_way(&store(&[&a, &b]), &store(&[&newer_a]));
+        assert!(merge.conflicts.is_empty());
+        assert_eq!(names(&merge.store), vec!["a", "b"]);
+        assert_eq!(merge.store.snippets[0].content, "ls -la");
+
+        let mut same_time = a.clone();
+        same_time.content = "ls -1".into();
+        let merge = merge_two_way(&store(&[&a]), &store(&[&same_time]));
+        assert_eq!(merge.conflicts.len(), 1);
+    }
+
+    #[test]
+    fn test_conflict_markers_wrap_differing_lines() {
+        let text = conflict_markers(
+            "name: a\ncontent: ls\n",
+            "name: a\ncontent: pwd\n",
+            "local",
+            "other",
+        );
+        assert_eq!(
+            text,
+            "name: a\n<<<<<<< local\ncontent: ls\n=======\ncontent: pwd\n>>>>>>> other\n"
+        );
+        assert!(has_conflict_markers(&text));
+        assert!(!has_conflict_markers("name: a\n"));
+    }
+}
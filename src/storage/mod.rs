@@ -1,15 +1,39 @@
 use std::path::PathBuf;
 use std::{fmt, path::Path};
 
-use crate::models::{Snippet, SnippetStore};
+use crate::{
+    models::{Revision, Snippet, SnippetStore, TrashEntry},
+    storage::{
+        filter::{Filter, apply_filter},
+        git::SyncReport,
+        journal::JournalEntry,
+        merge::Side,
+        retention::BackupFile,
+    },
+};
 
+pub mod atomic;
+pub mod backend;
+pub mod crypto;
 pub mod file_storage;
 pub mod filter;
+pub mod git;
+pub mod include;
+pub mod journal;
+pub mod layered;
+pub mod lock;
+pub mod merge;
+pub mod project;
+pub mod retention;
+pub mod sqlite;
+pub mod stores;
+pub mod vault;
 
 #[derive(Debug)]
 pub enum StorageError {
     Io(std::io::Error),
     Serde(serde_yaml::Error),
+    Conflict(String),
 }
 
 impl From<std::io::Error> for StorageError {
@@ -29,14 +53,99 @@ impl fmt::Display for StorageError {
         match self {
             StorageError::Io(e) => write!(f, "IO error: {}", e),
             StorageError::Serde(e) => write!(f, "Serialization error: {}", e),
+            StorageError::Conflict(msg) => write!(f, "{}", msg),
         }
     }
 }
 
+pub fn ensure_unique_name(store: &SnippetStore, name: &str) -> Result<(), StorageError> {
+    if store
+        .snippets
+        .iter()
+        .any(|s| s.name.eq_ignore_ascii_case(name))
+    {
+        return Err(StorageError::Conflict(format!(
+            "A snippet with the name '{}' already exists.",
+            name
+        )));
+    }
+    Ok(())
+}
+
 pub trait Storage {
     fn load(&self) -> Result<SnippetStore, StorageError>;
+    fn find_by_name(&self, name: &str) -> Result<Option<Snippet>, StorageError> {
+        Ok(self
+            .load()?
+            .snippets
+            .into_iter()
+            .find(|s| s.name.eq_ignore_ascii_case(name)))
+    }
+    fn find_by_tag(&self, tag: &str) -> Result<Vec<Snippet>, StorageError> {
+        Ok(apply_filter(&self.load()?, Filter::Tag(tag.to_string())))
+    }
     fn save(&self, snippet: Snippet) -> Result<(), StorageError>;
     fn save_all(&self, store: &SnippetStore) -> Result<(), StorageError>;
     fn get_backups(&self) -> Result<Vec<PathBuf>, StorageError>;
     fn restore_backup(&self, path: &Path) -> Result<(), StorageError>;
 }
+
+pub trait SnippetSources {
+    fn source_of(&self, id: &str) -> Option<String>;
+}
+
+pub trait SnippetHistory {
+    fn get_history(&self, id: &str) -> Result<Vec<Revision>, StorageError>;
+}
+
+pub trait BackupRetention {
+    fn prune_backups(&self, dry_run: bool) -> Result<Vec<BackupFile>, StorageError>;
+}
+
+pub trait BackupArchive {
+    fn load_backup(&self, path: &Path) -> Result<SnippetStore, StorageError>;
+}
+
+pub trait Trash {
+    fn load_trash(&self) -> Result<Vec<TrashEntry>, StorageError>;
+    fn save_trash(&self, entries: &[TrashEntry]) -> Result<(), StorageError>;
+}
+
+pub trait Journal {
+    fn undo(&self) -> Result<Option<JournalEntry>, StorageError>;
+    fn redo(&self) -> Result<Option<JournalEntry>, StorageError>;
+}
+
+pub trait StoreCatalog {
+    fn list_stores(&self) -> Result<Vec<String>, StorageError>;
+    fn create_store(&self, name: &str) -> Result<(), StorageError>;
+    fn remove_store(&self, name: &str) -> Result<(), StorageError>;
+    fn default_store(&self) -> String;
+    fn set_default_store(&self, name: &str) -> Result<(), StorageError>;
+}
+
+pub trait StoreEncryption {
+    fn is_encrypted(&self) -> bool;
+    fn check_passphrase(&self, passphrase: &str) -> Result<(), StorageError>;
+    fn encrypt(&self, passphrase: &str) -> Result<(), StorageError>;
+    fn decrypt(&self) -> Result<(), StorageError>;
+}
+
+pub trait SecretStore {
+    fn is_initialized(&self) -> bool;
+    fn initialize(&self, passphrase: &str) -> Result<(), StorageError>;
+    fn secret_names(&self) -> Result<Vec<String>, StorageError>;
+    fn get_secret(&self, name: &str) -> Result<Option<String>, StorageError>;
+    fn set_secret(&self, name: &str, value: &str) -> Result<(), StorageError>;
+    fn remove_secret(&self, name: &str) -> Result<bool, StorageError>;
+}
+
+pub trait ProjectTrust {
+    fn is_trusted(&self, path: &Path) -> Result<bool, StorageError>;
+    fn trust(&self, path: &Path) -> Result<(), StorageError>;
+    fn revoke(&self, path: &Path) -> Result<bool, StorageError>;
+}
+
+pub trait SnippetSync {
+    fn sync(&self, prefer: Option<Side>) -> Result<SyncReport, StorageError>;
+}
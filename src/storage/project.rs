@@ -0,0 +1,248 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    models::{Snippet, SnippetStore},
+    storage::{
+        ProjectTrust, Storage, StorageError, atomic::write_atomic, ensure_unique_name,
+        lock::StoreLock,
+    },
+};
+
+pub const PROJECT_FILE: &str = ".markit.yml";
+const TRUST_FILE: &str = "trusted_projects.yml";
+const PROJECT_LOCK: &str = "project.lock";
+
+pub fn discover(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_FILE))
+        .find(|path| path.is_file())
+}
+
+pub struct ProjectFile {
+    path: PathBuf,
+    lock: StoreLock,
+}
+
+impl ProjectFile {
+    pub fn new(path: PathBuf, state_dir: &Path) -> Self {
+        Self {
+            path,
+            lock: StoreLock::new(state_dir.join(PROJECT_LOCK)),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn write_store(&self, store: &SnippetStore) -> Result<(), StorageError> {
+        let yaml = serde_yaml::to_string(store)?;
+        write_atomic(&self.path, yaml.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl Storage for ProjectFile {
+    fn load(&self) -> Result<SnippetStore, StorageError> {
+        if !self.path.exists() {
+            return Ok(SnippetStore::default());
+        }
+
+        let file = File::open(&self.path)?;
+        let mut store: SnippetStore = serde_yaml::from_reader(file)?;
+        let scope = self.path.to_string_lossy();
+        store.backfill_ids_with(|snippet| Snippet::derived_id(&scope, &snippet.name));
+        Ok(store)
+    }
+
+    fn save(&self, snippet: Snippet) -> Result<(), StorageError> {
+        let _lock = self.lock.acquire()?;
+        let mut store = self.load()?;
+        ensure_unique_name(&store, &snippet.name)?;
+        store.snippets.push(snippet);
+        self.write_store(&store)
+    }
+
+    fn save_all(&self, store: &SnippetStore) -> Result<(), StorageError> {
+        let _lock = self.lock.acquire()?;
+        self.write_store(store)
+    }
+
+    fn get_backups(&self) -> Result<Vec<PathBuf>, StorageError> {
+        Ok(vec![])
+    }
+
+    fn restore_backup(&self, _: &Path) -> Result<(), StorageError> {
+        Err(StorageError::Conflict(format!(
+            "'{}' has no backups; use version control to restore it.",
+            self.path.display()
+        )))
+    }
+}
+
+pub struct TrustList {
+    path: PathBuf,
+}
+
+impl TrustList {
+    pub fn new(state_dir: &Path) -> Self {
+        Self {
+            path: state_dir.join(TRUST_FILE),
+        }
+    }
+
+    fn load(&self) -> Result<BTreeMap<PathBuf, String>, StorageError> {
+        if !self.path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        Ok(serde_yaml::from_slice(&fs::read(&self.path)?)?)
+    }
+
+    fn write(&self, trusted: &BTreeMap<PathBuf, String>) -> Result<(), StorageError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let yaml = serde_yaml::to_string(trusted)?;
+        write_atomic(&self.path, yaml.as_bytes())?;
+        Ok(())
+    }
+}
+
+fn content_hash(path: &Path) -> Result<String, StorageError> {
+    Ok(Sha256::digest(fs::read(path)?)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+impl ProjectTrust for TrustList {
+    fn is_trusted(&self, path: &Path) -> Result<bool, StorageError> {
+        let path = path.canonicalize()?;
+        Ok(self
+            .load()?
+            .get(&path)
+            .is_some_and(|hash| content_hash(&path).is_ok_and(|current| *hash == current)))
+    }
+
+    fn trust(&self, path: &Path) -> Result<(), StorageError> {
+        let path = path.canonicalize()?;
+        let mut trusted = self.load()?;
+        trusted.insert(path.clone(), content_hash(&path)?);
+        self.write(&trusted)
+    }
+
+    fn revoke(&self, path: &Path) -> Result<bool, StorageError> {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let mut trusted = self.load()?;
+        if trusted.remove(&path).is_none() {
+            return Ok(false);
+        }
+        self.write(&trusted)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SnippetKind;
+    use chrono::Utc;
+    use std::fs;
+
+    fn sample_snippet(name: &str) -> Snippet {
+        Snippet {
+            id: Snippet::generate_id(),
+            name: name.into(),
+            description: String::new(),
+            content: "make deploy".into(),
+            kind: SnippetKind::Shell,
+            tags: vec![],
+            variables: vec![],
+            database: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_discover_walks_up_from_start() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("a/b/c");
+        fs::create_dir_all(&nested).unwrap();
+        assert_eq!(discover(&nested), None);
+
+        fs::write(dir.path().join("a").join(PROJECT_FILE), "snippets: []\n").unwrap();
+        assert_eq!(
+            discover(&nested),
+            Some(dir.path().join("a").join(PROJECT_FILE))
+        );
+
+        fs::write(nested.join(PROJECT_FILE), "snippets: []\n").unwrap();
+        assert_eq!(discover(&nested), Some(nested.join(PROJECT_FILE)));
+    }
+
+    #[test]
+    fn test_save_creates_and_appends() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = ProjectFile::new(dir.path().join(PROJECT_FILE), dir.path());
+        assert!(project.load().unwrap().snippets.is_empty());
+
+        project.save(sample_snippet("deploy")).unwrap();
+        project.save(sample_snippet("seed")).unwrap();
+        assert!(project.save(sample_snippet("Deploy")).is_err());
+
+        let names: Vec<String> = project
+            .load()
+            .unwrap()
+            .snippets
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, vec!["deploy", "seed"]);
+        assert!(project.restore_backup(Path::new("x.yml")).is_err());
+    }
+
+    #[test]
+    fn test_load_accepts_snippets_without_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(PROJECT_FILE);
+        fs::write(
+            &path,
+            "snippets:\n- name: deploy\n  description: ''\n  content: make deploy\n  kind: shell\n  tags: []\n",
+        )
+        .unwrap();
+
+        let project = ProjectFile::new(path.clone(), dir.path());
+        let store = project.load().unwrap();
+        assert!(!store.snippets[0].id.is_empty());
+        assert_eq!(store.snippets[0].id, project.load().unwrap().snippets[0].id);
+        assert!(!fs::read_to_string(path).unwrap().contains("id:"));
+    }
+
+    #[test]
+    fn test_trust_is_tied_to_file_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(PROJECT_FILE);
+        fs::write(&path, "snippets: []\n").unwrap();
+        let trust = TrustList::new(&dir.path().join("state"));
+
+        assert!(!trust.is_trusted(&path).unwrap());
+        trust.trust(&path).unwrap();
+        assert!(trust.is_trusted(&path).unwrap());
+
+        fs::write(&path, "snippets: []\n# changed\n").unwrap();
+        assert!(!trust.is_trusted(&path).unwrap());
+
+        trust.trust(&path).unwrap();
+        assert!(trust.revoke(&path).unwrap());
+        assert!(!trust.revoke(&path).unwrap());
+        assert!(!trust.is_trusted(&path).unwrap());
+    }
+}
@@ -0,0 +1,145 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+use crate::config::BackupConfig;
+
+pub const BACKUP_TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H-%M-%SZ";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackupFile {
+    pub path: PathBuf,
+    pub created_at: DateTime<Utc>,
+    pub size: u64,
+}
+
+pub fn backup_timestamp(path: &Path) -> Option<DateTime<Utc>> {
+    let stem = path.file_stem()?.to_str()?;
+    NaiveDateTime::parse_from_str(stem, BACKUP_TIMESTAMP_FORMAT)
+        .ok()
+        .map(|dt| dt.and_utc())
+}
+
+pub fn plan_prune(
+    backups: &[BackupFile],
+    policy: &BackupConfig,
+    now: DateTime<Utc>,
+) -> Vec<BackupFile> {
+    let mut newest_first: Vec<&BackupFile> = backups.iter().collect();
+    newest_first.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+
+    let mut keep: HashSet<&Path> = newest_first
+        .iter()
+        .take(policy.keep_last)
+        .map(|b| b.path.as_path())
+        .collect();
+
+    let mut seen_days = HashSet::new();
+    for backup in &newest_first {
+        let age_days = (now - backup.created_at).num_days();
+        if age_days < i64::from(policy.keep_daily_days)
+            && seen_days.insert(backup.created_at.date_naive())
+        {
+            keep.insert(backup.path.as_path());
+        }
+    }
+
+    if let Some(max) = policy.max_total_size {
+        let mut total = 0;
+        for (i, backup) in newest_first.iter().enumerate() {
+            if !keep.contains(backup.path.as_path()) {
+                continue;
+            }
+
+            total += backup.size;
+            if i > 0 && total > max.0 {
+                keep.remove(backup.path.as_path());
+            }
+        }
+    }
+
+    newest_first
+        .into_iter()
+        .filter(|b| !keep.contains(b.path.as_path()))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ByteSize;
+    use chrono::Duration;
+
+    fn backup(now: DateTime<Utc>, age_hours: i64, size: u64) -> BackupFile {
+        let created_at = now - Duration::hours(age_hours);
+        BackupFile {
+            path: PathBuf::from(format!(
+                "{}.yml",
+                created_at.format(BACKUP_TIMESTAMP_FORMAT)
+            )),
+            created_at,
+            size,
+        }
+    }
+
+    fn policy(keep_last: usize, keep_daily_days: u32, max: Option<u64>) -> BackupConfig {
+        BackupConfig {
+            keep_last,
+            keep_daily_days,
+            max_total_size: max.map(ByteSize),
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2025-06-15T12:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn test_backup_timestamp_from_file_name() {
+        let ts = backup_timestamp(Path::new("/tmp/2025-06-15T12-30-05Z.yml")).unwrap();
+        assert_eq!(ts.to_rfc3339(), "2025-06-15T12:30:05+00:00");
+        assert!(backup_timestamp(Path::new("notes.yml")).is_none());
+    }
+
+    #[test]
+    fn test_keep_last_prunes_oldest() {
+        let backups: Vec<_> = (0..5).map(|h| backup(now(), h, 10)).collect();
+
+        let pruned = plan_prune(&backups, &policy(3, 0, None), now());
+
+        assert_eq!(pruned, vec![backups[3].clone(), backups[4].clone()]);
+    }
+
+    #[test]
+    fn test_keep_daily_keeps_newest_per_day() {
+        let backups = vec![
+            backup(now(), 0, 10),
+            backup(now(), 1, 10),
+            backup(now(), 24, 10),
+            backup(now(), 25, 10),
+            backup(now(), 24 * 10, 10),
+        ];
+
+        let pruned = plan_prune(&backups, &policy(1, 3, None), now());
+
+        assert_eq!(
+            pruned,
+            vec![backups[1].clone(), backups[3].clone(), backups[4].clone()]
+        );
+    }
+
+    #[test]
+    fn test_max_total_size_drops_oldest_kept_but_never_newest() {
+        let backups: Vec<_> = (0..3).map(|h| backup(now(), h, 100)).collect();
+
+        let pruned = plan_prune(&backups, &policy(10, 0, Some(250)), now());
+        assert_eq!(pruned, vec![backups[2].clone()]);
+
+        let pruned = plan_prune(&backups, &policy(10, 0, Some(1)), now());
+        assert_eq!(pruned, vec![backups[1].clone(), backups[2].clone()]);
+    }
+}
@@ -0,0 +1,482 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{Connection, OptionalExtension, Transaction, TransactionBehavior, params};
+
+use crate::{
+    models::{PartialSnippet, Snippet, SnippetStore},
+    storage::{
+        StorageError,
+        backend::{BackendKind, SnippetBackend},
+    },
+};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS snippets (
+        id TEXT PRIMARY KEY,
+        position INTEGER NOT NULL,
+        name TEXT NOT NULL,
+        description TEXT NOT NULL,
+        content TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        variables TEXT NOT NULL,
+        database TEXT,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS snippets_name ON snippets (name COLLATE NOCASE);
+    CREATE TABLE IF NOT EXISTS snippet_tags (
+        snippet_id TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        tag TEXT NOT NULL,
+        tag_key TEXT NOT NULL DEFAULT '',
+        PRIMARY KEY (snippet_id, position)
+    );
+";
+
+const TAG_INDEX: &str = "
+    DROP INDEX IF EXISTS snippet_tags_tag;
+    CREATE INDEX IF NOT EXISTS snippet_tags_key ON snippet_tags (tag_key);
+";
+
+impl From<rusqlite::Error> for StorageError {
+    fn from(e: rusqlite::Error) -> Self {
+        StorageError::Conflict(format!("Database error: {}", e))
+    }
+}
+
+pub struct SqliteBackend {
+    path: PathBuf,
+}
+
+impl SqliteBackend {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    fn connect(&self) -> Result<Connection, StorageError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut conn = Connection::open(&self.path)?;
+        conn.execute_batch(SCHEMA)?;
+        if !has_tag_keys(&conn)? {
+            add_tag_keys(&mut conn)?;
+        }
+        Ok(conn)
+    }
+}
+
+impl SnippetBackend for SqliteBackend {
+    fn kind(&self) -> BackendKind {
+        BackendKind::Sqlite
+    }
+
+    fn exists(&self) -> bool {
+        self.path.is_file()
+    }
+
+    fn is_transactional(&self) -> bool {
+        true
+    }
+
+    fn find_by_name(&self, name: &str) -> Result<Option<Snippet>, StorageError> {
+        if !self.exists() {
+            return Ok(None);
+        }
+
+        let conn = self.connect()?;
+        let id: Option<String> = conn
+            .query_row(
+                "SELECT id FROM snippets WHERE name = ?1 COLLATE NOCASE",
+                [name],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(match id {
+            Some(id) => read_snippets(&conn, Some(&id))?.into_iter().next(),
+            None => None,
+        })
+    }
+
+    fn find_by_tag(&self, tag: &str) -> Result<Vec<Snippet>, StorageError> {
+        if !self.exists() {
+            return Ok(vec![]);
+        }
+
+        let conn = self.connect()?;
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT snippets.id, snippets.position FROM snippets
+             JOIN snippet_tags ON snippet_tags.snippet_id = snippets.id
+             WHERE snippet_tags.tag_key = ?1
+             ORDER BY snippets.position",
+        )?;
+        let ids: Vec<String> = stmt
+            .query_map([tag_key(tag)], |row| row.get(0))?
+            .collect::<Result<_, _>>()?;
+
+        let mut snippets = Vec::new();
+        for id in ids {
+            snippets.extend(read_snippets(&conn, Some(&id))?);
+        }
+        Ok(snippets)
+    }
+
+    fn read(&self) -> Result<SnippetStore, StorageError> {
+        if !self.exists() {
+            return Ok(SnippetStore::default());
+        }
+
+        let conn = self.connect()?;
+        Ok(SnippetStore {
+            snippets: read_snippets(&conn, None)?,
+        })
+    }
+
+    fn write(&self, store: &SnippetStore) -> Result<(), StorageError> {
+        let mut conn = self.connect()?;
+        let tx = conn.transaction()?;
+
+        let existing: HashMap<String, (usize, Snippet)> = read_snippets(&tx, None)?
+            .into_iter()
+            .enumerate()
+            .map(|(position, snippet)| (snippet.id.clone(), (position, snippet)))
+            .collect();
+
+        for (position, snippet) in store.snippets.iter().enumerate() {
+            let unchanged = existing
+                .get(&snippet.id)
+                .is_some_and(|(p, old)| *p == position && same_snippet(old, snippet));
+            if !unchanged {
+                upsert_snippet(&tx, position, snippet)?;
+            }
+        }
+
+        for id in existing.keys() {
+            if !store.snippets.iter().any(|s| &s.id == id) {
+                tx.execute("DELETE FROM snippet_tags WHERE snippet_id = ?1", [id])?;
+                tx.execute("DELETE FROM snippets WHERE id = ?1", [id])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<(), StorageError> {
+        if self.exists() {
+            fs::remove_file(&self.path)?;
+        }
+        Ok(())
+    }
+}
+
+fn tag_key(tag: &str) -> String {
+    tag.to_lowercase()
+}
+
+fn has_tag_keys(conn: &Connection) -> Result<bool, StorageError> {
+    let indexed = conn
+        .prepare("SELECT 1 FROM sqlite_master WHERE type = 'index' AND name = 'snippet_tags_key'")?
+        .exists([])?;
+    Ok(indexed)
+}
+
+fn add_tag_keys(conn: &mut Connection) -> Result<(), StorageError> {
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    if has_tag_keys(&tx)? {
+        return Ok(());
+    }
+
+    let has_column = tx
+        .prepare("SELECT 1 FROM pragma_table_info('snippet_tags') WHERE name = 'tag_key'")?
+        .exists([])?;
+    if !has_column {
+        tx.execute_batch("ALTER TABLE snippet_tags ADD COLUMN tag_key TEXT NOT NULL DEFAULT ''")?;
+    }
+
+    let tags: Vec<(i64, String)> = tx
+        .prepare("SELECT rowid, tag FROM snippet_tags")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+    for (rowid, tag) in tags {
+        tx.execute(
+            "UPDATE snippet_tags SET tag_key = ?1 WHERE rowid = ?2",
+            params![tag_key(&tag), rowid],
+        )?;
+    }
+
+    tx.execute_batch(TAG_INDEX)?;
+    tx.commit()?;
+    Ok(())
+}
+
+fn same_snippet(a: &Snippet, b: &Snippet) -> bool {
+    a.id == b.id
+        && a.created_at == b.created_at
+        && a.updated_at == b.updated_at
+        && PartialSnippet::from(a) == PartialSnippet::from(b)
+}
+
+fn format_time(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+fn parse_time(raw: &str) -> rusqlite::Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|e| conversion_error(0, e.into()))
+}
+
+fn conversion_error(
+    column: usize,
+    error: Box<dyn std::error::Error + Send + Sync>,
+) -> rusqlite::Error {
+    rusqlite::Error::FromSqlConversionFailure(column, rusqlite::types::Type::Text, error)
+}
+
+fn upsert_snippet(
+    tx: &Transaction,
+    position: usize,
+    snippet: &Snippet,
+) -> Result<(), StorageError> {
+    tx.execute(
+        "INSERT OR REPLACE INTO snippets
+            (id, position, name, description, content, kind, variables, database, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![
+            snippet.id,
+            position as i64,
+            snippet.name,
+            snippet.description,
+            snippet.content,
+            snippet.kind.to_string(),
+            serde_yaml::to_string(&snippet.variables)?,
+            snippet.database,
+            format_time(&snippet.created_at),
+            format_time(&snippet.updated_at),
+        ],
+    )?;
+
+    tx.execute(
+        "DELETE FROM snippet_tags WHERE snippet_id = ?1",
+        [&snippet.id],
+    )?;
+    for (position, tag) in snippet.tags.iter().enumerate() {
+        tx.execute(
+            "INSERT INTO snippet_tags (snippet_id, position, tag, tag_key) VALUES (?1, ?2, ?3, ?4)",
+            params![snippet.id, position as i64, tag, tag_key(tag)],
+        )?;
+    }
+
+    Ok(())
+}
+
+fn read_snippets(conn: &Connection, id: Option<&str>) -> Result<Vec<Snippet>, StorageError> {
+    let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+    let mut stmt = conn.prepare(
+        "SELECT snippet_id, tag FROM snippet_tags
+         WHERE ?1 IS NULL OR snippet_id = ?1
+         ORDER BY snippet_id, position",
+    )?;
+    let rows = stmt.query_map([id], |row| Ok((row.get::<_, String>(0)?, row.get(1)?)))?;
+    for row in rows {
+        let (snippet_id, tag) = row?;
+        tags.entry(snippet_id).or_default().push(tag);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT id, name, description, content, kind, variables, database, created_at, updated_at
+         FROM snippets
+         WHERE ?1 IS NULL OR id = ?1
+         ORDER BY position",
+    )?;
+    let rows = stmt.query_map([id], |row| {
+        let kind: String = row.get(4)?;
+        let variables: String = row.get(5)?;
+        Ok(Snippet {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            description: row.get(2)?,
+            content: row.get(3)?,
+            kind: kind
+                .parse()
+                .map_err(|e: String| conversion_error(4, e.into()))?,
+            tags: vec![],
+            variables: serde_yaml::from_str(&variables)
+                .map_err(|e| conversion_error(5, e.into()))?,
+            database: row.get(6)?,
+            created_at: parse_time(&row.get::<_, String>(7)?)?,
+            updated_at: parse_time(&row.get::<_, String>(8)?)?,
+        })
+    })?;
+
+    let mut snippets = Vec::new();
+    for row in rows {
+        let mut snippet = row?;
+        snippet.tags = tags.remove(&snippet.id).unwrap_or_default();
+        snippets.push(snippet);
+    }
+    Ok(snippets)
+}
+
+pub fn database_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("snippets.db")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{SnippetKind, Variable};
+
+    fn sample_snippet(name: &str, tags: &[&str]) -> Snippet {
+        Snippet {
+            id: Snippet::generate_id(),
+            name: name.into(),
+            description: "desc".into(),
+            content: "echo 'hi'\n".into(),
+            kind: SnippetKind::Python,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            variables: vec![Variable {
+                name: "ns".into(),
+                command: "kubectl get ns".into(),
+            }],
+            database: Some("app.db".into()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn backend() -> (tempfile::TempDir, SqliteBackend) {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = SqliteBackend::new(database_path(dir.path()));
+        (dir, backend)
+    }
+
+    #[test]
+    fn test_round_trips_snippets_in_order() {
+        let (_dir, backend) = backend();
+        assert!(!backend.exists());
+        assert!(backend.read().unwrap().snippets.is_empty());
+
+        let store = SnippetStore {
+            snippets: vec![
+                sample_snippet("b", &["ops", "k8s"]),
+                sample_snippet("a", &[]),
+            ],
+        };
+        backend.write(&store).unwrap();
+
+        let loaded = backend.read().unwrap();
+        assert_eq!(loaded.snippets.len(), 2);
+        for (a, b) in store.snippets.iter().zip(&loaded.snippets) {
+            assert!(same_snippet(a, b));
+        }
+    }
+
+    #[test]
+    fn test_write_updates_and_deletes_rows() {
+        let (_dir, backend) = backend();
+        let keep = sample_snippet("keep", &["a"]);
+        let drop = sample_snippet("drop", &["a"]);
+        backend
+            .write(&SnippetStore {
+                snippets: vec![keep.clone(), drop],
+            })
+            .unwrap();
+
+        let mut edited = keep.clone();
+        edited.tags = vec!["b".into()];
+        backend
+            .write(&SnippetStore {
+                snippets: vec![edited],
+            })
+            .unwrap();
+
+        let loaded = backend.read().unwrap();
+        assert_eq!(loaded.snippets.len(), 1);
+        assert_eq!(loaded.snippets[0].tags, vec!["b"]);
+        assert!(backend.find_by_tag("a").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_indexed_lookups() {
+        let (_dir, backend) = backend();
+        backend
+            .write(&SnippetStore {
+                snippets: vec![
+                    sample_snippet("Deploy", &["K8s"]),
+                    sample_snippet("seed", &["db"]),
+                ],
+            })
+            .unwrap();
+
+        assert_eq!(
+            backend.find_by_name("deploy").unwrap().map(|s| s.name),
+            Some("Deploy".to_string())
+        );
+        assert!(backend.find_by_name("missing").unwrap().is_none());
+        assert_eq!(backend.find_by_tag("k8s").unwrap()[0].name, "Deploy");
+    }
+
+    #[test]
+    fn test_tags_match_case_insensitively_beyond_ascii() {
+        let (_dir, backend) = backend();
+        backend
+            .write(&SnippetStore {
+                snippets: vec![sample_snippet("deploy", &["Überprüfung"])],
+            })
+            .unwrap();
+
+        assert_eq!(backend.find_by_tag("überprüfung").unwrap().len(), 1);
+        assert_eq!(backend.find_by_tag("ÜBERPRÜFUNG").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_tag_keys_are_added_to_older_databases() {
+        let (_dir, backend) = backend();
+        let conn = Connection::open(&backend.path).unwrap();
+        conn.execute_batch(&SCHEMA.replace("tag_key TEXT NOT NULL DEFAULT '',", ""))
+            .unwrap();
+        let mut snippet = sample_snippet("deploy", &["K8s"]);
+        snippet.tags.clear();
+        let tx = conn.unchecked_transaction().unwrap();
+        upsert_snippet(&tx, 0, &snippet).unwrap();
+        tx.execute(
+            "INSERT INTO snippet_tags (snippet_id, position, tag) VALUES (?1, 0, 'K8s')",
+            [&snippet.id],
+        )
+        .unwrap();
+        tx.commit().unwrap();
+        drop(conn);
+
+        assert_eq!(backend.find_by_tag("k8s").unwrap()[0].name, "deploy");
+    }
+
+    #[test]
+    fn test_corrupt_rows_are_errors() {
+        let (_dir, backend) = backend();
+        backend
+            .write(&SnippetStore {
+                snippets: vec![sample_snippet("deploy", &[])],
+            })
+            .unwrap();
+        let conn = backend.connect().unwrap();
+
+        conn.execute("UPDATE snippets SET kind = 'cobol'", [])
+            .unwrap();
+        assert!(backend.read().is_err());
+
+        conn.execute(
+            "UPDATE snippets SET kind = 'shell', variables = 'nope: ['",
+            [],
+        )
+        .unwrap();
+        assert!(backend.read().is_err());
+    }
+}
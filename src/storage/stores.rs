@@ -0,0 +1,234 @@
+use std::{fs, path::PathBuf};
+
+use crate::{
+    paths::Paths,
+    storage::{StorageError, StoreCatalog, atomic::write_atomic, file_storage::FileStorage},
+};
+
+pub const DEFAULT_STORE: &str = "default";
+pub const STORE_ENV: &str = "MARKIT_STORE";
+
+pub struct StoreRegistry {
+    root: PathBuf,
+    state_root: PathBuf,
+}
+
+impl StoreRegistry {
+    pub fn new(paths: &Paths) -> Self {
+        Self {
+            root: paths.data.clone(),
+            state_root: paths.state.clone(),
+        }
+    }
+
+    pub fn open(&self, name: &str) -> FileStorage {
+        FileStorage::with_dirs(self.path_for(name), self.state_path_for(name))
+    }
+
+    pub fn path_for(&self, name: &str) -> PathBuf {
+        if name == DEFAULT_STORE {
+            self.root.clone()
+        } else {
+            self.stores_dir().join(name)
+        }
+    }
+
+    fn state_path_for(&self, name: &str) -> PathBuf {
+        if name == DEFAULT_STORE {
+            self.state_root.clone()
+        } else {
+            self.state_root.join("stores").join(name)
+        }
+    }
+
+    pub fn exists(&self, name: &str) -> bool {
+        name == DEFAULT_STORE || (validate_store_name(name).is_ok() && self.path_for(name).is_dir())
+    }
+
+    pub fn resolve(&self, flag: Option<String>) -> String {
+        flag.or_else(|| std::env::var(STORE_ENV).ok().filter(|s| !s.is_empty()))
+            .unwrap_or_else(|| self.default_store())
+    }
+
+    fn stores_dir(&self) -> PathBuf {
+        self.root.join("stores")
+    }
+
+    fn default_file(&self) -> PathBuf {
+        self.root.join("default_store")
+    }
+}
+
+pub fn validate_store_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid store name '{}': use letters, digits, '-' and '_' only.",
+            name
+        ))
+    }
+}
+
+impl StoreCatalog for StoreRegistry {
+    fn list_stores(&self) -> Result<Vec<String>, StorageError> {
+        let mut names = vec![DEFAULT_STORE.to_string()];
+
+        if let Ok(entries) = fs::read_dir(self.stores_dir()) {
+            let mut named: Vec<String> = entries
+                .filter_map(|e| {
+                    let entry = e.ok()?;
+                    entry.file_type().ok()?.is_dir().then_some(())?;
+                    entry.file_name().into_string().ok()
+                })
+                .collect();
+            named.sort();
+            names.extend(named);
+        }
+
+        Ok(names)
+    }
+
+    fn create_store(&self, name: &str) -> Result<(), StorageError> {
+        if self.exists(name) {
+            return Err(StorageError::Conflict(format!(
+                "Store '{}' already exists.",
+                name
+            )));
+        }
+
+        fs::create_dir_all(self.path_for(name))?;
+        Ok(())
+    }
+
+    fn remove_store(&self, name: &str) -> Result<(), StorageError> {
+        if name == DEFAULT_STORE {
+            return Err(StorageError::Conflict(
+                "The default store cannot be removed.".to_string(),
+            ));
+        }
+        if !self.exists(name) {
+            return Err(StorageError::Conflict(format!(
+                "Store '{}' does not exist.",
+                name
+            )));
+        }
+
+        let was_default = self.default_store() == name;
+        fs::remove_dir_all(self.path_for(name))?;
+        let state = self.state_path_for(name);
+        if state.is_dir() {
+            fs::remove_dir_all(state)?;
+        }
+        if was_default {
+            fs::remove_file(self.default_file())?;
+        }
+        Ok(())
+    }
+
+    fn default_store(&self) -> String {
+        fs::read_to_string(self.default_file())
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty() && self.exists(s))
+            .unwrap_or_else(|| DEFAULT_STORE.to_string())
+    }
+
+    fn set_default_store(&self, name: &str) -> Result<(), StorageError> {
+        if !self.exists(name) {
+            return Err(StorageError::Conflict(format!(
+                "Store '{}' does not exist.",
+                name
+            )));
+        }
+
+        fs::create_dir_all(&self.root)?;
+        write_atomic(&self.default_file(), format!("{}\n", name).as_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> (tempfile::TempDir, StoreRegistry) {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = StoreRegistry::new(&Paths::single(dir.path().to_path_buf()));
+        (dir, registry)
+    }
+
+    #[test]
+    fn test_default_store_lives_in_root() {
+        let (dir, registry) = registry();
+
+        assert_eq!(registry.path_for(DEFAULT_STORE), dir.path());
+        assert_eq!(registry.path_for("work"), dir.path().join("stores/work"));
+        assert_eq!(registry.list_stores().unwrap(), vec!["default"]);
+    }
+
+    #[test]
+    fn test_remove_store_deletes_its_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut paths = Paths::single(dir.path().join("data"));
+        paths.state = dir.path().join("state");
+        let registry = StoreRegistry::new(&paths);
+
+        registry.create_store("work").unwrap();
+        fs::create_dir_all(paths.state.join("stores/work/backups")).unwrap();
+
+        registry.remove_store("work").unwrap();
+        assert!(!paths.state.join("stores/work").exists());
+        assert!(!paths.data.join("stores/work").exists());
+    }
+
+    #[test]
+    fn test_create_list_and_remove_stores() {
+        let (_dir, registry) = registry();
+
+        registry.create_store("work").unwrap();
+        registry.create_store("personal").unwrap();
+        assert!(registry.create_store("work").is_err());
+        assert_eq!(
+            registry.list_stores().unwrap(),
+            vec!["default", "personal", "work"]
+        );
+
+        registry.remove_store("work").unwrap();
+        assert!(!registry.exists("work"));
+        assert!(registry.remove_store("work").is_err());
+        assert!(registry.remove_store(DEFAULT_STORE).is_err());
+    }
+
+    #[test]
+    fn test_default_store_selection() {
+        let (_dir, registry) = registry();
+        assert_eq!(registry.default_store(), DEFAULT_STORE);
+        assert!(registry.set_default_store("work").is_err());
+
+        registry.create_store("work").unwrap();
+        registry.set_default_store("work").unwrap();
+        assert_eq!(registry.default_store(), "work");
+        assert_eq!(registry.resolve(Some("other".into())), "other");
+
+        registry.remove_store("work").unwrap();
+        assert_eq!(registry.default_store(), DEFAULT_STORE);
+
+        registry.create_store("work").unwrap();
+        assert_eq!(registry.default_store(), DEFAULT_STORE);
+    }
+
+    #[test]
+    fn test_validate_store_name() {
+        assert!(validate_store_name("work_2-a").is_ok());
+        assert!(validate_store_name("").is_err());
+        assert!(validate_store_name("../etc").is_err());
+        assert!(validate_store_name("a b").is_err());
+        assert!(!StoreRegistry::new(&Paths::single(PathBuf::from("/"))).exists(".."));
+    }
+}
@@ -0,0 +1,164 @@
+use std::{
+    cell::OnceCell,
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::storage::{
+    SecretStore, StorageError,
+    crypto::{self, StoreKey, read_file, write_file},
+};
+
+const VAULT_DIR: &str = "vault";
+const SECRETS_FILE: &str = "secrets.yml";
+
+type PassphraseSource = Box<dyn Fn() -> Option<String>>;
+
+pub struct SecretVault {
+    dir: PathBuf,
+    key: OnceCell<StoreKey>,
+    passphrase: Option<PassphraseSource>,
+}
+
+impl SecretVault {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            dir: data_dir.join(VAULT_DIR),
+            key: OnceCell::new(),
+            passphrase: None,
+        }
+    }
+
+    pub fn with_passphrase(mut self, source: impl Fn() -> Option<String> + 'static) -> Self {
+        self.passphrase = Some(Box::new(source));
+        self
+    }
+
+    fn secrets_path(&self) -> PathBuf {
+        self.dir.join(SECRETS_FILE)
+    }
+
+    fn key(&self) -> Result<&StoreKey, StorageError> {
+        if let Some(key) = self.key.get() {
+            return Ok(key);
+        }
+        if !self.is_initialized() {
+            return Err(StorageError::Conflict(
+                "No secrets saved yet. Add one with `markit secret set NAME`.".to_string(),
+            ));
+        }
+
+        let passphrase = self
+            .passphrase
+            .as_ref()
+            .and_then(|source| source())
+            .ok_or_else(|| {
+                StorageError::Conflict("A passphrase is needed to open the secrets.".to_string())
+            })?;
+        let key = StoreKey::unlock(&self.dir, &passphrase)?;
+        Ok(self.key.get_or_init(|| key))
+    }
+
+    fn read(&self) -> Result<BTreeMap<String, String>, StorageError> {
+        let key = self.key()?;
+        if !self.secrets_path().is_file() {
+            return Ok(BTreeMap::new());
+        }
+        Ok(serde_yaml::from_slice(&read_file(
+            &self.secrets_path(),
+            Some(key),
+        )?)?)
+    }
+
+    fn write(&self, secrets: &BTreeMap<String, String>) -> Result<(), StorageError> {
+        let yaml = serde_yaml::to_string(secrets)?;
+        write_file(&self.secrets_path(), yaml.as_bytes(), Some(self.key()?))
+    }
+}
+
+impl SecretStore for SecretVault {
+    fn is_initialized(&self) -> bool {
+        crypto::is_encrypted(&self.dir)
+    }
+
+    fn initialize(&self, passphrase: &str) -> Result<(), StorageError> {
+        if self.is_initialized() {
+            return Err(StorageError::Conflict(
+                "The secrets vault already exists.".to_string(),
+            ));
+        }
+
+        fs::create_dir_all(&self.dir)?;
+        let key = StoreKey::create(&self.dir, passphrase)?;
+        let _ = self.key.set(key);
+        self.write(&BTreeMap::new())
+    }
+
+    fn secret_names(&self) -> Result<Vec<String>, StorageError> {
+        Ok(self.read()?.into_keys().collect())
+    }
+
+    fn get_secret(&self, name: &str) -> Result<Option<String>, StorageError> {
+        Ok(self.read()?.remove(name))
+    }
+
+    fn set_secret(&self, name: &str, value: &str) -> Result<(), StorageError> {
+        let mut secrets = self.read()?;
+        secrets.insert(name.to_string(), value.to_string());
+        self.write(&secrets)
+    }
+
+    fn remove_secret(&self, name: &str) -> Result<bool, StorageError> {
+        let mut secrets = self.read()?;
+        if secrets.remove(name).is_none() {
+            return Ok(false);
+        }
+        self.write(&secrets)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secrets_are_sealed_on_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = SecretVault::new(dir.path());
+        assert!(vault.secret_names().is_err());
+
+        vault.initialize("hunter2").unwrap();
+        vault.set_secret("GITHUB_TOKEN", "ghp_abc123").unwrap();
+        vault.set_secret("DB_PASSWORD", "s3cret").unwrap();
+
+        let raw = fs::read(vault.secrets_path()).unwrap();
+        assert!(crypto::is_sealed(&raw));
+        assert!(!String::from_utf8_lossy(&raw).contains("ghp_abc123"));
+
+        let reopened = SecretVault::new(dir.path()).with_passphrase(|| Some("hunter2".into()));
+        assert_eq!(
+            reopened.secret_names().unwrap(),
+            vec!["DB_PASSWORD", "GITHUB_TOKEN"]
+        );
+        assert_eq!(
+            reopened.get_secret("GITHUB_TOKEN").unwrap().as_deref(),
+            Some("ghp_abc123")
+        );
+        assert!(reopened.remove_secret("DB_PASSWORD").unwrap());
+        assert!(!reopened.remove_secret("DB_PASSWORD").unwrap());
+    }
+
+    #[test]
+    fn test_wrong_passphrase_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        SecretVault::new(dir.path()).initialize("hunter2").unwrap();
+
+        let vault = SecretVault::new(dir.path()).with_passphrase(|| Some("wrong".into()));
+        assert!(vault.get_secret("TOKEN").is_err());
+
+        let vault = SecretVault::new(dir.path());
+        assert!(vault.get_secret("TOKEN").is_err());
+    }
+}
@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+
+const SECRET_OPEN: &str = "${secret:";
+const SECRET_ENV_PREFIX: &str = "MARKIT_SECRET_";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placeholder {
+    pub name: String,
+    pub default: Option<String>,
+}
+
+struct Token {
+    start: usize,
+    end: usize,
+    placeholder: Placeholder,
+}
+
+pub fn parse_placeholders(content: &str) -> Result<Vec<Placeholder>, String> {
+    let mut placeholders: Vec<Placeholder> = Vec::new();
+
+    for token in tokenize(content) {
+        let found = token.placeholder;
+        let Some(existing) = placeholders.iter_mut().find(|p| p.name == found.name) else {
+            placeholders.push(found);
+            continue;
+        };
+
+        match (&existing.default, found.default) {
+            (Some(a), Some(b)) if *a != b => {
+                return Err(format!(
+                    "placeholder '{}' has two different defaults: '{}' and '{}'",
+                    found.name, a, b
+                ));
+            }
+            (None, Some(default)) => existing.default = Some(default),
+            _ => {}
+        }
+    }
+
+    Ok(placeholders)
+}
+
+pub fn render(content: &str, values: &HashMap<String, String>) -> String {
+    let mut rendered = String::with_capacity(content.len());
+    let mut cursor = 0;
+
+    for token in tokenize(content) {
+        rendered.push_str(&content[cursor..token.start]);
+        match values.get(&token.placeholder.name) {
+            Some(value) => rendered.push_str(value),
+            None => rendered.push_str(&content[token.start..token.end]),
+        }
+        cursor = token.end;
+    }
+
+    rendered.push_str(&content[cursor..]);
+    rendered
+}
+
+pub fn parse_assignment(input: &str) -> Result<(String, String), String> {
+    let (key, value) = input
+        .split_once('=')
+        .ok_or_else(|| format!("invalid KEY=VALUE: no `=` found in '{}'", input))?;
+
+    let key = key.trim();
+    if !is_valid_name(key) {
+        return Err(format!("invalid placeholder name '{}'", key));
+    }
+
+    Ok((key.to_string(), value.to_string()))
+}
+
+pub fn parse_secret_refs(content: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+
+    for (_, _, name) in secret_refs(content) {
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+
+    names
+}
+
+pub fn secrets_to_env(content: &str) -> String {
+    let mut rendered = String::with_capacity(content.len());
+    let mut cursor = 0;
+
+    for (start, end, name) in secret_refs(content) {
+        rendered.push_str(&content[cursor..start]);
+        let reference = format!("${{{}}}", secret_env_name(name));
+        match quote_at(content, start) {
+            Some('"') => rendered.push_str(&reference),
+            Some(_) => rendered.push_str(&format!("'\"{}\"'", reference)),
+            None => rendered.push_str(&format!("\"{}\"", reference)),
+        }
+        cursor = end;
+    }
+
+    rendered.push_str(&content[cursor..]);
+    rendered
+}
+
+fn quote_at(content: &str, position: usize) -> Option<char> {
+    let mut quote = None;
+    let mut chars = content[..position].chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some('\''), '\'') => quote = None,
+            (Some('\''), _) => {}
+            (_, '\\') => {
+                chars.next();
+            }
+            (None, '\'' | '"') => quote = Some(c),
+            (Some('"'), '"') => quote = None,
+            _ => {}
+        }
+    }
+
+    quote
+}
+
+pub fn secret_env_name(name: &str) -> String {
+    format!("{}{}", SECRET_ENV_PREFIX, name)
+}
+
+pub fn is_valid_secret_name(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn secret_refs(content: &str) -> Vec<(usize, usize, &str)> {
+    let mut refs = Vec::new();
+    let mut offset = 0;
+
+    while let Some(open) = content[offset..].find(SECRET_OPEN) {
+        let start = offset + open;
+        let name_start = start + SECRET_OPEN.len();
+        let Some(close) = content[name_start..].find('}') else {
+            break;
+        };
+        let name = &content[name_start..name_start + close];
+
+        if is_valid_secret_name(name) {
+            refs.push((start, name_start + close + 1, name));
+            offset = name_start + close + 1;
+        } else {
+            offset = name_start;
+        }
+    }
+
+    refs
+}
+
+fn tokenize(content: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+
+    while let Some(open) = content[offset..].find("{{") {
+        let start = offset + open;
+        let Some(close) = content[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + close + 2;
+        let inner = &content[start + 2..end - 2];
+
+        match parse_inner(inner) {
+            Some(placeholder) => {
+                tokens.push(Token {
+                    start,
+                    end,
+                    placeholder,
+                });
+                offset = end;
+            }
+            None => offset = start + 2,
+        }
+    }
+
+    tokens
+}
+
+fn parse_inner(inner: &str) -> Option<Placeholder> {
+    let (name, default) = match inner.split_once(':') {
+        Some((name, default)) => (name.trim(), Some(default.to_string())),
+        None => (inner.trim(), None),
+    };
+
+    if !is_valid_name(name) {
+        return None;
+    }
+
+    Some(Placeholder {
+        name: name.to_string(),
+        default,
+    })
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_placeholders_with_defaults() {
+        let placeholders =
+            parse_placeholders("kubectl -n {{ns:default}} logs {{pod}} --context {{ ctx }}")
+                .unwrap();
+
+        assert_eq!(
+            placeholders,
+            vec![
+                Placeholder {
+                    name: "ns".into(),
+                    default: Some("default".into()),
+                },
+                Placeholder {
+                    name: "pod".into(),
+                    default: None,
+                },
+                Placeholder {
+                    name: "ctx".into(),
+                    default: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_placeholders_deduplicates() {
+        let placeholders =
+            parse_placeholders("echo {{host}} && ping {{host:localhost}} {{host:localhost}}")
+                .unwrap();
+
+        assert_eq!(placeholders.len(), 1);
+        assert_eq!(placeholders[0].name, "host");
+        assert_eq!(placeholders[0].default.as_deref(), Some("localhost"));
+    }
+
+    #[test]
+    fn test_parse_placeholders_rejects_conflicting_defaults() {
+        assert!(parse_placeholders("ssh {{host:a}} && ping {{host:b}}").is_err());
+    }
+
+    #[test]
+    fn test_parse_placeholders_ignores_invalid() {
+        let content = "echo '{{ not valid }}' {{}} {{unterminated";
+        assert!(parse_placeholders(content).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_render_replaces_all_occurrences() {
+        let rendered = render(
+            "ssh {{host}} && scp file {{host:fallback}}:/tmp",
+            &values(&[("host", "prod-1")]),
+        );
+
+        assert_eq!(rendered, "ssh prod-1 && scp file prod-1:/tmp");
+    }
+
+    #[test]
+    fn test_render_leaves_unknown_placeholders() {
+        let rendered = render("echo {{a}} {{b}}", &values(&[("a", "1")]));
+        assert_eq!(rendered, "echo 1 {{b}}");
+    }
+
+    #[test]
+    fn test_render_keeps_default_with_colon() {
+        let placeholders = parse_placeholders("curl {{url:http://localhost:8080}}").unwrap();
+        assert_eq!(
+            placeholders[0].default.as_deref(),
+            Some("http://localhost:8080")
+        );
+    }
+
+    #[test]
+    fn test_parse_assignment() {
+        assert_eq!(
+            parse_assignment("ns=prod"),
+            Ok(("ns".to_string(), "prod".to_string()))
+        );
+        assert_eq!(
+            parse_assignment("query=a=b"),
+            Ok(("query".to_string(), "a=b".to_string()))
+        );
+        assert!(parse_assignment("novalue").is_err());
+        assert!(parse_assignment("bad name=x").is_err());
+    }
+
+    #[test]
+    fn test_secret_refs_become_env_references() {
+        let content =
+            "curl -H \"Authorization: ${secret:GH_TOKEN}\" ${secret:bad-name} ${secret:GH_TOKEN}";
+
+        assert_eq!(parse_secret_refs(content), vec!["GH_TOKEN"]);
+        assert_eq!(
+            secrets_to_env(content),
+            "curl -H \"Authorization: ${MARKIT_SECRET_GH_TOKEN}\" ${secret:bad-name} \"${MARKIT_SECRET_GH_TOKEN}\""
+        );
+        assert_eq!(
+            secrets_to_env("${secret:PATH}"),
+            "\"${MARKIT_SECRET_PATH}\""
+        );
+        assert!(parse_secret_refs("echo ${HOME} {{secret}}").is_empty());
+    }
+
+    #[test]
+    fn test_secret_env_references_keep_their_quoting() {
+        assert_eq!(
+            secrets_to_env("echo \\\"${secret:Y}"),
+            "echo \\\"\"${MARKIT_SECRET_Y}\""
+        );
+        assert_eq!(
+            secrets_to_env("echo 'token ${secret:X}' \"${secret:X}\""),
+            "echo 'token '\"${MARKIT_SECRET_X}\"'' \"${MARKIT_SECRET_X}\""
+        );
+    }
+}
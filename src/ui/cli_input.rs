@@ -0,0 +1,33 @@
+use dialoguer::{Input, Password, theme::ColorfulTheme};
+
+use crate::ui::{InputPrompt, SecretPrompt};
+
+pub struct DialoguerInput;
+
+impl InputPrompt for DialoguerInput {
+    fn input(&self, message: &str, default: Option<&str>) -> Option<String> {
+        let theme = ColorfulTheme::default();
+        let mut input = Input::<String>::with_theme(&theme)
+            .with_prompt(message)
+            .allow_empty(true);
+
+        if let Some(default) = default {
+            input = input.default(default.to_string());
+        }
+
+        input.interact_text().ok()
+    }
+}
+
+impl SecretPrompt for DialoguerInput {
+    fn secret(&self, message: &str, confirm: bool) -> Option<String> {
+        let theme = ColorfulTheme::default();
+        let mut password = Password::with_theme(&theme).with_prompt(message);
+
+        if confirm {
+            password = password.with_confirmation("Repeat passphrase", "Passphrases do not match.");
+        }
+
+        password.interact().ok()
+    }
+}
@@ -1,6 +1,9 @@
-use dialoguer::{Select, theme::ColorfulTheme};
+use dialoguer::{MultiSelect, Select, theme::ColorfulTheme};
 
-use crate::{models::Snippet, ui::SelectionUI};
+use crate::{
+    models::Snippet,
+    ui::{MultiSelectUI, SelectionUI},
+};
 
 pub struct CliSelection {}
 
@@ -43,4 +46,23 @@ impl SelectionUI for CliSelection {
             .interact()
             .ok()
     }
+
+    fn with_choice_list(&self, prompt: &str, choices: &[String]) -> Option<usize> {
+        Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .items(choices)
+            .default(0)
+            .interact()
+            .ok()
+    }
+}
+
+impl MultiSelectUI for CliSelection {
+    fn with_multi_choice_list(&self, prompt: &str, choices: &[String]) -> Option<Vec<usize>> {
+        MultiSelect::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .items(choices)
+            .interact()
+            .ok()
+    }
 }
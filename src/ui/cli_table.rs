@@ -1,12 +1,13 @@
-use crate::{models::Snippet, ui::TableUI};
+use crate::{models::Snippet, storage::SnippetSources, ui::TableUI};
 use comfy_table::{Cell, Color, Row, Table, presets::UTF8_FULL};
 
-pub struct CliTable {
+pub struct CliTable<'a> {
     table: Table,
     header_color: Color,
+    sources: Option<&'a dyn SnippetSources>,
 }
 
-impl CliTable {
+impl<'a> CliTable<'a> {
     pub fn new() -> Self {
         let mut table = Table::new();
         table.load_preset(UTF8_FULL);
@@ -20,30 +21,52 @@ impl CliTable {
         Self {
             table,
             header_color,
+            sources: None,
         }
     }
+
+    pub fn with_sources(mut self, sources: &'a dyn SnippetSources) -> Self {
+        self.sources = Some(sources);
+        self
+    }
 }
 
-impl TableUI for CliTable {
+impl TableUI for CliTable<'_> {
     fn with_snippet_list(&mut self, snippets: Vec<Snippet>) -> Table {
-        self.table.set_header(vec![
+        let sources: Vec<Option<String>> = snippets
+            .iter()
+            .map(|s| self.sources.and_then(|sources| sources.source_of(&s.id)))
+            .collect();
+        let show_sources = sources.iter().any(Option::is_some);
+
+        let mut header = vec![
+            Cell::new("ID").fg(self.header_color),
             Cell::new("Name").fg(self.header_color),
             Cell::new("Description").fg(self.header_color),
-            Cell::new("Executable").fg(self.header_color),
+            Cell::new("Kind").fg(self.header_color),
             Cell::new("Created at").fg(self.header_color),
             Cell::new("Updated at").fg(self.header_color),
             Cell::new("Tags").fg(self.header_color),
-        ]);
+        ];
+        if show_sources {
+            header.push(Cell::new("Source").fg(self.header_color));
+        }
+        self.table.set_header(header);
 
-        for snippet in snippets {
-            self.table.add_row(Row::from(vec![
+        for (snippet, source) in snippets.into_iter().zip(sources) {
+            let mut row = vec![
+                Cell::new(snippet.short_id()).fg(Color::White),
                 Cell::new(snippet.name).fg(Color::White),
                 Cell::new(snippet.description).fg(Color::White),
-                Cell::new(if snippet.executable { "yes" } else { "no" }).fg(Color::White),
+                Cell::new(snippet.kind).fg(Color::White),
                 Cell::new(snippet.created_at).fg(Color::White),
                 Cell::new(snippet.updated_at).fg(Color::White),
                 Cell::new(snippet.tags.join(", ")).fg(Color::White),
-            ]));
+            ];
+            if show_sources {
+                row.push(Cell::new(source.unwrap_or_default()).fg(Color::White));
+            }
+            self.table.add_row(Row::from(row));
         }
 
         self.table.clone()
@@ -3,6 +3,7 @@ use comfy_table::Table;
 use crate::models::Snippet;
 
 pub mod cli_confirm;
+pub mod cli_input;
 pub mod cli_selection;
 pub mod cli_table;
 
@@ -13,8 +14,21 @@ pub trait TableUI {
 pub trait SelectionUI {
     fn with_snippet_list(&self, snippets: Vec<Snippet>) -> Option<Snippet>;
     fn with_backup_list(&self, backups: &[String]) -> Option<usize>;
+    fn with_choice_list(&self, prompt: &str, choices: &[String]) -> Option<usize>;
+}
+
+pub trait MultiSelectUI {
+    fn with_multi_choice_list(&self, prompt: &str, choices: &[String]) -> Option<Vec<usize>>;
 }
 
 pub trait ConfirmPrompt {
     fn confirm(&self, message: &str) -> bool;
 }
+
+pub trait InputPrompt {
+    fn input(&self, message: &str, default: Option<&str>) -> Option<String>;
+}
+
+pub trait SecretPrompt {
+    fn secret(&self, message: &str, confirm: bool) -> Option<String>;
+}